@@ -10,10 +10,11 @@ use dada_ir_ast::{
 use dada_ir_sym::{
     ir::{
         binder::{Binder, BoundTerm},
-        classes::{SymAggregate, SymClassMember, SymField},
+        classes::{SymAggregate, SymClassMember, SymField, SymVariant},
         functions::{SignatureSymbols, SymFunction, SymFunctionSignature, SymInputOutput},
         generics::{SymWhereClause, SymWhereClauseKind},
         module::{SymItem, SymModule},
+        traits::{SymImpl, SymTrait},
         types::{SymGenericKind, SymGenericTerm, SymPerm, SymPlace, SymTy},
         variables::SymVariable,
     },
@@ -49,6 +50,7 @@ impl<'db> Check<'db> for SourceFile {
 impl<'db> Check<'db> for SymModule<'db> {
     fn check(&self, db: &'db dyn crate::Db) {
         self.items(db).for_each(|item| item.check(db));
+        self.impls(db).iter().for_each(|i| i.check(db));
         self.check_use_items(db);
     }
 }
@@ -57,6 +59,7 @@ impl<'db> Check<'db> for SymItem<'db> {
     fn check(&self, db: &'db dyn crate::Db) {
         match self {
             SymItem::SymClass(sym_class) => sym_class.check(db),
+            SymItem::SymTrait(sym_trait) => sym_trait.check(db),
             SymItem::SymFunction(sym_function) => sym_function.check(db),
             SymItem::SymPrimitive(_sym_primtive) => (),
         }
@@ -66,6 +69,31 @@ impl<'db> Check<'db> for SymItem<'db> {
 impl<'db> Check<'db> for SymAggregate<'db> {
     fn check(&self, db: &'db dyn crate::Db) {
         self.members(db).iter().for_each(|member| member.check(db));
+        self.variants(db)
+            .iter()
+            .for_each(|variant| variant.check(db));
+    }
+}
+
+impl<'db> Check<'db> for SymTrait<'db> {
+    fn check(&self, db: &'db dyn crate::Db) {
+        self.check_members(db);
+        self.methods(db).iter().for_each(|method| {
+            let _ = method.checked_signature(db);
+        });
+    }
+}
+
+impl<'db> Check<'db> for SymImpl<'db> {
+    fn check(&self, db: &'db dyn crate::Db) {
+        self.check_members(db);
+        self.methods(db).iter().for_each(|method| method.check(db));
+    }
+}
+
+impl<'db> Check<'db> for SymVariant<'db> {
+    fn check(&self, db: &'db dyn crate::Db) {
+        self.fields(db).iter().for_each(|field| field.check(db));
     }
 }
 
@@ -199,13 +227,14 @@ impl<'db> Check<'db> for SymWhereClause<'db> {
     }
 }
 
-impl<'db> Check<'db> for SymWhereClauseKind {
-    fn check(&self, _db: &'db dyn crate::Db) {
+impl<'db> Check<'db> for SymWhereClauseKind<'db> {
+    fn check(&self, db: &'db dyn crate::Db) {
         match self {
             SymWhereClauseKind::Unique => (),
             SymWhereClauseKind::Shared => (),
             SymWhereClauseKind::Owned => (),
             SymWhereClauseKind::Lent => (),
+            SymWhereClauseKind::Trait(trait_ref) => trait_ref.args(db).check(db),
         }
     }
 }
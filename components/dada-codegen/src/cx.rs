@@ -1,55 +1,170 @@
-use dada_ir_sym::{ir::functions::SymFunction, ir::types::SymGenericTerm};
+use std::collections::VecDeque;
+
+use dada_ir_sym::{
+    ir::exprs::SymByteLiteralData, ir::functions::SymFunction, ir::types::SymGenericTerm,
+};
 use dada_util::{FromImpls, Map};
+use host_fn::{MEMORY_EXPORT, STACK_START_EXPORT};
 use salsa::Update;
-use wasm_encoder::{CodeSection, FunctionSection, TypeSection};
+use wasm_encoder::{
+    CodeSection, ConstExpr, DataSection, ExportKind, ExportSection, FunctionSection, GlobalSection,
+    GlobalType, ImportSection, MemorySection, MemoryType, TypeSection, ValType,
+};
 
 mod generate_expr;
 mod generate_fn;
+mod host_fn;
+mod intrinsic_fn;
 mod wasm_fn_type;
 mod wasm_repr;
 
+/// Number of 64KiB pages of linear memory the module starts with (in addition to
+/// those needed for static data). The static data for byte literals is placed at the
+/// start of memory and the stack grows upward from just after it. The heap begins
+/// at the end of the initial memory and grows the memory as needed.
+const INITIAL_MEMORY_PAGES: u64 = 16;
+
+/// Size in bytes of a page of wasm linear memory.
+const MEMORY_PAGE_SIZE: u32 = 64 * 1024;
+
+/// Alignment in bytes of the stack start.
+const STACK_ALIGNMENT: u32 = 8;
+
+/// Index of the (mutable, `i32`) global holding the address of the next free byte of the heap.
+/// Global 0 holds the stack start.
+const HEAP_POINTER_GLOBAL: u32 = 1;
+
+/// Alignment in bytes of heap allocations.
+const HEAP_ALIGNMENT: u32 = 8;
+
 /// Core codegen context.
 pub(crate) struct Cx<'db> {
     db: &'db dyn crate::Db,
     function_section: FunctionSection,
     type_section: TypeSection,
+    import_section: ImportSection,
+    export_section: ExportSection,
     code_section: CodeSection,
+    data_section: DataSection,
     functions: Map<FnKey<'db>, FnIndex>,
-    codegen_queue: Vec<CodegenQueueItem<'db>>,
+
+    /// Functions that have been declared but not yet generated, in the order they were declared.
+    /// The code section must list the function bodies in the same order as the function section.
+    codegen_queue: VecDeque<CodegenQueueItem<'db>>,
+
+    /// Address in linear memory of the data for each byte literal.
+    /// Identical literals share their data.
+    byte_literals: Map<SymByteLiteralData<'db>, u32>,
+
+    /// Number of bytes of static data placed at the start of linear memory.
+    static_data_size: u32,
 }
 
 impl<'db> Cx<'db> {
     pub fn new(db: &'db dyn crate::Db) -> Self {
-        Self {
+        let mut cx = Self {
             db,
             function_section: Default::default(),
             type_section: Default::default(),
+            import_section: Default::default(),
+            export_section: Default::default(),
             code_section: Default::default(),
+            data_section: Default::default(),
             functions: Default::default(),
             codegen_queue: Default::default(),
-        }
+            byte_literals: Default::default(),
+            static_data_size: 0,
+        };
+        cx.declare_host_fns();
+        cx.declare_alloc_fn();
+        cx
     }
 
     /// Generates all code reachable from the given fn instantiated with the given arguments.
+    /// The starting function is exported under its own name so that it can be invoked by the host.
     pub fn generate_from_fn(
         mut self,
         function: SymFunction<'db>,
         generics: Vec<SymGenericTerm<'db>>,
     ) -> wasm_encoder::Module {
-        self.declare_fn(function, generics);
-        while let Some(item) = self.codegen_queue.pop() {
+        let fn_index = self.declare_fn(function, generics);
+        self.export_section.export(
+            function.name(self.db).text(self.db),
+            ExportKind::Func,
+            fn_index.0,
+        );
+
+        while let Some(item) = self.codegen_queue.pop_front() {
             match item {
                 CodegenQueueItem::Function(fn_key) => self.codegen_fn(fn_key),
             }
         }
 
+        let stack_start = self.static_data_size.next_multiple_of(STACK_ALIGNMENT);
+
+        let memory_pages = INITIAL_MEMORY_PAGES + u64::from(stack_start.div_ceil(MEMORY_PAGE_SIZE));
+        let mut memory_section = MemorySection::new();
+        memory_section.memory(MemoryType {
+            minimum: memory_pages,
+            maximum: None,
+            memory64: false,
+            shared: false,
+            page_size_log2: None,
+        });
+        self.export_section
+            .export(MEMORY_EXPORT, ExportKind::Memory, 0);
+
+        let mut global_section = GlobalSection::new();
+        global_section.global(
+            GlobalType {
+                val_type: ValType::I32,
+                mutable: false,
+                shared: false,
+            },
+            &ConstExpr::i32_const(stack_start as i32),
+        );
+        self.export_section
+            .export(STACK_START_EXPORT, ExportKind::Global, 0);
+        global_section.global(
+            GlobalType {
+                val_type: ValType::I32,
+                mutable: true,
+                shared: false,
+            },
+            &ConstExpr::i32_const((memory_pages * u64::from(MEMORY_PAGE_SIZE)) as i32),
+        );
+
         let mut module = wasm_encoder::Module::new();
         module.section(&self.type_section);
+        module.section(&self.import_section);
         module.section(&self.function_section);
+        module.section(&memory_section);
+        module.section(&global_section);
+        module.section(&self.export_section);
         module.section(&self.code_section);
+        module.section(&self.data_section);
 
         module
     }
+
+    /// Returns the address in linear memory of the static data for a byte literal,
+    /// adding it to the data section if this is the first time we have seen it.
+    pub(crate) fn declare_byte_literal(&mut self, data: SymByteLiteralData<'db>) -> u32 {
+        if let Some(&address) = self.byte_literals.get(&data) {
+            return address;
+        }
+
+        let bytes = data.value(self.db);
+        let address = self.static_data_size;
+        self.data_section.active(
+            0,
+            &ConstExpr::i32_const(address as i32),
+            bytes.iter().copied(),
+        );
+        self.static_data_size += u32::try_from(bytes.len()).expect("byte literal too large");
+        self.byte_literals.insert(data, address);
+        address
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Update)]
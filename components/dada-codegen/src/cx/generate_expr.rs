@@ -2,18 +2,21 @@ use std::sync::Arc;
 
 use dada_ir_ast::{ast::PermissionOp, diagnostic::Reported};
 use dada_ir_sym::ir::exprs::{SymBinaryOp, SymExpr, SymExprKind, SymLiteral, SymMatchArm};
-use dada_ir_sym::ir::types::{SymGenericTerm, SymTy, SymTyKind};
+use dada_ir_sym::ir::functions::SymInputOutput;
+use dada_ir_sym::ir::types::{SymGenericTerm, SymPerm, SymTy, SymTyKind};
 use dada_ir_sym::{
     ir::primitive::SymPrimitiveKind, ir::subst::Subst, ir::types::SymTyName,
-    ir::variables::SymVariable,
+    ir::variables::SymVariable, well_known,
 };
 use dada_util::Map;
 use wasm_encoder::{Instruction, ValType};
-use wasm_place_repr::{WasmLocal, WasmPlaceRepr};
+use wasm_place_repr::{FLAG_OWNED, WasmLocal, WasmPlaceRepr};
 
+use super::host_fn::HostFn;
 use super::wasm_repr::WasmReprCx;
 use super::{Cx, wasm_repr::WasmRepr};
 
+mod vec;
 pub(crate) mod wasm_place_repr;
 
 pub(crate) struct ExprCodegen<'cx, 'db> {
@@ -23,8 +26,12 @@ pub(crate) struct ExprCodegen<'cx, 'db> {
     generics: Map<SymVariable<'db>, SymGenericTerm<'db>>,
 
     /// Accumulates wasm locals. We make no effort to reduce the number of local variables created.
+    /// The first `wasm_num_params` entries are the function parameters.
     wasm_locals: Vec<wasm_encoder::ValType>,
 
+    /// Number of locals that are function parameters (and hence not declared in the function body).
+    wasm_num_params: usize,
+
     /// Local variable that stores starting address in our stack frame
     wasm_stack_pointer: WasmLocal,
 
@@ -36,27 +43,41 @@ pub(crate) struct ExprCodegen<'cx, 'db> {
 
     /// Accumulates wasm instructions.
     instructions: Vec<Instruction<'static>>,
+
+    /// Number of wasm control-flow blocks (`block`, `loop`, `if`) enclosing the current instruction.
+    /// Used to compute the relative label indices for branches.
+    block_depth: u32,
+
+    /// For each enclosing Dada loop (innermost last), the value of `block_depth`
+    /// outside of the wasm `block` that is the target of `break`.
+    /// The wasm `loop` targeted by `continue` is directly inside of it.
+    loops: Vec<u32>,
 }
 
 impl<'cx, 'db> ExprCodegen<'cx, 'db> {
     pub fn new(cx: &'cx mut Cx<'db>, generics: Map<SymVariable<'db>, SymGenericTerm<'db>>) -> Self {
-        // Initially there is one local variable, the stack pointer.
+        // Initially there is one local variable, the stack pointer, which is always the first parameter.
         Self {
             cx,
             generics,
             wasm_locals: vec![ValType::I32],
+            wasm_num_params: 1,
             variables: Default::default(),
             instructions: Default::default(),
             wasm_stack_frame_size: 0,
             wasm_stack_pointer: WasmLocal { index: 0 },
+            block_depth: 0,
+            loops: vec![],
         }
     }
 
     pub fn into_function(self) -> wasm_encoder::Function {
-        let mut f = wasm_encoder::Function::new_with_locals_types(self.wasm_locals);
+        let locals = self.wasm_locals[self.wasm_num_params..].iter().copied();
+        let mut f = wasm_encoder::Function::new_with_locals_types(locals);
         for instruction in self.instructions {
             f.instruction(&instruction);
         }
+        f.instruction(&Instruction::End);
         f
     }
 
@@ -67,14 +88,32 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
         wrcx.wasm_repr_of_type(ty)
     }
 
+    /// True if values with the permission `sym_perm` are represented by a pointer (see [`WasmReprCx::is_leased_perm`]).
+    pub fn is_leased_perm(&self, sym_perm: SymPerm<'db>) -> bool {
+        let db = self.cx.db;
+        WasmReprCx::new(db, &self.generics).is_leased_perm(sym_perm)
+    }
+
+    /// Declare the wasm parameters for the function inputs and move their values
+    /// into the places for the corresponding Dada variables.
     pub fn pop_arguments(&mut self, inputs: &[SymVariable<'db>], input_tys: &[SymTy<'db>]) {
         assert_eq!(inputs.len(), input_tys.len());
+
+        // The flattened values of each input follow the stack pointer in the parameter list.
+        // Push them all onto the wasm stack so they can be popped into their places below.
+        for &input_ty in input_tys {
+            for val_type in self.wasm_repr_of_type(input_ty).flatten() {
+                let index = u32::try_from(self.wasm_locals.len()).expect("too many locals");
+                self.wasm_locals.push(val_type);
+                self.instructions.push(Instruction::LocalGet(index));
+            }
+        }
+        self.wasm_num_params = self.wasm_locals.len();
+
         for (&input, &input_ty) in inputs.iter().zip(input_tys).rev() {
             self.insert_variable(input, input_ty);
             self.pop_and_store(&self.place_for_local(input));
         }
-        self.instructions
-            .push(Instruction::LocalSet(self.wasm_stack_pointer.index));
     }
 
     /// Generate code to execute the expression, leaving the result on the top of the wasm stack.
@@ -118,8 +157,10 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                 // FIXME: for now we just ignore futures and execute everything synchronously
             }
             SymExprKind::Assign { place, value } => {
-                let wasm_place = self.place(place);
+                // Evaluate the value first: finding an index place generates code
+                // (e.g., the bounds check) that must see the effects of the value.
                 self.push_expr(value);
+                let wasm_place = self.place(place);
 
                 // FIXME: have to drop the old value
 
@@ -141,7 +182,7 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                     }
 
                     PermissionOp::Share => {
-                        todo!()
+                        self.push_share_from(&wasm_place_repr);
                     }
                 }
             }
@@ -151,6 +192,18 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                 ref arg_temps,
             } => {
                 let fn_args = substitution.subst_vars(db, &self.generics);
+
+                // Calls to trait methods are dispatched to the method from the impl
+                // for the `Self` type, which is known now that generics are substituted.
+                let (function, fn_args) = match function.owning_trait(db) {
+                    Some(sym_trait) => sym_trait
+                        .resolve_method(db, function, &fn_args)
+                        .unwrap_or_else(|| {
+                            panic!("no impl of `{sym_trait}` found for call to {function:?}")
+                        }),
+                    None => (function, fn_args),
+                };
+
                 let fn_index = self.cx.declare_fn(function, fn_args);
 
                 // First push the stack pointer for the new function;
@@ -176,6 +229,43 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                 self.instructions.push(Instruction::I32Const(1));
                 self.instructions.push(Instruction::I32Xor);
             }
+            SymExprKind::Negate {
+                operand,
+                op_span: _,
+            } => match self.primitive_kind(operand.ty(db)) {
+                // Wasm has no integer negation, so compute `0 - operand`.
+                Ok(SymPrimitiveKind::Int { bits }) if bits <= 32 => {
+                    self.instructions.push(Instruction::I32Const(0));
+                    self.push_expr(operand);
+                    self.instructions.push(Instruction::I32Sub);
+                }
+                Ok(SymPrimitiveKind::Isize) => {
+                    self.instructions.push(Instruction::I32Const(0));
+                    self.push_expr(operand);
+                    self.instructions.push(Instruction::I32Sub);
+                }
+                Ok(SymPrimitiveKind::Int { .. }) => {
+                    self.instructions.push(Instruction::I64Const(0));
+                    self.push_expr(operand);
+                    self.instructions.push(Instruction::I64Sub);
+                }
+                Ok(SymPrimitiveKind::Float { bits }) if bits <= 32 => {
+                    self.push_expr(operand);
+                    self.instructions.push(Instruction::F32Neg);
+                }
+                Ok(SymPrimitiveKind::Float { .. }) => {
+                    self.push_expr(operand);
+                    self.instructions.push(Instruction::F64Neg);
+                }
+                Ok(prim_kind) => panic!("cannot negate a value of type {prim_kind:?}"),
+                Err(NotPrimitive::DeadCode) => self.push_expr(operand),
+                Err(NotPrimitive::OtherType) => {
+                    panic!(
+                        "don't know how to negate a value of type {:?}",
+                        operand.ty(db)
+                    )
+                }
+            },
             SymExprKind::BinaryOp(binary_op, object_expr, object_expr1) => {
                 self.push_expr(object_expr);
                 self.push_expr(object_expr1);
@@ -193,29 +283,151 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                     WasmRepr::Class(field_reprs) => {
                         assert_eq!(fields.len(), field_reprs.len());
 
-                        // push flag word
-                        self.instructions.push(Instruction::I32Const(1));
+                        // push flag word: a newly constructed instance is owned
+                        self.instructions.push(Instruction::I32Const(FLAG_OWNED));
 
                         for &field in fields {
                             self.push_expr(field);
                         }
                     }
-                    WasmRepr::Val(_) | WasmRepr::Nothing => {
+                    WasmRepr::Val(_) | WasmRepr::Enum(_) | WasmRepr::Nothing => {
                         panic!("not an aggregate: {ty:?}")
                     }
                 }
             }
+            SymExprKind::Variant {
+                ty,
+                variant,
+                ref fields,
+            } => {
+                let wasm_repr = self.wasm_repr_of_type(ty);
+                for &field in fields {
+                    self.push_expr(field);
+                }
+                self.push_variant_from_fields(&wasm_repr, variant.index(db));
+            }
+            SymExprKind::IsVariant { place, variant } => {
+                let wasm_place_repr = self.place(place);
+                match &*wasm_place_repr {
+                    WasmPlaceRepr::Enum { tag, .. } => {
+                        self.push_from(tag);
+                        self.instructions
+                            .push(Instruction::I32Const(variant.index(db) as i32));
+                        self.instructions.push(Instruction::I32Eq);
+                    }
+                    // The place has type `!` or an error type, so this code is unreachable.
+                    WasmPlaceRepr::Nowhere => self.instructions.push(Instruction::Unreachable),
+                    _ => panic!("not an enum: {wasm_place_repr:?}"),
+                }
+            }
             SymExprKind::Match { ref arms } => {
                 self.push_match_expr(expr.ty(db), arms);
             }
+            SymExprKind::Loop { body } => self.push_loop_expr(expr.ty(db), body),
+            SymExprKind::Break => {
+                let loop_depth = *self.loops.last().expect("`break` outside of a loop");
+                self.push_branch_to(loop_depth);
+            }
+            SymExprKind::Continue => {
+                let loop_depth = *self.loops.last().expect("`continue` outside of a loop");
+                self.push_branch_to(loop_depth + 1);
+            }
             SymExprKind::Error(reported) => self.push_error(reported),
-            #[expect(unused_variables)]
-            SymExprKind::ByteLiteral(sym_byte_literal) => todo!(),
+            SymExprKind::ByteLiteral(sym_byte_literal) => {
+                // A byte literal is a `Pointer[u8]` to its data in the data section.
+                let address = self.cx.declare_byte_literal(sym_byte_literal.data(db));
+                self.instructions
+                    .push(Instruction::I32Const(address as i32));
+            }
         }
     }
 
-    fn pop_and_drop(&mut self, _of_type: SymTy<'db>) {
-        // currently everything is stack allocated, no dropping required
+    /// Generate the body of a function implemented by the host:
+    /// push the values the host expects (see [`HostFn`]), invoke the import,
+    /// and convert what it returns (if anything) into the function's result.
+    pub fn push_host_call(
+        &mut self,
+        host_fn: HostFn,
+        inputs: &[SymVariable<'db>],
+        input_output: &SymInputOutput<'db>,
+    ) {
+        let input_tys = &input_output.input_tys;
+        match host_fn {
+            HostFn::Print => {
+                let &[s] = inputs else {
+                    panic!("expected `print` to have one input, found {inputs:?}")
+                };
+                self.push_string_data(s, input_tys[0]);
+            }
+            HostFn::Concat => {
+                let &[s, other] = inputs else {
+                    panic!("expected `String.concat` to have two inputs, found {inputs:?}")
+                };
+                self.push_string_data(s, input_tys[0]);
+                self.push_string_data(other, input_tys[1]);
+            }
+            HostFn::PrimitiveToString(_) => {
+                let &[value] = inputs else {
+                    panic!("expected `primitive_to_string` to have one input, found {inputs:?}")
+                };
+                self.push_from(&self.place_for_local(value));
+            }
+        }
+        self.instructions
+            .push(Instruction::Call(host_fn.fn_index().0));
+
+        match host_fn {
+            HostFn::Print => (),
+            HostFn::Concat | HostFn::PrimitiveToString(_) => self.push_string_from_data(),
+        }
+    }
+
+    /// Push the address and length of the data of the string stored in the variable `s`,
+    /// whose type `s_ty` is `String` with some permission (e.g., a leased string is a pointer).
+    fn push_string_data(&mut self, s: SymVariable<'db>, s_ty: SymTy<'db>) {
+        let db = self.cx.db;
+        let string_class = well_known::string_class(db).expect("`String` class must be defined");
+        let s_place = self.place_for_local(s);
+        for field_name in ["data", "length"] {
+            let field = string_class
+                .fields(db)
+                .find(|f| f.name(db).text(db) == field_name)
+                .unwrap_or_else(|| panic!("`String` has no field `{field_name}`"));
+            let field_place = self.field_place(s_place.clone(), s_ty, field);
+            self.push_from(&field_place);
+        }
+    }
+
+    /// Given that the address and length of some string data allocated by the host
+    /// are on the wasm stack, pop them and push a new `String` that owns the data:
+    /// the flag word, `data`, `length`, and `capacity`.
+    fn push_string_from_data(&mut self) {
+        let length = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, length);
+        let data = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, data);
+
+        self.instructions.push(Instruction::I32Const(FLAG_OWNED));
+        self.push_from_local(ValType::I32, data);
+        self.push_from_local(ValType::I32, length);
+        self.push_from_local(ValType::I32, length);
+    }
+
+    /// Generate the body of the function exported as [`ALLOC_EXPORT`][].
+    /// Its only parameter, which takes the place of the stack pointer
+    /// received by other functions, is the number of bytes to allocate.
+    ///
+    /// [`ALLOC_EXPORT`]: super::host_fn::ALLOC_EXPORT
+    pub fn push_alloc_fn_body(&mut self) {
+        self.push_from_local(ValType::I32, self.wasm_stack_pointer);
+        self.push_heap_allocation();
+    }
+
+    fn pop_and_drop(&mut self, of_type: SymTy<'db>) {
+        // currently everything is stack allocated, so we just have to discard the values
+        for _ in self.wasm_repr_of_type(of_type).flatten() {
+            self.instructions.push(Instruction::Drop);
+        }
     }
 
     pub(super) fn pop_and_return(&mut self, _of_type: SymTy<'db>) {
@@ -256,8 +468,20 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             | (SymPrimitiveKind::Char, SymBinaryOp::Div)
             | (SymPrimitiveKind::Bool, SymBinaryOp::Add)
             | (SymPrimitiveKind::Bool, SymBinaryOp::Sub)
+            | (SymPrimitiveKind::Char, SymBinaryOp::Rem)
+            | (SymPrimitiveKind::Char, SymBinaryOp::BitAnd)
+            | (SymPrimitiveKind::Char, SymBinaryOp::BitOr)
+            | (SymPrimitiveKind::Char, SymBinaryOp::BitXor)
+            | (SymPrimitiveKind::Char, SymBinaryOp::Shl)
+            | (SymPrimitiveKind::Char, SymBinaryOp::Shr)
             | (SymPrimitiveKind::Bool, SymBinaryOp::Mul)
-            | (SymPrimitiveKind::Bool, SymBinaryOp::Div) => {
+            | (SymPrimitiveKind::Bool, SymBinaryOp::Div)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::Rem)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::BitAnd)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::BitOr)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::BitXor)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::Shl)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::Shr) => {
                 panic!("invalid primitive binary op: {binary_op:?}, {prim_kind:?}")
             }
 
@@ -271,11 +495,14 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             | (SymPrimitiveKind::Bool, SymBinaryOp::GreaterEqual) => Instruction::I32GeU,
 
             (SymPrimitiveKind::Char, SymBinaryOp::LessEqual)
-            | (SymPrimitiveKind::Bool, SymBinaryOp::LessEqual) => Instruction::I32GeU,
+            | (SymPrimitiveKind::Bool, SymBinaryOp::LessEqual) => Instruction::I32LeU,
 
             (SymPrimitiveKind::Char, SymBinaryOp::EqualEqual)
             | (SymPrimitiveKind::Bool, SymBinaryOp::EqualEqual) => Instruction::I32Eq,
 
+            (SymPrimitiveKind::Char, SymBinaryOp::NotEqual)
+            | (SymPrimitiveKind::Bool, SymBinaryOp::NotEqual) => Instruction::I32Ne,
+
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::Add) if bits <= 32 => Instruction::I32Add,
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::Sub) if bits <= 32 => Instruction::I32Sub,
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::Mul) if bits <= 32 => Instruction::I32Mul,
@@ -297,6 +524,25 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::EqualEqual) if bits <= 32 => {
                 Instruction::I32Eq
             }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Rem) if bits <= 32 => {
+                Instruction::I32RemS
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitAnd) if bits <= 32 => {
+                Instruction::I32And
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitOr) if bits <= 32 => {
+                Instruction::I32Or
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitXor) if bits <= 32 => {
+                Instruction::I32Xor
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Shl) if bits <= 32 => Instruction::I32Shl,
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Shr) if bits <= 32 => {
+                Instruction::I32ShrS
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::NotEqual) if bits <= 32 => {
+                Instruction::I32Ne
+            }
 
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::Add) if bits <= 64 => Instruction::I64Add,
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::Sub) if bits <= 64 => Instruction::I64Sub,
@@ -319,6 +565,25 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Int { bits }, SymBinaryOp::EqualEqual) if bits <= 64 => {
                 Instruction::I64Eq
             }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Rem) if bits <= 64 => {
+                Instruction::I64RemS
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitAnd) if bits <= 64 => {
+                Instruction::I64And
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitOr) if bits <= 64 => {
+                Instruction::I64Or
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::BitXor) if bits <= 64 => {
+                Instruction::I64Xor
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Shl) if bits <= 64 => Instruction::I64Shl,
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::Shr) if bits <= 64 => {
+                Instruction::I64ShrS
+            }
+            (SymPrimitiveKind::Int { bits }, SymBinaryOp::NotEqual) if bits <= 64 => {
+                Instruction::I64Ne
+            }
 
             (SymPrimitiveKind::Isize, SymBinaryOp::Add) => Instruction::I32Add,
             (SymPrimitiveKind::Isize, SymBinaryOp::Sub) => Instruction::I32Sub,
@@ -329,6 +594,13 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Isize, SymBinaryOp::GreaterEqual) => Instruction::I32GeS,
             (SymPrimitiveKind::Isize, SymBinaryOp::LessEqual) => Instruction::I32LeS,
             (SymPrimitiveKind::Isize, SymBinaryOp::EqualEqual) => Instruction::I32Eq,
+            (SymPrimitiveKind::Isize, SymBinaryOp::Rem) => Instruction::I32RemS,
+            (SymPrimitiveKind::Isize, SymBinaryOp::BitAnd) => Instruction::I32And,
+            (SymPrimitiveKind::Isize, SymBinaryOp::BitOr) => Instruction::I32Or,
+            (SymPrimitiveKind::Isize, SymBinaryOp::BitXor) => Instruction::I32Xor,
+            (SymPrimitiveKind::Isize, SymBinaryOp::Shl) => Instruction::I32Shl,
+            (SymPrimitiveKind::Isize, SymBinaryOp::Shr) => Instruction::I32ShrS,
+            (SymPrimitiveKind::Isize, SymBinaryOp::NotEqual) => Instruction::I32Ne,
 
             (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Add) if bits <= 32 => {
                 Instruction::I32Add
@@ -357,6 +629,27 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Uint { bits }, SymBinaryOp::EqualEqual) if bits <= 32 => {
                 Instruction::I32Eq
             }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Rem) if bits <= 32 => {
+                Instruction::I32RemU
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitAnd) if bits <= 32 => {
+                Instruction::I32And
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitOr) if bits <= 32 => {
+                Instruction::I32Or
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitXor) if bits <= 32 => {
+                Instruction::I32Xor
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Shl) if bits <= 32 => {
+                Instruction::I32Shl
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Shr) if bits <= 32 => {
+                Instruction::I32ShrU
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::NotEqual) if bits <= 32 => {
+                Instruction::I32Ne
+            }
 
             (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Add) if bits <= 64 => {
                 Instruction::I64Add
@@ -385,6 +678,27 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Uint { bits }, SymBinaryOp::EqualEqual) if bits <= 64 => {
                 Instruction::I64Eq
             }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Rem) if bits <= 64 => {
+                Instruction::I64RemU
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitAnd) if bits <= 64 => {
+                Instruction::I64And
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitOr) if bits <= 64 => {
+                Instruction::I64Or
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::BitXor) if bits <= 64 => {
+                Instruction::I64Xor
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Shl) if bits <= 64 => {
+                Instruction::I64Shl
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::Shr) if bits <= 64 => {
+                Instruction::I64ShrU
+            }
+            (SymPrimitiveKind::Uint { bits }, SymBinaryOp::NotEqual) if bits <= 64 => {
+                Instruction::I64Ne
+            }
 
             (SymPrimitiveKind::Usize, SymBinaryOp::Add) => Instruction::I32Add,
             (SymPrimitiveKind::Usize, SymBinaryOp::Sub) => Instruction::I32Sub,
@@ -395,6 +709,13 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Usize, SymBinaryOp::GreaterEqual) => Instruction::I32GeU,
             (SymPrimitiveKind::Usize, SymBinaryOp::LessEqual) => Instruction::I32LeU,
             (SymPrimitiveKind::Usize, SymBinaryOp::EqualEqual) => Instruction::I32Eq,
+            (SymPrimitiveKind::Usize, SymBinaryOp::Rem) => Instruction::I32RemU,
+            (SymPrimitiveKind::Usize, SymBinaryOp::BitAnd) => Instruction::I32And,
+            (SymPrimitiveKind::Usize, SymBinaryOp::BitOr) => Instruction::I32Or,
+            (SymPrimitiveKind::Usize, SymBinaryOp::BitXor) => Instruction::I32Xor,
+            (SymPrimitiveKind::Usize, SymBinaryOp::Shl) => Instruction::I32Shl,
+            (SymPrimitiveKind::Usize, SymBinaryOp::Shr) => Instruction::I32ShrU,
+            (SymPrimitiveKind::Usize, SymBinaryOp::NotEqual) => Instruction::I32Ne,
 
             (SymPrimitiveKind::Float { bits }, SymBinaryOp::Add) if bits <= 32 => {
                 Instruction::F32Add
@@ -423,6 +744,9 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Float { bits }, SymBinaryOp::EqualEqual) if bits <= 32 => {
                 Instruction::F32Eq
             }
+            (SymPrimitiveKind::Float { bits }, SymBinaryOp::NotEqual) if bits <= 32 => {
+                Instruction::F32Ne
+            }
 
             (SymPrimitiveKind::Float { bits }, SymBinaryOp::Add) if bits <= 64 => {
                 Instruction::F64Add
@@ -451,6 +775,21 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             (SymPrimitiveKind::Float { bits }, SymBinaryOp::EqualEqual) if bits <= 64 => {
                 Instruction::F64Eq
             }
+            (SymPrimitiveKind::Float { bits }, SymBinaryOp::NotEqual) if bits <= 64 => {
+                Instruction::F64Ne
+            }
+
+            (
+                SymPrimitiveKind::Float { bits: _ },
+                SymBinaryOp::Rem
+                | SymBinaryOp::BitAnd
+                | SymBinaryOp::BitOr
+                | SymBinaryOp::BitXor
+                | SymBinaryOp::Shl
+                | SymBinaryOp::Shr,
+            ) => {
+                panic!("invalid primitive binary op: {binary_op:?}, {prim_kind:?}")
+            }
 
             (SymPrimitiveKind::Int { bits: _ }, _)
             | (SymPrimitiveKind::Uint { bits: _ } | SymPrimitiveKind::Float { bits: _ }, _) => {
@@ -476,8 +815,9 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             }
             SymTyKind::Never | SymTyKind::Error(_) => Err(NotPrimitive::DeadCode),
             SymTyKind::Infer(_) => panic!("unexpected inference variable"),
-            #[expect(unused_variables)]
-            SymTyKind::Perm(sym_perm, sym_ty) => todo!(),
+            // Only classes can be leased (see `push_leased_from`), so a permission
+            // applied to a primitive does not change its representation.
+            SymTyKind::Perm(_, sym_ty) => self.primitive_kind(*sym_ty),
         }
     }
 
@@ -494,6 +834,7 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             // if the condition was true. Otherwise it will skip to the `Else` or `End.`
             let block_type = self.block_type(match_ty);
             self.instructions.push(Instruction::If(block_type));
+            self.block_depth += 1;
 
             // Code to execute if true.
             self.push_expr(if_arm.body);
@@ -504,6 +845,7 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
 
             // End the if.
             self.instructions.push(Instruction::End);
+            self.block_depth -= 1;
         } else {
             // Execute body unconditionally.
             self.push_expr(if_arm.body);
@@ -513,6 +855,54 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
         }
     }
 
+    /// Generates a loop like
+    ///
+    /// ```text
+    /// block         ;; `break` branches here, exiting the loop
+    ///   loop        ;; `continue` branches here, starting the next iteration
+    ///     <body>
+    ///     br 0
+    ///   end
+    /// end
+    /// ```
+    ///
+    /// Loops only produce a value (`()`) by executing `break`, so both blocks have empty types.
+    fn push_loop_expr(&mut self, loop_ty: SymTy<'db>, body: SymExpr<'db>) {
+        let db = self.cx.db;
+
+        self.loops.push(self.block_depth);
+        self.instructions
+            .push(Instruction::Block(wasm_encoder::BlockType::Empty));
+        self.instructions
+            .push(Instruction::Loop(wasm_encoder::BlockType::Empty));
+        self.block_depth += 2;
+
+        self.push_expr(body);
+        self.pop_and_drop(body.ty(db));
+        self.instructions.push(Instruction::Br(0));
+
+        self.instructions.push(Instruction::End);
+        self.instructions.push(Instruction::End);
+        self.block_depth -= 2;
+        self.loops.pop();
+
+        // Exiting a loop via `break` produces `()`. If the loop has any other type
+        // (typically `!`, but inference may pick something else if there is no `break`),
+        // then it never exits normally and the code following it is unreachable.
+        if matches!(loop_ty.kind(db), SymTyKind::Never)
+            || !self.wasm_repr_of_type(loop_ty).flatten().is_empty()
+        {
+            self.instructions.push(Instruction::Unreachable);
+        }
+    }
+
+    /// Branch to the label of the wasm block that was opened when `block_depth` was `depth`.
+    fn push_branch_to(&mut self, depth: u32) {
+        assert!(depth < self.block_depth);
+        self.instructions
+            .push(Instruction::Br(self.block_depth - depth - 1));
+    }
+
     /// [Block control-flow instructions][cfi] like `if` and friends
     /// come equipped with an associated "block type". This is a function
     /// type indicating the *inputs* they consume from the stack (in our case,
@@ -550,8 +940,7 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
             SymTyKind::Error(reported) => {
                 return self.push_error(*reported);
             }
-            #[expect(unused_variables)]
-            SymTyKind::Perm(sym_perm, sym_ty) => todo!(),
+            SymTyKind::Perm(_, sym_ty) => return self.push_literal(*sym_ty, literal),
         };
         match kind {
             SymPrimitiveKind::Bool
@@ -576,16 +965,12 @@ impl<'cx, 'db> ExprCodegen<'cx, 'db> {
                 self.instructions.push(Instruction::I64Const(bits as i64));
             }
             SymPrimitiveKind::Float { bits } if bits <= 32 => {
-                let SymLiteral::Float { bits } = literal else {
-                    panic!("expected float {literal:?}");
-                };
-                self.instructions.push(Instruction::F32Const(bits.0 as f32));
+                self.instructions
+                    .push(Instruction::F32Const(float_literal_value(literal) as f32));
             }
-            SymPrimitiveKind::Float { bits } if bits <= 32 => {
-                let SymLiteral::Float { bits } = literal else {
-                    panic!("expected float {literal:?}");
-                };
-                self.instructions.push(Instruction::F64Const(bits.0));
+            SymPrimitiveKind::Float { bits } if bits <= 64 => {
+                self.instructions
+                    .push(Instruction::F64Const(float_literal_value(literal)));
             }
             SymPrimitiveKind::Int { .. }
             | SymPrimitiveKind::Uint { .. }
@@ -606,3 +991,12 @@ enum NotPrimitive {
     DeadCode,
     OtherType,
 }
+
+/// Value of a literal whose type is a float.
+/// Integer literals like `22` may be inferred to have a float type.
+fn float_literal_value(literal: SymLiteral) -> f64 {
+    match literal {
+        SymLiteral::Integral { bits } => bits as f64,
+        SymLiteral::Float { bits } => bits.0,
+    }
+}
@@ -0,0 +1,304 @@
+//! Code generation for `Vec`: the intrinsic functions that create and modify
+//! vectors (see [`IntrinsicFn`]) and the places for indexing into them.
+//!
+//! A `Vec[T]` is a class whose `data` field points to a heap allocation with room
+//! for `capacity` elements, the first `length` of which are initialized.
+//! Elements are stored one after the other, laid out as by [`place_in_memory`].
+
+use std::sync::Arc;
+
+use dada_ir_sym::{
+    ir::{
+        functions::SymInputOutput,
+        types::{SymTy, SymTyKind, SymTyName},
+        variables::SymVariable,
+    },
+    well_known,
+};
+use wasm_encoder::{BlockType, Instruction, ValType};
+
+use crate::cx::{
+    HEAP_ALIGNMENT, HEAP_POINTER_GLOBAL, MEMORY_PAGE_SIZE, intrinsic_fn::IntrinsicFn,
+    wasm_repr::WasmRepr,
+};
+
+use super::{
+    ExprCodegen,
+    wasm_place_repr::{FLAG_OWNED, WasmPlaceRepr, WasmPointer, place_in_memory, size_in_memory},
+};
+
+/// Number of elements in the first heap allocation of a vector.
+const INITIAL_CAPACITY: i32 = 4;
+
+/// The places storing the fields of a vector.
+struct VecPlaces {
+    data: Arc<WasmPlaceRepr>,
+    length: Arc<WasmPlaceRepr>,
+    capacity: Arc<WasmPlaceRepr>,
+}
+
+impl<'db> ExprCodegen<'_, 'db> {
+    /// Generate the body of a function implemented by the compiler (see [`IntrinsicFn`]),
+    /// leaving its result on the wasm stack.
+    pub fn push_intrinsic_call(
+        &mut self,
+        intrinsic_fn: IntrinsicFn,
+        inputs: &[SymVariable<'db>],
+        input_output: &SymInputOutput<'db>,
+    ) {
+        match intrinsic_fn {
+            IntrinsicFn::VecNew => {
+                // An empty vector has no allocation: push the flag word, `data`, `length`, and `capacity`.
+                self.instructions.push(Instruction::I32Const(FLAG_OWNED));
+                for _ in 0..3 {
+                    self.instructions.push(Instruction::I32Const(0));
+                }
+            }
+
+            IntrinsicFn::VecPush => {
+                let &[vec, value] = inputs else {
+                    panic!("expected `Vec.push` to have two inputs, found {inputs:?}")
+                };
+                let element_repr = self.wasm_repr_of_type(input_output.input_tys[1]);
+                let vec_places =
+                    self.vec_places(self.place_for_local(vec), input_output.input_tys[0]);
+
+                // If the vector is full, grow it.
+                self.push_from(&vec_places.length);
+                self.push_from(&vec_places.capacity);
+                self.instructions.push(Instruction::I32Eq);
+                self.instructions.push(Instruction::If(BlockType::Empty));
+                self.block_depth += 1;
+                self.push_grow_vec(&vec_places, &element_repr);
+                self.instructions.push(Instruction::End);
+                self.block_depth -= 1;
+
+                // Move the value into the slot after the last element...
+                self.push_from(&vec_places.length);
+                let element_place = self.element_place(&element_repr, &vec_places.data);
+                self.push_from(&self.place_for_local(value));
+                self.pop_and_store(&element_place);
+
+                // ...and count it.
+                self.push_from(&vec_places.length);
+                self.instructions.push(Instruction::I32Const(1));
+                self.instructions.push(Instruction::I32Add);
+                self.pop_and_store(&vec_places.length);
+            }
+
+            IntrinsicFn::VecPop => {
+                let &[vec] = inputs else {
+                    panic!("expected `Vec.pop` to have one input, found {inputs:?}")
+                };
+                let element_repr = self.wasm_repr_of_type(input_output.output_ty);
+                let vec_places =
+                    self.vec_places(self.place_for_local(vec), input_output.input_tys[0]);
+
+                // Trap if the vector is empty.
+                self.push_from(&vec_places.length);
+                self.instructions.push(Instruction::I32Eqz);
+                self.push_trap_if();
+
+                // Otherwise, uncount the last element and move it out.
+                self.push_from(&vec_places.length);
+                self.instructions.push(Instruction::I32Const(1));
+                self.instructions.push(Instruction::I32Sub);
+                self.pop_and_store(&vec_places.length);
+
+                self.push_from(&vec_places.length);
+                let element_place = self.element_place(&element_repr, &vec_places.data);
+                self.push_from(&element_place);
+            }
+        }
+    }
+
+    /// Representation for the place storing the element of an owner of type `owner_ty`
+    /// (a `Vec`) stored in `owner_place`, whose index is stored in `index_place`.
+    /// Unlike other places, this generates code: it traps if the index is out of bounds.
+    pub(super) fn index_place(
+        &mut self,
+        owner_place: Arc<WasmPlaceRepr>,
+        owner_ty: SymTy<'db>,
+        index_place: &WasmPlaceRepr,
+    ) -> Arc<WasmPlaceRepr> {
+        if let WasmPlaceRepr::Nowhere = *owner_place {
+            return owner_place;
+        }
+        if let WasmPlaceRepr::Nowhere = *index_place {
+            return Arc::new(WasmPlaceRepr::Nowhere);
+        }
+
+        let element_repr = self.wasm_repr_of_type(self.vec_element_ty(owner_ty));
+        let vec_places = self.vec_places(owner_place, owner_ty);
+
+        // Trap unless `index < length`.
+        self.push_from(index_place);
+        self.push_from(&vec_places.length);
+        self.instructions.push(Instruction::I32GeU);
+        self.push_trap_if();
+
+        self.push_from(index_place);
+        self.element_place(&element_repr, &vec_places.data)
+    }
+
+    /// The places storing the fields of a vector of type `vec_ty` that is stored in `vec_place`.
+    fn vec_places(&mut self, vec_place: Arc<WasmPlaceRepr>, vec_ty: SymTy<'db>) -> VecPlaces {
+        let db = self.cx.db;
+        let vec_class = well_known::vec_class(db).expect("`Vec` class must be defined");
+        let mut field_place = |field_name: &str| {
+            let field = vec_class
+                .fields(db)
+                .find(|f| f.name(db).text(db) == field_name)
+                .unwrap_or_else(|| panic!("`Vec` has no field `{field_name}`"));
+            self.field_place(vec_place.clone(), vec_ty, field)
+        };
+        VecPlaces {
+            data: field_place("data"),
+            length: field_place("length"),
+            capacity: field_place("capacity"),
+        }
+    }
+
+    /// The type `T` of the elements of a vector of type `vec_ty`.
+    fn vec_element_ty(&self, vec_ty: SymTy<'db>) -> SymTy<'db> {
+        let db = self.cx.db;
+        match vec_ty.kind(db) {
+            SymTyKind::Named(SymTyName::Aggregate(_), generics) => generics[0].assert_type(db),
+            SymTyKind::Var(sym_variable) => {
+                self.vec_element_ty(self.generics[sym_variable].assert_type(db))
+            }
+            SymTyKind::Perm(_, sym_ty) => self.vec_element_ty(*sym_ty),
+            _ => panic!("not a vector: {vec_ty:?}"),
+        }
+    }
+
+    /// Given that an index is on the wasm stack, pop it and return the place storing the element
+    /// at that index in the heap allocation whose address is stored in `data`.
+    fn element_place(
+        &mut self,
+        element_repr: &WasmRepr,
+        data: &WasmPlaceRepr,
+    ) -> Arc<WasmPlaceRepr> {
+        self.instructions
+            .push(Instruction::I32Const(size_in_memory(element_repr) as i32));
+        self.instructions.push(Instruction::I32Mul);
+        self.push_from(data);
+        self.instructions.push(Instruction::I32Add);
+
+        let address = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, address);
+        place_in_memory(element_repr, &mut WasmPointer::new(address))
+    }
+
+    /// Move the elements of the vector whose fields are stored in `vec_places` into
+    /// a new heap allocation with twice the capacity (or [`INITIAL_CAPACITY`], if it had none).
+    /// The old allocation is not freed.
+    fn push_grow_vec(&mut self, vec_places: &VecPlaces, element_repr: &WasmRepr) {
+        let element_size = size_in_memory(element_repr) as i32;
+
+        // `select` picks its first operand if the condition is non-zero, so this is
+        // `if capacity == 0 { INITIAL_CAPACITY } else { capacity * 2 }`.
+        self.instructions
+            .push(Instruction::I32Const(INITIAL_CAPACITY));
+        self.push_from(&vec_places.capacity);
+        self.instructions.push(Instruction::I32Const(2));
+        self.instructions.push(Instruction::I32Mul);
+        self.push_from(&vec_places.capacity);
+        self.instructions.push(Instruction::I32Eqz);
+        self.instructions.push(Instruction::Select);
+        let new_capacity = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, new_capacity);
+
+        self.push_from_local(ValType::I32, new_capacity);
+        self.instructions.push(Instruction::I32Const(element_size));
+        self.instructions.push(Instruction::I32Mul);
+        self.push_heap_allocation();
+        let new_data = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, new_data);
+
+        // Copy the existing elements: `memory.copy` expects the destination, source, and size.
+        self.push_from_local(ValType::I32, new_data);
+        self.push_from(&vec_places.data);
+        self.push_from(&vec_places.length);
+        self.instructions.push(Instruction::I32Const(element_size));
+        self.instructions.push(Instruction::I32Mul);
+        self.instructions.push(Instruction::MemoryCopy {
+            src_mem: 0,
+            dst_mem: 0,
+        });
+
+        self.push_from_local(ValType::I32, new_data);
+        self.pop_and_store(&vec_places.data);
+        self.push_from_local(ValType::I32, new_capacity);
+        self.pop_and_store(&vec_places.capacity);
+    }
+
+    /// Given that a size in bytes is on the wasm stack, pop it, allocate that many bytes
+    /// from the heap, and push the address of the allocation.
+    /// The heap is a simple bump allocator that grows the memory as needed
+    /// (trapping if it cannot); allocations are never freed.
+    pub(super) fn push_heap_allocation(&mut self) {
+        let size = self.fresh_local_index(ValType::I32);
+        self.pop_to_local(ValType::I32, size);
+        let address = self.fresh_local_index(ValType::I32);
+        self.instructions
+            .push(Instruction::GlobalGet(HEAP_POINTER_GLOBAL));
+        self.pop_to_local(ValType::I32, address);
+
+        // Advance the heap pointer past the allocation, keeping it aligned.
+        self.push_from_local(ValType::I32, address);
+        self.push_from_local(ValType::I32, size);
+        self.instructions.push(Instruction::I32Add);
+        self.instructions
+            .push(Instruction::I32Const(HEAP_ALIGNMENT as i32 - 1));
+        self.instructions.push(Instruction::I32Add);
+        self.instructions
+            .push(Instruction::I32Const(-(HEAP_ALIGNMENT as i32)));
+        self.instructions.push(Instruction::I32And);
+        self.instructions
+            .push(Instruction::GlobalSet(HEAP_POINTER_GLOBAL));
+
+        // If the heap pointer is now past the end of memory,
+        // grow the memory by enough pages to cover the difference.
+        self.push_heap_overflow();
+        self.instructions.push(Instruction::I32Const(0));
+        self.instructions.push(Instruction::I32GtS);
+        self.instructions.push(Instruction::If(BlockType::Empty));
+        self.block_depth += 1;
+        self.push_heap_overflow();
+        self.instructions
+            .push(Instruction::I32Const(MEMORY_PAGE_SIZE as i32 - 1));
+        self.instructions.push(Instruction::I32Add);
+        self.instructions
+            .push(Instruction::I32Const(MEMORY_PAGE_SIZE.ilog2() as i32));
+        self.instructions.push(Instruction::I32ShrU);
+        self.instructions.push(Instruction::MemoryGrow(0));
+        self.instructions.push(Instruction::I32Const(-1));
+        self.instructions.push(Instruction::I32Eq);
+        self.push_trap_if();
+        self.instructions.push(Instruction::End);
+        self.block_depth -= 1;
+
+        self.push_from_local(ValType::I32, address);
+    }
+
+    /// Push the number of bytes by which the heap pointer exceeds the size of memory.
+    fn push_heap_overflow(&mut self) {
+        self.instructions
+            .push(Instruction::GlobalGet(HEAP_POINTER_GLOBAL));
+        self.instructions.push(Instruction::MemorySize(0));
+        self.instructions
+            .push(Instruction::I32Const(MEMORY_PAGE_SIZE.ilog2() as i32));
+        self.instructions.push(Instruction::I32Shl);
+        self.instructions.push(Instruction::I32Sub);
+    }
+
+    /// Given that an `i32` condition is on the wasm stack, pop it and trap if it is non-zero.
+    fn push_trap_if(&mut self) {
+        self.instructions.push(Instruction::If(BlockType::Empty));
+        self.block_depth += 1;
+        self.instructions.push(Instruction::Unreachable);
+        self.instructions.push(Instruction::End);
+        self.block_depth -= 1;
+    }
+}
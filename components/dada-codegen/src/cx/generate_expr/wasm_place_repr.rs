@@ -22,9 +22,45 @@ pub enum WasmPlaceRepr {
     Heap(WasmPointer, ValType),
     Struct(Vec<Arc<WasmPlaceRepr>>),
     Class(WasmPointer, Vec<Arc<WasmPlaceRepr>>),
+
+    /// An enum, stored as a tagged union (see [`WasmRepr::Enum`]).
+    Enum {
+        /// Place storing the `I32` tag that identifies the variant.
+        tag: Arc<WasmPlaceRepr>,
+
+        /// Places storing each of the union slots, each of which is a `Local` or `Heap` value.
+        slots: Vec<Arc<WasmPlaceRepr>>,
+
+        /// For each variant, the places storing each of its fields.
+        /// These are assembled from the places in `slots`.
+        variants: Vec<Vec<Arc<WasmPlaceRepr>>>,
+    },
+
     Nowhere,
 }
 
+impl WasmPlaceRepr {
+    /// True if this place stores (part of) a class instance.
+    fn contains_class(&self) -> bool {
+        match self {
+            WasmPlaceRepr::Local(..) | WasmPlaceRepr::Heap(..) | WasmPlaceRepr::Nowhere => false,
+            WasmPlaceRepr::Struct(fields) => fields.iter().any(|r| r.contains_class()),
+            WasmPlaceRepr::Class(..) => true,
+            WasmPlaceRepr::Enum { variants, .. } => {
+                variants.iter().flatten().any(|r| r.contains_class())
+            }
+        }
+    }
+}
+
+/// Value of the flag word of a class instance that is uniquely owned
+/// (e.g., one that was just constructed).
+pub(super) const FLAG_OWNED: i32 = 1;
+
+/// Value of the flag word of a class instance that is shared,
+/// either because it was shared (`x.share`) or because it is a copy made by `x.ref`.
+pub(super) const FLAG_SHARED: i32 = 0;
+
 impl<'db> ExprCodegen<'_, 'db> {
     /// Returns a [`WasmPointer`] to the current start of a callee's stack frame.
     /// This value is only valid until [`Self::insert_variable`] is next called.
@@ -50,7 +86,8 @@ impl<'db> ExprCodegen<'_, 'db> {
     }
 
     /// The representation of the given Dada place.
-    pub(super) fn place(&self, place: SymPlaceExpr<'db>) -> Arc<WasmPlaceRepr> {
+    /// For an index place, this generates the code that finds the element (see [`Self::index_place`]).
+    pub(super) fn place(&mut self, place: SymPlaceExpr<'db>) -> Arc<WasmPlaceRepr> {
         let db = self.cx.db;
         match *place.kind(db) {
             SymPlaceExprKind::Var(v) => self.place_for_local(v),
@@ -59,6 +96,11 @@ impl<'db> ExprCodegen<'_, 'db> {
                 let owner_place = self.place(owner);
                 self.field_place(owner_place, owner.ty(db), field)
             }
+            SymPlaceExprKind::Index(owner, index) => {
+                let owner_place = self.place(owner);
+                let index_place = self.place_for_local(index);
+                self.index_place(owner_place, owner.ty(db), &index_place)
+            }
         }
     }
 
@@ -78,6 +120,12 @@ impl<'db> ExprCodegen<'_, 'db> {
                 self.push_from_memory(ValType::I32, flags);
                 fields.iter().for_each(|r| self.push_from(r));
             }
+            WasmPlaceRepr::Enum {
+                ref tag, ref slots, ..
+            } => {
+                self.push_from(tag);
+                slots.iter().for_each(|r| self.push_from(r));
+            }
             WasmPlaceRepr::Nowhere => (),
         }
     }
@@ -88,23 +136,80 @@ impl<'db> ExprCodegen<'_, 'db> {
             WasmPlaceRepr::Struct(ref fields) => {
                 fields.iter().for_each(|r| self.push_shared_from(r));
             }
-            WasmPlaceRepr::Class(flags, ref fields) => {
-                // Push a 0 for the flags if shared
-                self.instructions.push(Instruction::I32Const(0));
-
-                self.push_from_memory(ValType::I32, flags);
+            WasmPlaceRepr::Class(_, ref fields) => {
+                // The copy is shared, regardless of the flags of the original.
+                self.instructions.push(Instruction::I32Const(FLAG_SHARED));
                 fields.iter().for_each(|r| self.push_shared_from(r));
             }
-            WasmPlaceRepr::Local(..) | WasmPlaceRepr::Heap(..) | WasmPlaceRepr::Nowhere => {
+            WasmPlaceRepr::Local(..)
+            | WasmPlaceRepr::Heap(..)
+            | WasmPlaceRepr::Enum { .. }
+            | WasmPlaceRepr::Nowhere => {
                 self.push_from(place);
             }
         }
     }
 
-    /// Push a shared copy the value found in `place` onto the WASM stack.
+    /// Share the value found in `place` and push it onto the WASM stack.
+    /// Unlike [`Self::push_shared_from`], this records in `place` itself that
+    /// the value is now shared, since the original and the copy are both `our` afterwards.
+    pub(super) fn push_share_from(&mut self, place: &WasmPlaceRepr) {
+        self.mark_shared(place);
+        self.push_from(place);
+    }
+
+    /// Set the flag word of each class instance stored in `place` to [`FLAG_SHARED`].
+    fn mark_shared(&mut self, place: &WasmPlaceRepr) {
+        match *place {
+            WasmPlaceRepr::Struct(ref fields) => {
+                fields.iter().for_each(|r| self.mark_shared(r));
+            }
+            WasmPlaceRepr::Class(flags, ref fields) => {
+                self.instructions.push(Instruction::I32Const(FLAG_SHARED));
+                self.pop_to_memory(ValType::I32, flags);
+                fields.iter().for_each(|r| self.mark_shared(r));
+            }
+            WasmPlaceRepr::Enum {
+                ref tag,
+                ref variants,
+                ..
+            } => {
+                // Only the fields of the current variant are valid,
+                // so test the tag before marking each variant's fields.
+                for (index, fields) in variants.iter().enumerate() {
+                    if !fields.iter().any(|r| r.contains_class()) {
+                        continue;
+                    }
+                    self.push_from(tag);
+                    self.instructions.push(Instruction::I32Const(index as i32));
+                    self.instructions.push(Instruction::I32Eq);
+                    self.instructions
+                        .push(Instruction::If(wasm_encoder::BlockType::Empty));
+                    self.block_depth += 1;
+                    fields.iter().for_each(|r| self.mark_shared(r));
+                    self.instructions.push(Instruction::End);
+                    self.block_depth -= 1;
+                }
+            }
+            WasmPlaceRepr::Local(..) | WasmPlaceRepr::Heap(..) | WasmPlaceRepr::Nowhere => {}
+        }
+    }
+
+    /// Push a pointer to the class instance found in `place` onto the WASM stack.
+    /// Traps if the instance has been shared, since shared instances cannot be leased.
     pub(super) fn push_leased_from(&mut self, place: &WasmPlaceRepr) {
         match *place {
             WasmPlaceRepr::Class(flags, _) => {
+                self.push_from_memory(ValType::I32, flags);
+                self.instructions.push(Instruction::I32Const(FLAG_SHARED));
+                self.instructions.push(Instruction::I32Eq);
+                self.instructions
+                    .push(Instruction::If(wasm_encoder::BlockType::Empty));
+                self.block_depth += 1;
+                self.instructions.push(Instruction::Unreachable);
+                self.instructions.push(Instruction::End);
+                self.block_depth -= 1;
+
                 self.push_pointer(flags);
             }
             _ => panic!("can only lease classes"),
@@ -125,14 +230,20 @@ impl<'db> ExprCodegen<'_, 'db> {
                 fields.iter().rev().for_each(|r| self.pop_and_store(r));
                 self.pop_to_memory(ValType::I32, flags);
             }
+            WasmPlaceRepr::Enum {
+                ref tag, ref slots, ..
+            } => {
+                slots.iter().rev().for_each(|r| self.pop_and_store(r));
+                self.pop_and_store(tag);
+            }
             WasmPlaceRepr::Nowhere => (),
         }
     }
 
     /// Representation for the place storing a given field found in
     /// an owner of type `owner_ty` that is stored in `owner_place`.
-    fn field_place(
-        &self,
+    pub(super) fn field_place(
+        &mut self,
         owner_place_repr: Arc<WasmPlaceRepr>,
         owner_ty: SymTy<'db>,
         field: SymField<'db>,
@@ -150,13 +261,26 @@ impl<'db> ExprCodegen<'_, 'db> {
                 _ => panic!("unexpeced place for {owner_ty:?}: {owner_place_repr:?}"),
             },
             SymTyKind::Named(ty_name, _) => match *ty_name {
-                SymTyName::Future => match &*owner_place_repr {
-                    WasmPlaceRepr::Class(_, vec) => vec[0].clone(),
+                SymTyName::Future => panic!("futures do not have fields"),
+                SymTyName::Primitive(_) => panic!("primitive types do not have fields"),
+                SymTyName::Tuple { arity: _ } => todo!(),
+                SymTyName::Aggregate(aggr) if aggr.is_enum(db) => match &*owner_place_repr {
+                    // Fields of an enum belong to one of its variants.
+                    WasmPlaceRepr::Enum { variants, .. } => {
+                        let (variant_index, field_index) = aggr
+                            .variants(db)
+                            .iter()
+                            .enumerate()
+                            .find_map(|(i, v)| {
+                                let j = v.fields(db).iter().position(|f| *f == field)?;
+                                Some((i, j))
+                            })
+                            .expect("field not found in any variant");
+                        variants[variant_index][field_index].clone()
+                    }
                     WasmPlaceRepr::Nowhere => owner_place_repr,
                     _ => panic!("unexpeced place for {owner_ty:?}: {owner_place_repr:?}"),
                 },
-                SymTyName::Primitive(_) => panic!("primitive types do not have fields"),
-                SymTyName::Tuple { arity: _ } => todo!(),
                 SymTyName::Aggregate(aggr) => {
                     // Where is the owner's data stored?
                     match &*owner_place_repr {
@@ -172,8 +296,49 @@ impl<'db> ExprCodegen<'_, 'db> {
                     }
                 }
             },
-            #[expect(unused_variables)]
-            SymTyKind::Perm(sym_perm, sym_ty) => todo!(),
+            SymTyKind::Perm(sym_perm, sym_ty) => {
+                if self.is_leased_perm(*sym_perm) {
+                    // A lease is a pointer to the owner's data,
+                    // so find the field in the place that it points at.
+                    let pointee_place_repr = self.place_behind_pointer(&owner_place_repr, *sym_ty);
+                    self.field_place(pointee_place_repr, *sym_ty, field)
+                } else {
+                    // Other permissions do not change the representation.
+                    self.field_place(owner_place_repr, *sym_ty, field)
+                }
+            }
+        }
+    }
+
+    /// Given that `pointer_place_repr` stores a pointer to a value of type `pointee_ty`,
+    /// returns the representation of the place where that value is stored.
+    /// If the pointer is itself stored in memory, this generates code to load it into a fresh local.
+    fn place_behind_pointer(
+        &mut self,
+        pointer_place_repr: &WasmPlaceRepr,
+        pointee_ty: SymTy<'db>,
+    ) -> Arc<WasmPlaceRepr> {
+        match *pointer_place_repr {
+            WasmPlaceRepr::Local(local, ValType::I32) => {
+                let mut pointer = WasmPointer {
+                    base_variable: local,
+                    offset: 0,
+                };
+                place_in_memory(&self.wasm_repr_of_type(pointee_ty), &mut pointer)
+            }
+            WasmPlaceRepr::Nowhere => Arc::new(WasmPlaceRepr::Nowhere),
+            WasmPlaceRepr::Heap(pointer_slot, ValType::I32) => {
+                // E.g., a lease stored in a field of a class.
+                let local = self.fresh_local_index(ValType::I32);
+                self.push_from_memory(ValType::I32, pointer_slot);
+                self.pop_to_local(ValType::I32, local);
+                let mut pointer = WasmPointer {
+                    base_variable: local,
+                    offset: 0,
+                };
+                place_in_memory(&self.wasm_repr_of_type(pointee_ty), &mut pointer)
+            }
+            _ => panic!("unexpected place for a pointer: {pointer_place_repr:?}"),
         }
     }
 
@@ -189,92 +354,108 @@ impl<'db> ExprCodegen<'_, 'db> {
                 vec.iter().map(|r| self.emplace_local(r)).collect(),
             )),
             WasmRepr::Class(_) => self.emplace_memory(repr),
+            WasmRepr::Enum(variants) => {
+                // The data of a class found in a variant must be in memory,
+                // and the union slots are shared between variants, so if any
+                // variant contains a class, the entire enum goes in memory.
+                if variants.iter().flatten().any(|r| r.contains_class()) {
+                    return self.emplace_memory(repr);
+                }
+                let tag = self.emplace_local(&WasmRepr::Val(ValType::I32));
+                let slots = repr
+                    .union_val_tys()
+                    .into_iter()
+                    .map(|v| self.emplace_local(&WasmRepr::Val(v)))
+                    .collect();
+                Arc::new(enum_place(tag, slots, variants))
+            }
             WasmRepr::Nothing => Arc::new(WasmPlaceRepr::Nowhere),
         }
     }
 
     /// The representation for a Dada place found in WASM memory
     /// that stores values with representation `repr`.
+    /// This allocates space in the current stack frame.
     fn emplace_memory(&mut self, repr: &WasmRepr) -> Arc<WasmPlaceRepr> {
-        match repr {
-            WasmRepr::Val(val_type) => {
-                let pointer = self.fresh_memory_slot(*val_type);
-                Arc::new(WasmPlaceRepr::Heap(pointer, *val_type))
-            }
-            WasmRepr::Struct(vec) => Arc::new(WasmPlaceRepr::Struct(
-                vec.iter().map(|r| self.emplace_memory(r)).collect(),
-            )),
-            WasmRepr::Class(vec) => {
-                let flag_word = self.fresh_memory_slot(ValType::I32);
-                Arc::new(WasmPlaceRepr::Class(
-                    flag_word,
-                    vec.iter().map(|r| self.emplace_memory(r)).collect(),
+        let mut pointer = self.next_stack_frame();
+        let place = place_in_memory(repr, &mut pointer);
+        self.wasm_stack_frame_size = pointer.offset;
+        place
+    }
+
+    /// Given that the fields of the `variant_index`-th variant of an enum with representation `enum_repr`
+    /// are on the wasm stack, pop them and push the enum value.
+    pub(super) fn push_variant_from_fields(&mut self, enum_repr: &WasmRepr, variant_index: usize) {
+        let WasmRepr::Enum(variants) = enum_repr else {
+            panic!("not an enum: {enum_repr:?}")
+        };
+
+        // Pop the fields into scratch locals...
+        let scratch_locals: Vec<WasmLocal> = variants[variant_index]
+            .iter()
+            .flat_map(|r| r.stack_val_tys())
+            .map(|v| self.fresh_local_index(v))
+            .collect();
+        for &local in scratch_locals.iter().rev() {
+            self.pop_to_local(self.wasm_locals[local.index as usize], local);
+        }
+
+        // ...and then push the tag followed by each slot,
+        // using a zero value for the slots that the variant does not use.
+        self.instructions
+            .push(Instruction::I32Const(variant_index as i32));
+        let scratch_places: Vec<Arc<WasmPlaceRepr>> = scratch_locals
+            .iter()
+            .map(|&local| {
+                Arc::new(WasmPlaceRepr::Local(
+                    local,
+                    self.wasm_locals[local.index as usize],
                 ))
+            })
+            .collect();
+        let mut cursor = SlotCursor::new(&scratch_places);
+        for v in enum_repr.union_val_tys() {
+            match cursor.next_slot(v) {
+                Some(place) => self.push_from(&place),
+                None => self.instructions.push(match v {
+                    ValType::I32 => Instruction::I32Const(0),
+                    ValType::I64 => Instruction::I64Const(0),
+                    ValType::F32 => Instruction::F32Const(0.0),
+                    ValType::F64 => Instruction::F64Const(0.0),
+                    ValType::V128 | ValType::Ref(_) => panic!("unexpected val type {v:?}"),
+                }),
             }
-            WasmRepr::Nothing => Arc::new(WasmPlaceRepr::Nowhere),
         }
     }
 
     /// Create a fresh local index storing a value of type `v`.
-    fn fresh_local_index(&mut self, v: ValType) -> WasmLocal {
+    pub(super) fn fresh_local_index(&mut self, v: ValType) -> WasmLocal {
         let index = u32::try_from(self.wasm_locals.len()).expect("too many locals");
         self.wasm_locals.push(v);
         WasmLocal { index }
     }
 
-    /// Create a fresh slot in memory storing a value of type `v`.
-    fn fresh_memory_slot(&mut self, v: ValType) -> WasmPointer {
-        let offset = self.wasm_stack_frame_size;
-        self.wasm_stack_frame_size += val_type_size_in_bytes(v);
-        WasmPointer {
-            base_variable: self.wasm_stack_pointer,
-            offset,
-        }
-    }
-
     /// Push a value of type `val_type` found in `local`.
-    fn push_from_local(&mut self, val_type: wasm_encoder::ValType, local: WasmLocal) {
+    pub(super) fn push_from_local(&mut self, val_type: wasm_encoder::ValType, local: WasmLocal) {
         assert_eq!(self.wasm_locals[local.index as usize], val_type);
         self.instructions.push(Instruction::LocalGet(local.index));
     }
 
     /// Pop a value of type `val_type` and store it in `local`.
-    fn pop_to_local(&mut self, v: ValType, local: WasmLocal) {
+    pub(super) fn pop_to_local(&mut self, v: ValType, local: WasmLocal) {
         assert_eq!(self.wasm_locals[local.index as usize], v);
         self.instructions.push(Instruction::LocalSet(local.index));
     }
 
     /// Push a value of type `val_type` found in the given memory slot.
-    fn push_from_memory(
-        &mut self,
-        v: ValType,
-        WasmPointer {
-            base_variable,
-            offset,
-        }: WasmPointer,
-    ) {
-        let offset = offset as u64;
+    fn push_from_memory(&mut self, v: ValType, pointer: WasmPointer) {
+        self.push_from_local(ValType::I32, pointer.base_variable);
+        let memarg = memarg(pointer.offset);
         self.instructions.push(match v {
-            ValType::I32 => Instruction::I32Load(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::I64 => Instruction::I64Load(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::F32 => Instruction::F32Load(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::F64 => Instruction::F64Load(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
+            ValType::I32 => Instruction::I32Load(memarg),
+            ValType::I64 => Instruction::I64Load(memarg),
+            ValType::F32 => Instruction::F32Load(memarg),
+            ValType::F64 => Instruction::F64Load(memarg),
             ValType::V128 | ValType::Ref(_) => panic!("unexpected val type {v:?}"),
         });
     }
@@ -293,41 +474,35 @@ impl<'db> ExprCodegen<'_, 'db> {
     }
 
     /// Pop a value of type `val_type` and store it to the given memory slot.
-    fn pop_to_memory(
-        &mut self,
-        v: ValType,
-        WasmPointer {
-            base_variable,
-            offset,
-        }: WasmPointer,
-    ) {
-        let offset = offset as u64;
+    fn pop_to_memory(&mut self, v: ValType, pointer: WasmPointer) {
+        // Wasm stores expect the address beneath the value,
+        // so stash the value in a scratch local while we push the address.
+        let scratch = self.fresh_local_index(v);
+        self.pop_to_local(v, scratch);
+        self.push_from_local(ValType::I32, pointer.base_variable);
+        self.push_from_local(v, scratch);
+
+        let memarg = memarg(pointer.offset);
         self.instructions.push(match v {
-            ValType::I32 => Instruction::I32Store(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::I64 => Instruction::I64Store(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::F32 => Instruction::F32Store(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
-            ValType::F64 => Instruction::F64Store(wasm_encoder::MemArg {
-                offset,
-                align: 4,
-                memory_index: base_variable.index,
-            }),
+            ValType::I32 => Instruction::I32Store(memarg),
+            ValType::I64 => Instruction::I64Store(memarg),
+            ValType::F32 => Instruction::F32Store(memarg),
+            ValType::F64 => Instruction::F64Store(memarg),
             ValType::V128 | ValType::Ref(_) => panic!("unexpected val type {v:?}"),
         });
     }
 }
 
+/// Memory operand for accessing `offset` bytes past the address on the stack.
+/// Our stack frames only guarantee 4-byte alignment and there is just one memory.
+fn memarg(offset: u32) -> wasm_encoder::MemArg {
+    wasm_encoder::MemArg {
+        offset: offset as u64,
+        align: 2,
+        memory_index: 0,
+    }
+}
+
 impl WasmRepr {
     /// Primitive WASM values needed for a value with this representation stored on the WASM stack or in memory.
     pub fn flatten(&self) -> Vec<ValType> {
@@ -342,10 +517,68 @@ impl WasmRepr {
                 .chain(fields.iter().flat_map(|r| r.local_val_tys()))
                 .collect(),
 
+            // Enums begin with an `I32` tag.
+            WasmRepr::Enum(_) => std::iter::once(ValType::I32)
+                .chain(self.union_val_tys())
+                .collect(),
+
             WasmRepr::Nothing => vec![],
         }
     }
 
+    /// Returns the types of the union slots that follow the tag of an enum.
+    /// Each variant stores its flattened fields in the slots, with the `n`th value of a
+    /// given [`ValType`][] going into the `n`th slot of that type. Therefore there are
+    /// as many slots of each type as the most used by any one variant, ordered by type.
+    pub fn union_val_tys(&self) -> Vec<ValType> {
+        let WasmRepr::Enum(variants) = self else {
+            panic!("not an enum: {self:?}")
+        };
+        UNION_VAL_TYS
+            .iter()
+            .flat_map(|&v| {
+                let count = variants
+                    .iter()
+                    .map(|fields| {
+                        fields
+                            .iter()
+                            .flat_map(|r| r.stack_val_tys())
+                            .filter(|&u| u == v)
+                            .count()
+                    })
+                    .max()
+                    .unwrap_or(0);
+                std::iter::repeat_n(v, count)
+            })
+            .collect()
+    }
+
+    /// Primitive WASM values pushed onto the WASM stack by [`ExprCodegen::push_from`]
+    /// for a value with this representation. Unlike [`Self::flatten`], this includes the
+    /// values for classes nested within other values. These are the values that
+    /// each variant of an enum stores in its union slots.
+    fn stack_val_tys(&self) -> Vec<ValType> {
+        match self {
+            WasmRepr::Val(val_type) => vec![*val_type],
+            WasmRepr::Struct(fields) => fields.iter().flat_map(|r| r.stack_val_tys()).collect(),
+            WasmRepr::Class(fields) => std::iter::once(ValType::I32)
+                .chain(fields.iter().flat_map(|r| r.stack_val_tys()))
+                .collect(),
+            WasmRepr::Enum(_) => self.flatten(),
+            WasmRepr::Nothing => vec![],
+        }
+    }
+
+    /// True if storing a value with this representation requires storing a class.
+    fn contains_class(&self) -> bool {
+        match self {
+            WasmRepr::Val(_) | WasmRepr::Nothing => false,
+            WasmRepr::Struct(fields) => fields.iter().any(|r| r.contains_class()),
+            WasmRepr::Class(_) => true,
+            WasmRepr::Enum(variants) => variants.iter().flatten().any(|r| r.contains_class()),
+        }
+    }
+
     /// Returns the types of the WASM local variables that would be used to store a value with this representation.
     /// Any data found inside of a class is stored in memory and hence not represented in the return type.
     pub fn local_val_tys(&self) -> Vec<ValType> {
@@ -353,11 +586,157 @@ impl WasmRepr {
             WasmRepr::Val(val_type) => vec![*val_type],
             WasmRepr::Struct(fields) => fields.iter().flat_map(|r| r.local_val_tys()).collect(),
             WasmRepr::Class(_) => vec![],
+            WasmRepr::Enum(variants) => {
+                if variants.iter().flatten().any(|r| r.contains_class()) {
+                    vec![]
+                } else {
+                    self.flatten()
+                }
+            }
             WasmRepr::Nothing => vec![],
         }
     }
 }
 
+/// The types that may appear in the union slots of an enum, in the order the slots appear.
+const UNION_VAL_TYS: &[ValType] = &[ValType::I32, ValType::I64, ValType::F32, ValType::F64];
+
+/// Assigns the values of a variant's fields to the union slots of an enum.
+struct SlotCursor<'s> {
+    slots: &'s [Arc<WasmPlaceRepr>],
+
+    /// For each type in [`UNION_VAL_TYS`], the number of slots of that type assigned so far.
+    used: [usize; UNION_VAL_TYS.len()],
+}
+
+impl<'s> SlotCursor<'s> {
+    fn new(slots: &'s [Arc<WasmPlaceRepr>]) -> Self {
+        Self {
+            slots,
+            used: [0; UNION_VAL_TYS.len()],
+        }
+    }
+
+    /// Returns the next unassigned slot of type `v`, if any.
+    fn next_slot(&mut self, v: ValType) -> Option<Arc<WasmPlaceRepr>> {
+        let type_index = UNION_VAL_TYS
+            .iter()
+            .position(|&u| u == v)
+            .unwrap_or_else(|| panic!("unexpected val type {v:?}"));
+        let slot = self
+            .slots
+            .iter()
+            .filter(|slot| slot_val_type(slot) == v)
+            .nth(self.used[type_index])?;
+        self.used[type_index] += 1;
+        Some(slot.clone())
+    }
+
+    /// Returns the place for a value with representation `repr` made up of the next unassigned slots.
+    fn place_for(&mut self, repr: &WasmRepr) -> Arc<WasmPlaceRepr> {
+        match repr {
+            WasmRepr::Val(v) => self.next_slot(*v).expect("not enough union slots"),
+            WasmRepr::Struct(fields) => Arc::new(WasmPlaceRepr::Struct(
+                fields.iter().map(|r| self.place_for(r)).collect(),
+            )),
+            WasmRepr::Class(fields) => {
+                let flags = self
+                    .next_slot(ValType::I32)
+                    .expect("not enough union slots");
+                let WasmPlaceRepr::Heap(flags, _) = *flags else {
+                    panic!("class data must be stored in memory: {flags:?}")
+                };
+                Arc::new(WasmPlaceRepr::Class(
+                    flags,
+                    fields.iter().map(|r| self.place_for(r)).collect(),
+                ))
+            }
+            WasmRepr::Enum(variants) => {
+                let tag = self
+                    .next_slot(ValType::I32)
+                    .expect("not enough union slots");
+                let slots = repr
+                    .union_val_tys()
+                    .into_iter()
+                    .map(|v| self.next_slot(v).expect("not enough union slots"))
+                    .collect();
+                Arc::new(enum_place(tag, slots, variants))
+            }
+            WasmRepr::Nothing => Arc::new(WasmPlaceRepr::Nowhere),
+        }
+    }
+}
+
+/// The representation for a Dada place found in WASM memory, starting at `pointer`,
+/// that stores values with representation `repr`. Advances `pointer` past the end of the place.
+///
+/// Values are laid out one after the other, so this is also used to find the fields
+/// of a class instance given a pointer to it (e.g., a lease).
+pub(super) fn place_in_memory(repr: &WasmRepr, pointer: &mut WasmPointer) -> Arc<WasmPlaceRepr> {
+    match repr {
+        WasmRepr::Val(val_type) => {
+            Arc::new(WasmPlaceRepr::Heap(pointer.advance(*val_type), *val_type))
+        }
+        WasmRepr::Struct(vec) => Arc::new(WasmPlaceRepr::Struct(
+            vec.iter().map(|r| place_in_memory(r, pointer)).collect(),
+        )),
+        WasmRepr::Class(vec) => {
+            let flag_word = pointer.advance(ValType::I32);
+            Arc::new(WasmPlaceRepr::Class(
+                flag_word,
+                vec.iter().map(|r| place_in_memory(r, pointer)).collect(),
+            ))
+        }
+        WasmRepr::Enum(variants) => {
+            let tag = place_in_memory(&WasmRepr::Val(ValType::I32), pointer);
+            let slots = repr
+                .union_val_tys()
+                .into_iter()
+                .map(|v| place_in_memory(&WasmRepr::Val(v), pointer))
+                .collect();
+            Arc::new(enum_place(tag, slots, variants))
+        }
+        WasmRepr::Nothing => Arc::new(WasmPlaceRepr::Nowhere),
+    }
+}
+
+/// Number of bytes occupied by a value with representation `repr` when stored in memory.
+pub(super) fn size_in_memory(repr: &WasmRepr) -> u32 {
+    let mut pointer = WasmPointer::new(WasmLocal { index: 0 });
+    place_in_memory(repr, &mut pointer);
+    pointer.offset
+}
+
+/// Creates the place for an enum whose tag is stored in `tag` and whose union slots
+/// are stored in `slots`. The fields of each variant are assigned to slots as
+/// described in [`WasmRepr::union_val_tys`].
+fn enum_place(
+    tag: Arc<WasmPlaceRepr>,
+    slots: Vec<Arc<WasmPlaceRepr>>,
+    variants: &[Vec<WasmRepr>],
+) -> WasmPlaceRepr {
+    let variants = variants
+        .iter()
+        .map(|fields| {
+            let mut cursor = SlotCursor::new(&slots);
+            fields.iter().map(|r| cursor.place_for(r)).collect()
+        })
+        .collect();
+    WasmPlaceRepr::Enum {
+        tag,
+        slots,
+        variants,
+    }
+}
+
+/// The type of the value stored in a union slot.
+fn slot_val_type(slot: &WasmPlaceRepr) -> ValType {
+    match *slot {
+        WasmPlaceRepr::Local(_, v) | WasmPlaceRepr::Heap(_, v) => v,
+        _ => panic!("unexpected union slot {slot:?}"),
+    }
+}
+
 fn val_type_size_in_bytes(v: ValType) -> u32 {
     match v {
         ValType::I32 => 4,
@@ -379,3 +758,20 @@ pub struct WasmPointer {
     base_variable: WasmLocal,
     offset: u32,
 }
+
+impl WasmPointer {
+    /// Pointer to the address stored in `base_variable`.
+    pub(super) fn new(base_variable: WasmLocal) -> Self {
+        WasmPointer {
+            base_variable,
+            offset: 0,
+        }
+    }
+
+    /// Returns `self` and then moves `self` past a value of type `v`.
+    fn advance(&mut self, v: ValType) -> WasmPointer {
+        let slot = *self;
+        self.offset += val_type_size_in_bytes(v);
+        slot
+    }
+}
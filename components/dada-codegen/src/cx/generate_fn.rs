@@ -10,7 +10,10 @@ use dada_ir_sym::{
 use dada_util::Map;
 use wasm_encoder::ValType;
 
-use super::{Cx, FnIndex, FnKey, generate_expr::ExprCodegen, wasm_repr::WasmReprCx};
+use super::{
+    Cx, FnIndex, FnKey, generate_expr::ExprCodegen, host_fn::HostFn, intrinsic_fn::IntrinsicFn,
+    wasm_repr::WasmReprCx,
+};
 
 impl<'db> Cx<'db> {
     /// Declares an instantiation of a function with a given set of arguments and returns its index.
@@ -58,11 +61,11 @@ impl<'db> Cx<'db> {
         };
 
         // Add to the WASM function section
-        let fn_index = FnIndex(self.function_section.len());
+        let fn_index = FnIndex(self.num_imported_fns() + self.function_section.len());
         self.function_section.function(u32::from(ty_index));
 
         // Record on the queue to generate code
-        self.codegen_queue.push(key.clone().into());
+        self.codegen_queue.push_back(key.clone().into());
 
         // Memoize the result for later
         self.functions.insert(key, fn_index);
@@ -72,11 +75,7 @@ impl<'db> Cx<'db> {
 
     pub(crate) fn codegen_fn(&mut self, FnKey(function, generics): FnKey<'db>) {
         let db = self.db;
-
-        let object_check_body = match function.checked_body(self.db) {
-            Some(body) => body,
-            None => panic!("asked to codegen function with no body: {function:?}"),
-        };
+        let host_fn = HostFn::for_function(db, function, &generics);
 
         let CodegenSignature {
             inputs,
@@ -85,15 +84,28 @@ impl<'db> Cx<'db> {
         } = self.codegen_signature(function, &generics);
 
         // Generate the function body.
-        let function = {
+        let wasm_function = {
             let mut ecx = ExprCodegen::new(self, generics);
             ecx.pop_arguments(inputs, &input_output.input_tys);
-            ecx.push_expr(object_check_body);
-            ecx.pop_and_return(object_check_body.ty(db));
+            if let Some(object_check_body) = function.checked_body(db) {
+                ecx.push_expr(object_check_body);
+                ecx.pop_and_return(object_check_body.ty(db));
+            } else if let Some(host_fn) = host_fn {
+                // Some functions without a body are implemented by the host;
+                // we generate a small wrapper that forwards to the import.
+                ecx.push_host_call(host_fn, inputs, &input_output);
+                ecx.pop_and_return(input_output.output_ty);
+            } else if let Some(intrinsic_fn) = IntrinsicFn::for_function(db, function) {
+                // Others are implemented by the compiler.
+                ecx.push_intrinsic_call(intrinsic_fn, inputs, &input_output);
+                ecx.pop_and_return(input_output.output_ty);
+            } else {
+                panic!("asked to codegen function with no body: {function:?}");
+            }
             ecx.into_function()
         };
 
-        self.code_section.function(&function);
+        self.code_section.function(&wasm_function);
     }
 
     fn codegen_signature(
@@ -117,8 +129,9 @@ impl<'db> Cx<'db> {
 
                 CodegenSignature {
                     inputs: &symbols.input_variables,
-                    generics: symbols
-                        .generic_variables
+                    generics: signature
+                        .input_output(self.db)
+                        .variables
                         .iter()
                         .copied()
                         .zip(generics.iter().copied())
@@ -0,0 +1,236 @@
+//! The runtime ABI between generated code and the host.
+//!
+//! Functions declared without a body in `libdada` (e.g., `print`) are implemented
+//! by the host, unless the compiler generates them (see [`IntrinsicFn`][]).
+//! Each one becomes a wasm import from the [`HOST_MODULE`] module.
+//! The generated module also exports its linear memory as [`MEMORY_EXPORT`],
+//! the address where the stack begins as [`STACK_START_EXPORT`],
+//! and a function that allocates heap memory as [`ALLOC_EXPORT`].
+//!
+//! The host side of this ABI lives in `dada-runtime` and must be kept in sync.
+//!
+//! [`IntrinsicFn`]: super::intrinsic_fn::IntrinsicFn
+
+use dada_ir_sym::{
+    ir::{
+        functions::SymFunction,
+        primitive::SymPrimitiveKind,
+        types::{SymGenericTerm, SymTy, SymTyKind, SymTyName},
+    },
+    well_known,
+};
+use wasm_encoder::{EntityType, ExportKind, ValType};
+
+use super::{Cx, FnIndex, generate_expr::ExprCodegen};
+
+/// Module name used for all functions imported from the host.
+pub(crate) const HOST_MODULE: &str = "dada";
+
+/// Name under which the linear memory is exported.
+pub(crate) const MEMORY_EXPORT: &str = "memory";
+
+/// Name under which the (immutable, `i32`) global holding the initial stack pointer is exported.
+pub(crate) const STACK_START_EXPORT: &str = "stack_start";
+
+/// Name under which the function that allocates heap memory is exported.
+/// It takes a size in bytes and returns the address of the allocation.
+/// Host functions that return a string use it to allocate the string's data.
+pub(crate) const ALLOC_EXPORT: &str = "alloc";
+
+/// Functions provided by the host.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum HostFn {
+    /// `print(s: String)`: receives the address of the string data and its length in bytes.
+    Print,
+
+    /// `String.concat(self, other: String)`: receives the address and length of the data of each string;
+    /// returns the address and length of the data for their concatenation.
+    Concat,
+
+    /// `primitive_to_string[T](value: T)` for a primitive type `T`: receives the value,
+    /// whose wasm representation is described by the [`PrimitiveFormat`];
+    /// returns the address and length of the data for the string.
+    PrimitiveToString(PrimitiveFormat),
+}
+
+/// How the host should interpret a primitive value that it is asked to convert to a string.
+/// Small integers are promoted to 32 bits, so they share a format with `i32` or `u32`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum PrimitiveFormat {
+    Bool,
+    Char,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+}
+
+impl HostFn {
+    /// All host functions. Their imports are declared in this order, so the
+    /// import for `ALL[i]` has function index `i`.
+    pub(crate) const ALL: &[HostFn] = &[
+        HostFn::Print,
+        HostFn::Concat,
+        HostFn::PrimitiveToString(PrimitiveFormat::Bool),
+        HostFn::PrimitiveToString(PrimitiveFormat::Char),
+        HostFn::PrimitiveToString(PrimitiveFormat::I32),
+        HostFn::PrimitiveToString(PrimitiveFormat::U32),
+        HostFn::PrimitiveToString(PrimitiveFormat::I64),
+        HostFn::PrimitiveToString(PrimitiveFormat::U64),
+        HostFn::PrimitiveToString(PrimitiveFormat::F32),
+        HostFn::PrimitiveToString(PrimitiveFormat::F64),
+    ];
+
+    /// The host function that implements `function` when instantiated with `generics`, if any.
+    pub(crate) fn for_function<'db>(
+        db: &'db dyn crate::Db,
+        function: SymFunction<'db>,
+        generics: &[SymGenericTerm<'db>],
+    ) -> Option<HostFn> {
+        if well_known::print_fn(db) == Ok(function) {
+            return Some(HostFn::Print);
+        }
+        if well_known::string_concat_fn(db) == Ok(function) {
+            return Some(HostFn::Concat);
+        }
+        if well_known::primitive_to_string_fn(db) == Ok(function) {
+            let &[ty] = generics else {
+                panic!(
+                    "expected `primitive_to_string` to have one generic argument, found {generics:?}"
+                )
+            };
+            let format = PrimitiveFormat::for_ty(db, ty.assert_type(db)).unwrap_or_else(|| {
+                panic!("`primitive_to_string` instantiated with a non-primitive type: {ty:?}")
+            });
+            return Some(HostFn::PrimitiveToString(format));
+        }
+        None
+    }
+
+    /// Name of the wasm import.
+    pub(crate) fn import_name(self) -> &'static str {
+        match self {
+            HostFn::Print => "print",
+            HostFn::Concat => "concat",
+            HostFn::PrimitiveToString(format) => match format {
+                PrimitiveFormat::Bool => "bool_to_string",
+                PrimitiveFormat::Char => "char_to_string",
+                PrimitiveFormat::I32 => "i32_to_string",
+                PrimitiveFormat::U32 => "u32_to_string",
+                PrimitiveFormat::I64 => "i64_to_string",
+                PrimitiveFormat::U64 => "u64_to_string",
+                PrimitiveFormat::F32 => "f32_to_string",
+                PrimitiveFormat::F64 => "f64_to_string",
+            },
+        }
+    }
+
+    /// Wasm types of the values passed to the host.
+    fn wasm_inputs(self) -> Vec<ValType> {
+        match self {
+            HostFn::Print => vec![ValType::I32, ValType::I32],
+            HostFn::Concat => vec![ValType::I32; 4],
+            HostFn::PrimitiveToString(format) => vec![format.val_type()],
+        }
+    }
+
+    /// Wasm types of the values returned by the host.
+    fn wasm_outputs(self) -> Vec<ValType> {
+        match self {
+            HostFn::Print => vec![],
+            HostFn::Concat | HostFn::PrimitiveToString(_) => vec![ValType::I32, ValType::I32],
+        }
+    }
+
+    /// Function index of the import for this host function.
+    pub(crate) fn fn_index(self) -> FnIndex {
+        let index = HostFn::ALL.iter().position(|&h| h == self).unwrap();
+        FnIndex(index as u32)
+    }
+}
+
+impl PrimitiveFormat {
+    /// The format for values of type `ty`, or `None` if `ty` is not a primitive type.
+    fn for_ty<'db>(db: &'db dyn crate::Db, ty: SymTy<'db>) -> Option<PrimitiveFormat> {
+        match *ty.kind(db) {
+            SymTyKind::Named(SymTyName::Primitive(primitive), _) => {
+                Some(PrimitiveFormat::for_kind(primitive.kind(db)))
+            }
+            SymTyKind::Perm(_, ty) => PrimitiveFormat::for_ty(db, ty),
+            _ => None,
+        }
+    }
+
+    /// The format for values of the given kind; this must agree with their representation
+    /// (see `WasmReprCx::wasm_valtype_for_primitive_kind`).
+    fn for_kind(kind: SymPrimitiveKind) -> PrimitiveFormat {
+        match kind {
+            SymPrimitiveKind::Bool => PrimitiveFormat::Bool,
+            SymPrimitiveKind::Char => PrimitiveFormat::Char,
+            SymPrimitiveKind::Int { bits: 0..=32 } | SymPrimitiveKind::Isize => {
+                PrimitiveFormat::I32
+            }
+            SymPrimitiveKind::Uint { bits: 0..=32 } | SymPrimitiveKind::Usize => {
+                PrimitiveFormat::U32
+            }
+            SymPrimitiveKind::Int { bits: _ } => PrimitiveFormat::I64,
+            SymPrimitiveKind::Uint { bits: _ } => PrimitiveFormat::U64,
+            SymPrimitiveKind::Float { bits: 32 } => PrimitiveFormat::F32,
+            SymPrimitiveKind::Float { bits: _ } => PrimitiveFormat::F64,
+        }
+    }
+
+    /// The wasm type used to pass values in this format.
+    fn val_type(self) -> ValType {
+        match self {
+            PrimitiveFormat::Bool
+            | PrimitiveFormat::Char
+            | PrimitiveFormat::I32
+            | PrimitiveFormat::U32 => ValType::I32,
+            PrimitiveFormat::I64 | PrimitiveFormat::U64 => ValType::I64,
+            PrimitiveFormat::F32 => ValType::F32,
+            PrimitiveFormat::F64 => ValType::F64,
+        }
+    }
+}
+
+impl Cx<'_> {
+    /// Declares the wasm imports for every host function.
+    /// Must be called before any other function is declared, since imported
+    /// functions occupy the start of the function index space.
+    pub(crate) fn declare_host_fns(&mut self) {
+        assert_eq!(self.function_section.len(), 0);
+        for &host_fn in HostFn::ALL {
+            let ty_index = self.declare_fn_type(host_fn.wasm_inputs(), host_fn.wasm_outputs());
+            self.import_section.import(
+                HOST_MODULE,
+                host_fn.import_name(),
+                EntityType::Function(u32::from(ty_index)),
+            );
+        }
+    }
+
+    /// Declares and generates the function exported as [`ALLOC_EXPORT`].
+    /// Must be called right after [`Self::declare_host_fns`], since its body is
+    /// generated immediately and the code section must follow the order of the function section.
+    pub(crate) fn declare_alloc_fn(&mut self) {
+        assert_eq!(self.function_section.len(), 0);
+        let ty_index = self.declare_fn_type(vec![ValType::I32], vec![ValType::I32]);
+        let fn_index = self.num_imported_fns();
+        self.function_section.function(u32::from(ty_index));
+        self.export_section
+            .export(ALLOC_EXPORT, ExportKind::Func, fn_index);
+
+        let mut ecx = ExprCodegen::new(self, Default::default());
+        ecx.push_alloc_fn_body();
+        let wasm_function = ecx.into_function();
+        self.code_section.function(&wasm_function);
+    }
+
+    /// Number of functions imported from the host.
+    pub(crate) fn num_imported_fns(&self) -> u32 {
+        HostFn::ALL.len() as u32
+    }
+}
@@ -0,0 +1,38 @@
+//! Functions declared without a body in `libdada` whose implementation
+//! is generated by the compiler rather than provided by the host (see [`HostFn`][]).
+//!
+//! [`HostFn`]: super::host_fn::HostFn
+
+use dada_ir_sym::{ir::functions::SymFunction, well_known};
+
+/// Functions implemented by the compiler.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum IntrinsicFn {
+    /// `Vec.new() -> Vec[T]`: creates an empty vector that has not yet allocated any memory.
+    VecNew,
+
+    /// `Vec.push(mut self, value: T)`: appends `value`, growing the heap allocation if it is full.
+    VecPush,
+
+    /// `Vec.pop(mut self) -> T`: removes the last element, trapping if there is none.
+    VecPop,
+}
+
+impl IntrinsicFn {
+    /// The intrinsic function that implements `function`, if any.
+    pub(crate) fn for_function<'db>(
+        db: &'db dyn crate::Db,
+        function: SymFunction<'db>,
+    ) -> Option<IntrinsicFn> {
+        if well_known::vec_new_fn(db) == Ok(function) {
+            return Some(IntrinsicFn::VecNew);
+        }
+        if well_known::vec_push_fn(db) == Ok(function) {
+            return Some(IntrinsicFn::VecPush);
+        }
+        if well_known::vec_pop_fn(db) == Ok(function) {
+            return Some(IntrinsicFn::VecPop);
+        }
+        None
+    }
+}
@@ -1,11 +1,12 @@
 use dada_ir_sym::{
     ir::{
-        classes::{SymAggregate, SymAggregateStyle},
+        classes::{SymAggregate, SymAggregateStyle, SymField},
         primitive::SymPrimitiveKind,
         types::{SymGenericTerm, SymPerm, SymPermKind, SymPlace, SymTy, SymTyKind, SymTyName},
         variables::SymVariable,
     },
     prelude::CheckedFieldTy,
+    well_known,
 };
 use dada_util::Map;
 use wasm_encoder::ValType;
@@ -43,6 +44,12 @@ pub(crate) enum WasmRepr {
     /// whatever values are needed to represent the fields, stored as a `Vec<WasmRepr>`.
     Class(Vec<WasmRepr>),
 
+    /// An enum, stored as a tagged union. The `Vec<Vec<WasmRepr>>` argument
+    /// has, for each variant, the values needed to represent its fields.
+    /// The data begins with an `I32` tag identifying the variant (its index)
+    /// followed by enough [union slots](`WasmRepr::union_val_tys`) to store the fields of any variant.
+    Enum(Vec<Vec<WasmRepr>>),
+
     /// No data at all (something zero-sized).
     Nothing,
 }
@@ -85,24 +92,33 @@ impl<'g, 'db> WasmReprCx<'g, 'db> {
     fn wasm_repr_of_perm_type(&mut self, sym_perm: SymPerm<'db>, sym_ty: SymTy<'db>) -> WasmRepr {
         let db = self.db;
         match *sym_perm.kind(db) {
-            SymPermKind::Mutable(_) => self.wasm_pointer(),
-            SymPermKind::My | SymPermKind::Our | SymPermKind::Referenced(_) => {
-                self.wasm_repr_of_type(sym_ty)
-            }
+            SymPermKind::Error(_) => WasmRepr::Nothing,
+            _ if self.is_leased_perm(sym_perm) => self.wasm_pointer(),
+            _ => self.wasm_repr_of_type(sym_ty),
+        }
+    }
+
+    /// True if a value with the permission `sym_perm` is represented by a pointer to the data
+    /// (i.e., it is a lease). Other permissions do not change the representation of a value.
+    pub(super) fn is_leased_perm(&self, sym_perm: SymPerm<'db>) -> bool {
+        let db = self.db;
+        match *sym_perm.kind(db) {
+            SymPermKind::Mutable(_) => true,
+            SymPermKind::My | SymPermKind::Our | SymPermKind::Referenced(_) => false,
             SymPermKind::Var(sym_variable) => {
                 let result = self
                     .generics
                     .get(&sym_variable)
                     .expect("expected value for each generic type")
                     .assert_perm(db);
-                self.wasm_repr_of_perm_type(result, sym_ty)
+                self.is_leased_perm(result)
             }
-            SymPermKind::Error(_) => WasmRepr::Nothing,
-            SymPermKind::Apply(left, _) => self.wasm_repr_of_perm_type(left, sym_ty),
+            SymPermKind::Error(_) => false,
+            SymPermKind::Apply(left, _) => self.is_leased_perm(left),
             SymPermKind::Infer(_infer_var_index) => unreachable!(),
             SymPermKind::Or(perm_l, _perm_r) => {
                 // the type check should ensure `perm_l` and `perm_r` are compatible
-                self.wasm_repr_of_perm_type(perm_l, sym_ty)
+                self.is_leased_perm(perm_l)
             }
         }
     }
@@ -118,6 +134,22 @@ impl<'g, 'db> WasmReprCx<'g, 'db> {
             SymTyName::Primitive(sym_primitive) => {
                 WasmRepr::Val(self.wasm_valtype_for_primitive_kind(sym_primitive.kind(db)))
             }
+            // `Pointer[T]` is a built-in struct represented as an address in linear memory.
+            SymTyName::Aggregate(aggr) if well_known::pointer_struct(db) == Ok(aggr) => {
+                self.wasm_pointer()
+            }
+            // enums are a tagged union of the fields of their variants
+            SymTyName::Aggregate(aggr) if aggr.is_enum(db) => WasmRepr::Enum(
+                aggr.variants(db)
+                    .iter()
+                    .map(|variant| {
+                        self.field_tys(variant.fields(db).iter().copied(), ty_args)
+                            .into_iter()
+                            .map(|ty| self.wasm_repr_of_type(ty))
+                            .collect()
+                    })
+                    .collect(),
+            ),
             SymTyName::Aggregate(aggr) => match aggr.style(db) {
                 // structs  have the fields inlined
                 SymAggregateStyle::Struct => {
@@ -129,9 +161,11 @@ impl<'g, 'db> WasmReprCx<'g, 'db> {
                 }
             },
             SymTyName::Future => {
+                // For now we execute async functions synchronously,
+                // so a future is represented by the value it produces.
                 assert_eq!(ty_args.len(), 1);
                 let ty_arg = ty_args[0].assert_type(db);
-                WasmRepr::Class(vec![self.wasm_repr_of_type(ty_arg)])
+                self.wasm_repr_of_type(ty_arg)
             }
             SymTyName::Tuple { arity } => {
                 assert_eq!(ty_args.len(), arity);
@@ -183,9 +217,18 @@ impl<'g, 'db> WasmReprCx<'g, 'db> {
         &self,
         aggr: SymAggregate<'db>,
         ty_args: &'a Vec<SymGenericTerm<'db>>,
+    ) -> Vec<SymTy<'db>> {
+        self.field_tys(aggr.fields(self.db), ty_args)
+    }
+
+    /// The types of the given fields, declared in some aggregate type, given the values `ty_args` for its generic arguments.
+    fn field_tys(
+        &self,
+        fields: impl Iterator<Item = SymField<'db>>,
+        ty_args: &[SymGenericTerm<'db>],
     ) -> Vec<SymTy<'db>> {
         let db = self.db;
-        aggr.fields(db)
+        fields
             .map(|f| f.checked_field_ty(db))
             .map(|ty| {
                 let ty = ty.substitute(db, ty_args);
@@ -5,7 +5,7 @@ use std::{
 
 use dada_ir_ast::{
     DebugEvent,
-    ast::{AstFunction, AstItem, AstMember, Identifier},
+    ast::{AstFunction, AstItem, AstMember, AstTraitMember, Identifier, SpanVec},
     diagnostic::Diagnostic,
     inputs::{CompilationRoot, Krate, SourceFile},
     span::AbsoluteSpan,
@@ -16,6 +16,8 @@ use url::Url;
 
 mod fork;
 pub use fork::Fork;
+mod manifest;
+pub use manifest::{MANIFEST_FILE_NAME, Manifest};
 mod realfs;
 pub use realfs::RealFs;
 mod vfs;
@@ -96,6 +98,61 @@ impl Compiler {
         Ok(source_file)
     }
 
+    /// Load every source file of the crate at `input`, which is either
+    /// a crate root like `foo.dada` or a directory like `foo/`.
+    /// In both cases, we load `foo.dada` (if it exists) and then
+    /// every `.dada` file found beneath `foo/`, so that modules which are
+    /// not (yet) referenced from the root are checked too.
+    /// If `input` is a directory with a `dada.toml`, the root named there is used instead.
+    pub fn load_crate_source_files(
+        &mut self,
+        input: &(impl ToUrl + ?Sized),
+    ) -> Fallible<Vec<SourceFile>> {
+        let input_url = input.to_url(&*self.vfs)?;
+        let input_path = UrlPath::from(input_url.clone());
+        let (root_url, directory_url) = if input_path.is_dada_file() {
+            (input_url.clone(), input_path.make_directory().url())
+        } else if let Some(manifest_url) = self.manifest_in_directory(&input_path) {
+            self.load_manifest(&manifest_url)?;
+            let root_url = self.manifest_root_url(&manifest_url);
+            let directory_url = UrlPath::from(root_url.clone()).make_directory().url();
+            (root_url, directory_url)
+        } else if self.vfs.is_directory(&input_url) {
+            (input_path.dada_url(), input_path.url())
+        } else {
+            bail!("expected a `.dada` file or a directory: `{input_url}`");
+        };
+
+        let mut urls = vec![];
+        if self.vfs.exists(&root_url) {
+            urls.push(root_url);
+        }
+        self.find_dada_files(&directory_url, &mut urls)?;
+
+        if urls.is_empty() {
+            bail!("no `.dada` files found at `{input_url}`");
+        }
+
+        urls.iter().map(|url| self.load_source_file(url)).collect()
+    }
+
+    /// Push the URL of every `.dada` file beneath `directory_url` onto `urls`.
+    fn find_dada_files(&self, directory_url: &Url, urls: &mut Vec<Url>) -> Fallible<()> {
+        if !self.vfs.is_directory(directory_url) {
+            return Ok(());
+        }
+
+        for entry in self.vfs.directory_entries(directory_url)? {
+            if self.vfs.is_directory(&entry) {
+                self.find_dada_files(&entry, urls)?;
+            } else if UrlPath::from(entry.clone()).is_dada_file() {
+                urls.push(entry);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the `SourceFile` for the given path.
     /// Errors if no source file was opened yet.
     pub fn get_previously_opened_source_file(
@@ -112,8 +169,13 @@ impl Compiler {
     }
 
     /// Given a .dada file, finds the enclosing crate and adds it into the list of crates.
-    /// Given some path `a/b/c.dada`, we decide that `c` is a submodule of `a/b` if there exists
-    /// a `a/b.dada`; otherwise, `c` is considered a crate of its own.
+    ///
+    /// If a `dada.toml` is found in an enclosing directory, it is loaded (along with
+    /// its dependencies) and, if the file belongs to the crate it declares, that crate is returned.
+    ///
+    /// Otherwise we guess from the file layout: given some path `a/b/c.dada`, we decide that
+    /// `c` is a submodule of `a/b` if there exists a `a/b.dada`; otherwise, `c` is considered
+    /// a crate of its own.
     pub fn add_crate_containing_source_file(&mut self, source_url: &Url) -> Fallible<Krate> {
         let url_path = UrlPath::from(source_url.clone());
 
@@ -121,6 +183,15 @@ impl Compiler {
             bail!("source URL not a `.dada` file: `{source_url}`");
         }
 
+        if let Some(manifest_url) = self.find_manifest(&url_path) {
+            let krate = self.load_manifest(&manifest_url)?;
+            let root_url = self.manifest_root_url(&manifest_url);
+            let root_path = UrlPath::from(root_url.clone());
+            if *source_url == root_url || url_path.starts_with(&root_path.make_directory()) {
+                return Ok(krate);
+            }
+        }
+
         // We are at `a/b/c.dada`. If there exists a path `a/b.dada`, then `c` is a submodule of `a.b`.
         // Otherwise, `c` is the root.
         let mut krate_path = url_path.clone();
@@ -135,6 +206,79 @@ impl Compiler {
         self.add_crate_with_root_path(&krate_path.dada_url())
     }
 
+    /// Search the directories enclosing `url_path` for a `dada.toml`.
+    fn find_manifest(&self, url_path: &UrlPath) -> Option<Url> {
+        let mut directory = url_path.clone();
+        while !directory.is_empty() {
+            directory = directory.pop();
+            if let Some(manifest_url) = self.manifest_in_directory(&directory) {
+                return Some(manifest_url);
+            }
+        }
+        None
+    }
+
+    /// If `directory` contains a `dada.toml`, return its URL.
+    fn manifest_in_directory(&self, directory: &UrlPath) -> Option<Url> {
+        if directory.is_dada_file() {
+            return None;
+        }
+
+        let mut manifest_path = directory.clone();
+        manifest_path.push(MANIFEST_FILE_NAME);
+        let manifest_url = manifest_path.url();
+        self.vfs.exists(&manifest_url).then_some(manifest_url)
+    }
+
+    /// Root file of the crate declared by a manifest that was previously loaded.
+    fn manifest_root_url(&self, manifest_url: &Url) -> Url {
+        self.inputs.lock().unwrap().manifests[manifest_url]
+            .1
+            .clone()
+    }
+
+    /// Load the `dada.toml` at `manifest_url`, adding the crate it declares
+    /// and (recursively) all of its dependencies. Returns the declared crate.
+    pub fn load_manifest(&mut self, manifest_url: &Url) -> Fallible<Krate> {
+        if let Some((krate, _)) = self.inputs.lock().unwrap().manifests.get(manifest_url) {
+            return Ok(*krate);
+        }
+
+        let contents = self.vfs.contents(manifest_url)?;
+        let manifest = Manifest::parse(manifest_url, &contents)?;
+
+        let root_url = manifest.root_url(manifest_url)?;
+        let root_path = UrlPath::from(root_url.clone());
+        if !root_path.is_dada_file() {
+            bail!("crate root should have `.dada` extension: `{root_url}` (in `{manifest_url}`)");
+        }
+
+        let krate = self.add_crate(
+            manifest.krate.name.clone(),
+            root_path.make_directory().url(),
+        )?;
+
+        // Record the manifest before loading dependencies so that cycles terminate.
+        self.inputs
+            .lock()
+            .unwrap()
+            .manifests
+            .insert(manifest_url.clone(), (krate, root_url));
+
+        for (dependency_name, dependency) in &manifest.dependencies {
+            let dependency_url = Manifest::dependency_manifest_url(manifest_url, dependency)?;
+            let dependency_krate = self.load_manifest(&dependency_url)?;
+            let actual_name = dependency_krate.name(self);
+            if actual_name != dependency_name {
+                bail!(
+                    "dependency `{dependency_name}` of `{manifest_url}` is the crate `{actual_name}`"
+                );
+            }
+        }
+
+        Ok(krate)
+    }
+
     /// Add a crate that is rooted in the given `dada` file.
     /// The crate is named after the file name.
     pub fn add_crate_with_root_path(&mut self, root_url: &Url) -> Fallible<Krate> {
@@ -174,6 +318,23 @@ impl Compiler {
         self.attach(|db| dada_probe::probe_expression_type(db, span))
     }
 
+    /// Return the location of the declaration referenced at the given `span`, formatted as
+    /// `line:column` (1-based), or `None` if there is no reference there.
+    /// Declarations in other files are prefixed with the file, as in `file:line:column`.
+    pub fn probe_definition(&self, span: AbsoluteSpan) -> Option<String> {
+        let definition = self.attach(|db| dada_probe::probe_definition(db, span))?;
+        let (line, column) = definition.source_file.line_col(self, definition.start);
+        let position = format!("{}:{}", line.as_u32() + 1, column.as_u32() + 1);
+        if definition.source_file == span.source_file {
+            Some(position)
+        } else {
+            Some(format!(
+                "{}:{position}",
+                definition.source_file.url_display(self)
+            ))
+        }
+    }
+
     /// Return compact AST representation of the expression at the given `span`.
     pub fn probe_ast(&self, span: AbsoluteSpan) -> Option<String> {
         self.attach(|db| dada_probe::probe_ast(db, span))
@@ -393,6 +554,9 @@ struct Inputs {
     source_files: Map<Url, SourceFile>,
     libdada_source_files: Map<String, SourceFile>,
     directories: Map<Krate, KrateSource>,
+
+    /// Manifests we have loaded, with the crate each declares and its root file.
+    manifests: Map<Url, (Krate, Url)>,
 }
 
 #[derive(FromImpls, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -434,6 +598,7 @@ fn fn_asts(db: &dyn Db, source_file: SourceFile) -> String {
                 for member in class_item.members(db) {
                     match member {
                         AstMember::Field(_field_decl) => (),
+                        AstMember::Variant(_variant) => (),
                         AstMember::Function(function) => {
                             writeln!(output, "### fn `{}`", function.name(db).id).unwrap();
                             writeln!(output).unwrap();
@@ -442,6 +607,14 @@ fn fn_asts(db: &dyn Db, source_file: SourceFile) -> String {
                     }
                 }
             }
+            AstItem::Trait(trait_item) => {
+                writeln!(output, "## trait `{}`", trait_item.name(db)).unwrap();
+                fn_asts_trait_members(db, &mut output, trait_item.members(db));
+            }
+            AstItem::Impl(impl_item) => {
+                writeln!(output, "## impl").unwrap();
+                fn_asts_trait_members(db, &mut output, impl_item.members(db));
+            }
             AstItem::Function(function) => {
                 writeln!(output, "## fn `{}`", function.name(db).id).unwrap();
                 writeln!(output).unwrap();
@@ -459,6 +632,23 @@ fn fn_asts(db: &dyn Db, source_file: SourceFile) -> String {
 
     return output;
 
+    fn fn_asts_trait_members<'db>(
+        db: &'db dyn Db,
+        output: &mut String,
+        members: &'db SpanVec<'db, AstTraitMember<'db>>,
+    ) {
+        for member in members {
+            match member {
+                AstTraitMember::AssociatedTy(_associated_ty) => (),
+                AstTraitMember::Function(function) => {
+                    writeln!(output, "### fn `{}`", function.name(db).id).unwrap();
+                    writeln!(output).unwrap();
+                    writeln!(output, "{}", fn_asts_fn(db, *function)).unwrap();
+                }
+            }
+        }
+    }
+
     fn fn_asts_fn<'db>(db: &'db dyn Db, function: AstFunction<'db>) -> String {
         if let Some(block) = function.body_block(db) {
             format!("{block:#?}")
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use dada_util::{Fallible, anyhow, bail};
+use serde::Deserialize;
+use url::Url;
+
+/// Name of the manifest file that describes a crate.
+pub const MANIFEST_FILE_NAME: &str = "dada.toml";
+
+/// Contents of a `dada.toml` file, e.g.:
+///
+/// ```toml
+/// [crate]
+/// name = "app"
+/// root = "src/app.dada"
+///
+/// [dependencies]
+/// util = { path = "../util" }
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(rename = "crate")]
+    pub krate: ManifestCrate,
+
+    /// Crates this crate may `use`, keyed by crate name.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, ManifestDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCrate {
+    /// Name by which other crates refer to this one.
+    pub name: String,
+
+    /// Root module of the crate, relative to the manifest.
+    /// Submodules live in the directory of the same name (e.g., `src/app/`).
+    /// Defaults to `<name>.dada`.
+    pub root: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDependency {
+    /// Directory containing the dependency's `dada.toml`, relative to this manifest.
+    pub path: String,
+}
+
+impl Manifest {
+    pub fn parse(manifest_url: &Url, contents: &str) -> Fallible<Self> {
+        let manifest: Manifest = toml::from_str(contents)
+            .map_err(|e| anyhow!("invalid manifest `{manifest_url}`: {e}"))?;
+
+        if manifest.krate.name.is_empty() {
+            bail!("invalid manifest `{manifest_url}`: crate name cannot be empty");
+        }
+
+        Ok(manifest)
+    }
+
+    /// URL of the crate's root `.dada` file.
+    pub fn root_url(&self, manifest_url: &Url) -> Fallible<Url> {
+        let root = match &self.krate.root {
+            Some(root) => root.clone(),
+            None => format!("{}.dada", self.krate.name),
+        };
+        Ok(manifest_url.join(&root)?)
+    }
+
+    /// URL of the manifest for `dependency`.
+    pub fn dependency_manifest_url(
+        manifest_url: &Url,
+        dependency: &ManifestDependency,
+    ) -> Fallible<Url> {
+        let path = dependency.path.trim_end_matches('/');
+        Ok(manifest_url.join(&format!("{path}/{MANIFEST_FILE_NAME}"))?)
+    }
+}
@@ -43,6 +43,26 @@ impl VirtualFileSystem for RealFs {
         }
     }
 
+    fn is_directory(&self, url: &Url) -> bool {
+        match Self::validate_scheme(url) {
+            Ok(path) => path.is_dir(),
+            Err(_) => false,
+        }
+    }
+
+    fn directory_entries(&self, url: &Url) -> Fallible<Vec<Url>> {
+        let path = Self::validate_scheme(url)?;
+        let mut entries = vec![];
+        for entry in std::fs::read_dir(&path)? {
+            let entry_path = entry?.path();
+            entries.push(Url::from_file_path(&entry_path).map_err(|()| {
+                anyhow!("unable to construct URL from `{}`", entry_path.display())
+            })?);
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
     fn path_url(&self, path: &Path) -> Fallible<Url> {
         let path = if let Some(base_dir) = &self.base_dir {
             base_dir.join(path)
@@ -10,6 +10,12 @@ pub trait VirtualFileSystem: Send + Sync + 'static {
     /// True if the given URL exists.
     fn exists(&self, url: &Url) -> bool;
 
+    /// True if the given URL is a directory.
+    fn is_directory(&self, url: &Url) -> bool;
+
+    /// Lists the entries of the directory at the given URL, sorted.
+    fn directory_entries(&self, url: &Url) -> Fallible<Vec<Url>>;
+
     /// (Try to) convert a path on the local file system to a URL
     fn path_url(&self, path: &Path) -> Fallible<Url>;
 
@@ -44,6 +50,11 @@ impl UrlPath {
         self.paths.is_empty()
     }
 
+    /// True if the components of `prefix` are a prefix of the components of `self`.
+    pub fn starts_with(&self, prefix: &UrlPath) -> bool {
+        self.paths.starts_with(&prefix.paths)
+    }
+
     /// Removes the final component (if any).
     /// Result will never be a dada file.
     pub fn pop(mut self) -> Self {
@@ -14,6 +14,8 @@ mod use_item;
 pub use use_item::*;
 mod class_item;
 pub use class_item::*;
+mod trait_item;
+pub use trait_item::*;
 mod member;
 pub use member::*;
 mod function;
@@ -49,6 +51,11 @@ impl<'db> Identifier<'db> {
         Identifier::new(db, "new")
     }
 
+    /// Create interned "_" identifier, used for wildcard patterns
+    pub fn underscore(db: &'db dyn crate::Db) -> Identifier<'db> {
+        Identifier::new(db, "_")
+    }
+
     /// Create interned "self" identifier
     pub fn self_ident(db: &'db dyn crate::Db) -> Identifier<'db> {
         Identifier::new(db, "self")
@@ -89,6 +96,8 @@ pub enum AstItem<'db> {
     SourceFile(SourceFile),
     Use(AstUse<'db>),
     Aggregate(AstAggregate<'db>),
+    Trait(AstTrait<'db>),
+    Impl(AstImpl<'db>),
     Function(AstFunction<'db>),
     MainFunction(AstMainFunction<'db>),
 }
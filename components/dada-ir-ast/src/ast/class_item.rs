@@ -52,4 +52,5 @@ impl<'db> Spanned<'db> for AstAggregate<'db> {
 pub enum AstAggregateKind {
     Class,
     Struct,
+    Enum,
 }
@@ -62,6 +62,10 @@ pub enum AstExprKind<'db> {
     /// `22`
     Literal(Literal<'db>),
 
+    /// `"x = {x}"`: a string literal with at least one interpolated expression.
+    /// String literals without interpolations are represented as a [`Literal`][].
+    InterpolatedString(Vec<AstStringPart<'db>>),
+
     /// `x`
     Id(SpannedIdentifier<'db>),
 
@@ -115,6 +119,30 @@ pub enum AstExprKind<'db> {
 
     /// If/else-if chain
     If(Vec<IfArm<'db>>),
+
+    /// `match E { P => E ... }`
+    Match {
+        scrutinee: AstExpr<'db>,
+        arms: SpanVec<'db, AstMatchArm<'db>>,
+    },
+
+    /// `loop { ... }`
+    Loop(AstBlock<'db>),
+
+    /// `unsafe { ... }`, a block in which unsafe functions may be called
+    Unsafe(AstBlock<'db>),
+
+    /// `while condition { ... }`
+    While {
+        condition: AstExpr<'db>,
+        body: AstBlock<'db>,
+    },
+
+    /// `break`, exits the innermost enclosing loop
+    Break,
+
+    /// `continue`, starts the next iteration of the innermost enclosing loop
+    Continue,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
@@ -134,6 +162,39 @@ pub struct IfArm<'db> {
     pub result: AstBlock<'db>,
 }
 
+/// `P => E`, one arm of a `match` expression
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
+pub struct AstMatchArm<'db> {
+    pub pattern: AstPattern<'db>,
+
+    /// the value if the pattern matches
+    pub result: AstExpr<'db>,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
+pub struct AstPattern<'db> {
+    pub span: Span<'db>,
+    pub kind: AstPatternKind<'db>,
+}
+
+impl<'db> Spanned<'db> for AstPattern<'db> {
+    fn span(&self, _db: &'db dyn crate::Db) -> Span<'db> {
+        self.span
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
+pub enum AstPatternKind<'db> {
+    /// `_`, matches anything
+    Wildcard,
+
+    /// `x`, matches anything and binds it to `x`
+    Binding(SpannedIdentifier<'db>),
+
+    /// `Enum.Variant` or `Enum.Variant(p0, ..., pN)`
+    Variant(AstPath<'db>, Option<SpanVec<'db, AstPattern<'db>>>),
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
 pub struct SpannedBinaryOp<'db> {
     pub span: Span<'db>,
@@ -146,6 +207,12 @@ pub enum AstBinaryOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     AndAnd,
     OrOr,
     GreaterThan,
@@ -153,6 +220,7 @@ pub enum AstBinaryOp {
     GreaterEqual,
     LessEqual,
     EqualEqual,
+    NotEqual,
     Assign,
 }
 
@@ -163,6 +231,12 @@ impl std::fmt::Display for AstBinaryOp {
             AstBinaryOp::Sub => write!(f, "-"),
             AstBinaryOp::Mul => write!(f, "*"),
             AstBinaryOp::Div => write!(f, "/"),
+            AstBinaryOp::Rem => write!(f, "%"),
+            AstBinaryOp::BitAnd => write!(f, "&"),
+            AstBinaryOp::BitOr => write!(f, "|"),
+            AstBinaryOp::BitXor => write!(f, "^"),
+            AstBinaryOp::Shl => write!(f, "<<"),
+            AstBinaryOp::Shr => write!(f, ">>"),
             AstBinaryOp::AndAnd => write!(f, "&&"),
             AstBinaryOp::OrOr => write!(f, "||"),
             AstBinaryOp::GreaterThan => write!(f, ">"),
@@ -170,6 +244,7 @@ impl std::fmt::Display for AstBinaryOp {
             AstBinaryOp::GreaterEqual => write!(f, ">="),
             AstBinaryOp::LessEqual => write!(f, "<="),
             AstBinaryOp::EqualEqual => write!(f, "=="),
+            AstBinaryOp::NotEqual => write!(f, "!="),
             AstBinaryOp::Assign => write!(f, "="),
         }
     }
@@ -211,10 +286,29 @@ pub struct Literal<'db> {
     pub text: String,
 }
 
+/// One piece of an interpolated string literal, in source order.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
+pub enum AstStringPart<'db> {
+    /// Literal text, with escape sequences already processed.
+    Text(String),
+
+    /// `{expr}`
+    Expr(AstExpr<'db>),
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
 pub enum LiteralKind {
     Boolean,
+
+    /// An integer like `22`, `0xFF`, or `22_u8`; the text is as written in the source.
     Integer,
+
+    /// A floating-point number like `1.5`, `2e10`, or `1_f32`; the text is as written in the source.
+    Float,
+
+    /// A character like `'c'`; the text is the character, with escapes already processed.
+    Char,
+
     String,
 }
 
@@ -0,0 +1,116 @@
+use dada_util::{FromImpls, SalsaSerialize};
+use salsa::Update;
+use serde::Serialize;
+
+use crate::{
+    ast::{AstVisibility, DeferredParse},
+    span::{Span, Spanned},
+};
+
+use super::{
+    AstFunction, AstGenericDecl, AstPath, AstTy, AstWhereClauses, Identifier, SpanVec,
+    SpannedIdentifier,
+};
+
+/// `trait $name[$generics] { ... }`
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct AstTrait<'db> {
+    pub span: Span<'db>,
+
+    /// Visibility of the trait
+    pub visibility: Option<AstVisibility<'db>>,
+
+    pub name: Identifier<'db>,
+
+    pub name_span: Span<'db>,
+
+    #[return_ref]
+    pub generics: Option<SpanVec<'db, AstGenericDecl<'db>>>,
+
+    #[return_ref]
+    pub where_clauses: Option<AstWhereClauses<'db>>,
+
+    /// The unparsed contents of the trait.
+    /// This can be parsed via the `members`
+    /// method defined in `dada_parser::prelude`.
+    #[return_ref]
+    pub contents: Option<DeferredParse<'db>>,
+}
+
+impl<'db> Spanned<'db> for AstTrait<'db> {
+    fn span(&self, db: &'db dyn crate::Db) -> Span<'db> {
+        AstTrait::span(*self, db)
+    }
+}
+
+/// `impl[$generics] $trait for $self_ty { ... }`
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct AstImpl<'db> {
+    pub span: Span<'db>,
+
+    /// Span of the `impl` keyword
+    pub impl_span: Span<'db>,
+
+    #[return_ref]
+    pub generics: Option<SpanVec<'db, AstGenericDecl<'db>>>,
+
+    /// The trait being implemented, e.g., `Place[P]`
+    pub trait_path: AstPath<'db>,
+
+    /// The type the trait is implemented for
+    pub self_ty: AstTy<'db>,
+
+    #[return_ref]
+    pub where_clauses: Option<AstWhereClauses<'db>>,
+
+    /// The unparsed contents of the impl.
+    /// This can be parsed via the `members`
+    /// method defined in `dada_parser::prelude`.
+    #[return_ref]
+    pub contents: Option<DeferredParse<'db>>,
+}
+
+impl<'db> Spanned<'db> for AstImpl<'db> {
+    fn span(&self, db: &'db dyn crate::Db) -> Span<'db> {
+        AstImpl::span(*self, db)
+    }
+}
+
+/// Members of a trait or impl.
+#[derive(
+    Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, FromImpls, Serialize,
+)]
+pub enum AstTraitMember<'db> {
+    AssociatedTy(AstAssociatedTy<'db>),
+    Function(AstFunction<'db>),
+}
+
+impl<'db> Spanned<'db> for AstTraitMember<'db> {
+    fn span(&self, db: &'db dyn crate::Db) -> Span<'db> {
+        match self {
+            AstTraitMember::AssociatedTy(ty) => ty.span(db),
+            AstTraitMember::Function(function) => function.span(db),
+        }
+    }
+}
+
+/// `type Output` (in a trait) or `type Output = $ty` (in an impl)
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct AstAssociatedTy<'db> {
+    pub span: Span<'db>,
+
+    /// Name of the associated type
+    pub name: SpannedIdentifier<'db>,
+
+    /// Value of the associated type (if provided)
+    pub value: Option<AstTy<'db>>,
+}
+
+impl<'db> Spanned<'db> for AstAssociatedTy<'db> {
+    fn span(&self, db: &'db dyn crate::Db) -> Span<'db> {
+        AstAssociatedTy::span(*self, db)
+    }
+}
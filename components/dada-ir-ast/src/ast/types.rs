@@ -169,4 +169,7 @@ pub enum AstWhereClauseKind<'db> {
 
     /// `lent`
     Lent(Span<'db>),
+
+    /// A trait like `Place[P]`
+    Trait(AstPath<'db>),
 }
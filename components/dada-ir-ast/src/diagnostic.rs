@@ -8,8 +8,11 @@ use dada_util::debug;
 use salsa::{Accumulator, Update};
 use serde::Serialize;
 
+mod codes;
 mod render;
 
+pub use codes::DiagnosticCode;
+
 /// Signals that a diagnostic was reported at the given span.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
 pub struct Reported(pub AbsoluteSpan);
@@ -35,6 +38,10 @@ pub struct Diagnostic {
     /// Main location of the message.
     pub span: AbsoluteSpan,
 
+    /// Stable code identifying the kind of diagnostic, if any.
+    /// Set with the `code` helper method.
+    pub code: Option<DiagnosticCode>,
+
     /// Message to be printed.
     pub message: String,
 
@@ -42,6 +49,10 @@ pub struct Diagnostic {
     /// Add labels with the `label` helper method.
     pub labels: Vec<DiagnosticLabel>,
 
+    /// Machine-applicable fixes for the problem.
+    /// Add suggestions with the `suggest` helper method.
+    pub suggestions: Vec<DiagnosticSuggestion>,
+
     /// Child diagnostics.
     pub children: Vec<Diagnostic>,
 }
@@ -69,6 +80,20 @@ pub struct DiagnosticLabel {
     pub message: String,
 }
 
+/// A suggested fix: replace the text at `span` with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct DiagnosticSuggestion {
+    /// Short description of the fix, e.g., "lease `x` mutably".
+    pub message: String,
+
+    /// The span to be replaced; if empty, `replacement` is inserted.
+    /// Must have the same source file as the main diagnostic!
+    pub span: AbsoluteSpan,
+
+    /// Text to put in place of `span`.
+    pub replacement: String,
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct RenderOptions {
     pub no_color: bool,
@@ -97,9 +122,11 @@ impl Diagnostic {
         Diagnostic {
             span: span.absolute_span(db),
             level,
+            code: None,
             children: vec![],
             message,
             labels: vec![],
+            suggestions: vec![],
         }
     }
 
@@ -140,11 +167,42 @@ impl Diagnostic {
         self
     }
 
+    pub fn code(mut self, code: DiagnosticCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn suggest(
+        mut self,
+        db: &dyn crate::Db,
+        span: Span,
+        replacement: impl Display,
+        message: impl Display,
+    ) -> Self {
+        let span = span.absolute_span(db);
+        assert_eq!(self.span.source_file, span.source_file);
+        self.suggestions.push(DiagnosticSuggestion {
+            message: message.to_string(),
+            span,
+            replacement: replacement.to_string(),
+        });
+        self
+    }
+
     pub fn child(mut self, child: Diagnostic) -> Self {
         self.children.push(child);
         self
     }
 
+    /// Suggestions from this diagnostic and all of its children.
+    pub fn all_suggestions(&self) -> Vec<&DiagnosticSuggestion> {
+        let mut suggestions: Vec<_> = self.suggestions.iter().collect();
+        for child in &self.children {
+            suggestions.extend(child.all_suggestions());
+        }
+        suggestions
+    }
+
     pub fn render(&self, db: &dyn crate::Db, opts: &RenderOptions) -> String {
         render::render(db, opts, self)
     }
@@ -0,0 +1,173 @@
+use serde::Serialize;
+
+/// Declares the [`DiagnosticCode`] enum.
+/// Each code has a long-form explanation in `explanations/<code>.md`
+/// which is embedded in the binary and printed by `dada explain`.
+macro_rules! diagnostic_codes {
+    ($($(#[$attr:meta])* $name:ident = $code:literal,)*) => {
+        /// Stable identifier for a kind of diagnostic, rendered like `D0001`.
+        ///
+        /// Codes are never reused: if a diagnostic is removed, its code is retired.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum DiagnosticCode {
+            $($(#[$attr])* $name,)*
+        }
+
+        impl DiagnosticCode {
+            /// All diagnostic codes, in order.
+            pub const ALL: &[DiagnosticCode] = &[$(DiagnosticCode::$name,)*];
+
+            /// The code as it is shown to users, e.g., `D0001`.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(DiagnosticCode::$name => $code,)*
+                }
+            }
+
+            /// Long-form explanation of the diagnostic, in Markdown.
+            pub fn explanation(self) -> &'static str {
+                match self {
+                    $(DiagnosticCode::$name => include_str!(concat!("explanations/", $code, ".md")),)*
+                }
+            }
+        }
+    };
+}
+
+diagnostic_codes! {
+    /// Characters that do not begin any token.
+    UnrecognizedCharacters = "D0001",
+
+    /// A `\` in a string literal that is not a valid escape.
+    InvalidEscape = "D0002",
+
+    /// A string literal without its closing quote(s).
+    UnterminatedString = "D0003",
+
+    /// An opening delimiter without its matching closing delimiter.
+    MissingClosingDelimiter = "D0004",
+
+    /// The parser expected some other syntax.
+    ExpectedSyntax = "D0005",
+
+    /// Input left over after parsing.
+    ExtraInput = "D0006",
+
+    /// Something at module level that is neither a statement nor an item.
+    ExpectedModuleItem = "D0007",
+
+    /// An effect keyword like `async` given twice.
+    DuplicateEffect = "D0008",
+
+    /// A source file that could not be read.
+    UnreadableSourceFile = "D0009",
+
+    /// The `prelude` name does not refer to a module.
+    PreludeNotModule = "D0010",
+
+    /// A name that is not in scope.
+    NameNotFound = "D0011",
+
+    /// Generic arguments supplied to something that does not take them.
+    ExtraGenericArguments = "D0012",
+
+    /// A name that is not found in the given module.
+    NameNotFoundInModule = "D0013",
+
+    /// A `.` in a path applied to something that has no members.
+    UnexpectedDotInPath = "D0014",
+
+    /// A `use` of a crate that does not exist.
+    CrateNotFound = "D0015",
+
+    /// A value whose type is not a subtype of the expected type.
+    SubtypeExpected = "D0016",
+
+    /// A where-clause on a called function that is not satisfied.
+    WhereClauseNotSatisfied = "D0017",
+
+    /// A `let` whose initializer does not match the declared type.
+    InitializerWrongType = "D0018",
+
+    /// An assignment whose right-hand side does not match the place's type.
+    AssignmentWrongType = "D0019",
+
+    /// A returned value that does not match the function's return type.
+    InvalidReturnValue = "D0020",
+
+    /// `.await` applied to something that is not a future.
+    AwaitNonFuture = "D0021",
+
+    /// A condition that is not a boolean.
+    BooleanExpected = "D0022",
+
+    /// An arithmetic operand that is not numeric.
+    NumericTypeExpected = "D0023",
+
+    /// A binary operator whose operands have different types.
+    SameTypesExpected = "D0024",
+
+    /// An inference variable with no constraints.
+    UnconstrainedInference = "D0025",
+
+    /// An inference variable whose bounds refer to one another.
+    CyclicInferenceBounds = "D0026",
+
+    /// A numeric or character literal that is malformed.
+    InvalidLiteral = "D0027",
+
+    /// A numeric literal whose value does not fit its type.
+    LiteralOutOfRange = "D0028",
+
+    /// An operand of `%`, a bitwise operator, or a shift that is not an integer.
+    IntegerTypeExpected = "D0029",
+
+    /// A negated value that is not a signed integer or float.
+    SignedTypeExpected = "D0030",
+
+    /// An index that is not a `u32`.
+    IndexTypeExpected = "D0031",
+
+    /// Two items in the same module with the same name.
+    DuplicateDefinition = "D0032",
+
+    /// A call to an `unsafe` function from code that is not `unsafe`.
+    UnsafeCallOutsideUnsafe = "D0033",
+
+    /// A field in a constructor expression that the class or struct does not declare.
+    UnrecognizedField = "D0034",
+
+    /// A field given a value more than once in a constructor expression.
+    DuplicateField = "D0035",
+
+    /// A constructor expression that does not give a value for every field.
+    MissingFields = "D0036",
+
+    /// An indexed value that is not a `Vec`.
+    IndexableTypeExpected = "D0037",
+}
+
+impl std::str::FromStr for DiagnosticCode {
+    type Err = ();
+
+    /// Parse a code like `D0001`; case-insensitive.
+    fn from_str(s: &str) -> Result<Self, ()> {
+        DiagnosticCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for DiagnosticCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{diagnostic::Diagnostic, span::AbsoluteSpan};
+use crate::{
+    diagnostic::{Diagnostic, DiagnosticSuggestion},
+    span::AbsoluteSpan,
+};
 use annotate_snippets::{Message, Renderer, Snippet};
 use dada_util::arena::Arena;
 
@@ -33,12 +36,50 @@ fn to_message<'a>(
     diagnostic: &'a Diagnostic,
     arena: &'a Arena,
 ) -> Message<'a> {
-    to_level(diagnostic.level)
-        .title(&diagnostic.message)
+    let message = to_level(diagnostic.level).title(&diagnostic.message);
+    let message = match diagnostic.code {
+        Some(code) => message.id(code.as_str()),
+        None => message,
+    };
+    message
         .snippet(to_snippet(db, diagnostic, arena))
+        .footers(
+            diagnostic
+                .suggestions
+                .iter()
+                .map(|s| suggestion_message(db, s, arena)),
+        )
         .footers(diagnostic.children.iter().map(|d| to_message(db, d, arena)))
 }
 
+fn suggestion_message<'a>(
+    db: &'a dyn crate::Db,
+    suggestion: &'a DiagnosticSuggestion,
+    arena: &'a Arena,
+) -> Message<'a> {
+    let source_file = suggestion.span.source_file;
+    let label: &str = if suggestion.span.start == suggestion.span.end {
+        arena.insert(format!("insert `{}`", suggestion.replacement))
+    } else {
+        arena.insert(format!("replace with `{}`", suggestion.replacement))
+    };
+    let origin = arena.insert(db.url_display(source_file.url(db)));
+
+    annotate_snippets::Level::Help
+        .title(&suggestion.message)
+        .snippet(
+            Snippet::source(source_file.contents_if_ok(db))
+                .line_start(1)
+                .origin(origin)
+                .fold(true)
+                .annotation(
+                    annotate_snippets::Level::Help
+                        .span(to_span(suggestion.span))
+                        .label(label),
+                ),
+        )
+}
+
 fn to_snippet<'a>(
     db: &'a dyn crate::Db,
     diagnostic: &'a Diagnostic,
@@ -15,6 +15,7 @@ pub(crate) mod fields;
 pub(crate) mod functions;
 mod generics;
 pub(crate) mod inference;
+mod literals;
 mod live_places;
 mod member_lookup;
 mod modules;
@@ -33,6 +34,7 @@ mod subst_impls;
 pub(crate) mod subtype;
 mod temporaries;
 mod to_red;
+pub(crate) mod traits;
 mod types;
 mod universe;
 
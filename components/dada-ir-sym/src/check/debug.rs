@@ -549,7 +549,10 @@ pub enum TaskDescription<'db> {
     RequireEqualTypes(SymTy<'db>, SymTy<'db>),
     RequireMyNumericType(SymTy<'db>),
     RequireNumericType(SymTy<'db>),
+    RequireIntegerType(SymTy<'db>),
+    RequireSignedNumericType(SymTy<'db>),
     RequireFutureType(SymTy<'db>),
+    RequireLiteralFits(SymTy<'db>),
     RequireBoundsProvablyPredicate(InferVarIndex, Predicate),
     RequireBoundsNotProvablyPredicate(InferVarIndex, Predicate),
     RequireWhereClause(SymWhereClause<'db>),
@@ -8,13 +8,15 @@ use crate::{
     },
     ir::{
         binder::BoundTerm,
-        generics::SymWhereClause,
+        functions::SymFunction,
+        generics::{SymWhereClause, SymWhereClauseKind},
         indices::{FromInfer, InferVarIndex},
         populate::variable_decl_requires_default_perm,
         subst::SubstWith,
+        traits::SymTraitRef,
         types::{
             AnonymousPermSymbol, Assumption, AssumptionKind, SymGenericKind, SymGenericTerm,
-            SymPerm, SymTy, SymTyName, Variance,
+            SymPerm, SymTy, SymTyKind, SymTyName, Variance,
         },
         variables::SymVariable,
     },
@@ -26,12 +28,17 @@ use dada_ir_ast::{
 };
 use dada_util::{Map, debug};
 
-use crate::{check::runtime::Runtime, check::universe::Universe, ir::exprs::SymExpr};
+use crate::{
+    check::runtime::Runtime,
+    check::universe::Universe,
+    ir::exprs::{SymExpr, SymLiteral},
+};
 
 use super::{
     CheckTyInEnv,
     debug::LogHandle,
     inference::{Direction, InferVarKind, InferenceVarData},
+    literals::require_literal_fits,
     live_places::LivePlaces,
     predicates::{Predicate, require_where_clause::require_where_clause},
     red::{RedPerm, RedTy},
@@ -39,7 +46,10 @@ use super::{
     runtime::DeferResult,
     subtype::{
         is_future::require_future_type,
-        is_numeric::{require_my_numeric_type, require_numeric_type},
+        is_numeric::{
+            require_integer_type, require_my_numeric_type, require_numeric_type,
+            require_signed_numeric_type,
+        },
         relate_infer_bounds::relate_infer_bounds,
         terms::reconcile_ty_bounds,
     },
@@ -71,8 +81,23 @@ pub(crate) struct Env<'db> {
     /// If `None`, not type checking a function or method.
     pub return_ty: Option<SymTy<'db>>,
 
+    /// The function or method being type checked, if any.
+    pub function: Option<SymFunction<'db>>,
+
+    /// Type of the innermost enclosing loop, which `break` must produce.
+    /// If `None`, we are not inside a loop.
+    pub loop_ty: Option<SymTy<'db>>,
+
+    /// True if we are inside an `unsafe` function or an `unsafe { ... }` block,
+    /// so that calls to unsafe functions are permitted.
+    pub unsafe_context: bool,
+
     /// Assumptions declared
     assumptions: Arc<Vec<Assumption<'db>>>,
+
+    /// Trait bounds declared on type variables via where-clauses,
+    /// e.g., `where T is Fn[my]`.
+    trait_bounds: Arc<Vec<(SymVariable<'db>, SymTraitRef<'db>)>>,
 }
 
 impl<'db> Env<'db> {
@@ -86,7 +111,11 @@ impl<'db> Env<'db> {
             variable_tys: Default::default(),
             variable_universes: Default::default(),
             return_ty: Default::default(),
+            function: Default::default(),
+            loop_ty: Default::default(),
+            unsafe_context: false,
             assumptions: Arc::new(vec![]), // FIXME
+            trait_bounds: Default::default(),
         }
     }
 
@@ -126,7 +155,11 @@ impl<'db> Env<'db> {
             variable_universes: self.variable_universes.clone(),
             variable_tys: self.variable_tys.clone(),
             return_ty: self.return_ty,
+            function: self.function,
+            loop_ty: self.loop_ty,
+            unsafe_context: self.unsafe_context,
             assumptions: self.assumptions.clone(),
+            trait_bounds: self.trait_bounds.clone(),
         }
     }
 
@@ -170,6 +203,32 @@ impl<'db> Env<'db> {
             .any(|a| a.var(self.db()) == var && kind(a.kind(self.db())))
     }
 
+    /// Assume the trait bounds found in `where_clauses`, so that they can be used
+    /// when checking the body (e.g., to call trait methods on a generic type).
+    /// Other where-clauses are not yet assumed (see `assumptions`).
+    pub fn assume_where_clauses(&mut self, where_clauses: &[SymWhereClause<'db>]) {
+        let db = self.db();
+        for &where_clause in where_clauses {
+            if let SymWhereClauseKind::Trait(trait_ref) = where_clause.kind(db)
+                && let SymGenericTerm::Type(ty) = where_clause.subject(db)
+                && let SymTyKind::Var(var) = *ty.kind(db)
+            {
+                Arc::make_mut(&mut self.trait_bounds).push((var, trait_ref));
+            }
+        }
+    }
+
+    /// Trait bounds declared on the variable `var`.
+    pub fn trait_bounds(
+        &self,
+        var: SymVariable<'db>,
+    ) -> impl Iterator<Item = SymTraitRef<'db>> + '_ {
+        self.trait_bounds
+            .iter()
+            .filter(move |&&(v, _)| v == var)
+            .map(|&(_, trait_ref)| trait_ref)
+    }
+
     /// Open the given symbols as universally quantified.
     /// Creates a new universe.
     #[allow(dead_code)]
@@ -426,6 +485,48 @@ impl<'db> Env<'db> {
         )
     }
 
+    /// Check that the value is an integer type with any permission.
+    #[track_caller]
+    pub(super) fn spawn_require_integer_type(&mut self, ty: SymTy<'db>, or_else: &dyn OrElse<'db>) {
+        let or_else = or_else.to_arc();
+        self.runtime.spawn(
+            self,
+            TaskDescription::RequireIntegerType(ty),
+            async move |env| require_integer_type(env, ty, &or_else).await,
+        )
+    }
+
+    /// Check that the value is a signed integer or floating-point type with any permission.
+    #[track_caller]
+    pub(super) fn spawn_require_signed_numeric_type(
+        &mut self,
+        ty: SymTy<'db>,
+        or_else: &dyn OrElse<'db>,
+    ) {
+        let or_else = or_else.to_arc();
+        self.runtime.spawn(
+            self,
+            TaskDescription::RequireSignedNumericType(ty),
+            async move |env| require_signed_numeric_type(env, ty, &or_else).await,
+        )
+    }
+
+    /// Check that the value of the literal `expr` fits in its (possibly inferred) type.
+    /// If `negated` is true, the literal is the operand of a unary `-`.
+    #[track_caller]
+    pub(super) fn spawn_require_literal_fits(
+        &mut self,
+        expr: SymExpr<'db>,
+        literal: SymLiteral,
+        negated: bool,
+    ) {
+        self.runtime.spawn(
+            self,
+            TaskDescription::RequireLiteralFits(expr.ty(self.db())),
+            async move |env| require_literal_fits(env, expr, literal, negated).await,
+        )
+    }
+
     #[track_caller]
     pub(super) fn spawn_require_future_type(
         &self,
@@ -474,7 +575,7 @@ impl<'db> Env<'db> {
     }
 
     pub fn describe_ty<'a>(&'a self, ty: SymTy<'db>) -> impl std::fmt::Display + 'a {
-        format!("{ty:?}") // FIXME
+        ty
     }
 
     #[track_caller]
@@ -3,19 +3,20 @@ use std::panic::Location;
 use crate::{
     check::{
         env::Env,
-        member_lookup::MemberLookup,
-        scope::{NameResolution, NameResolutionSym},
+        member_lookup::{MemberLookup, non_infer_lower_bound},
+        red::RedTy,
+        scope::{NameResolution, NameResolutionSym, ResolveToSym},
         scope_tree::ScopeTreeNode,
     },
     ir::{
         binder::Binder,
-        classes::SymAggregate,
+        classes::{SymAggregate, SymClassMember, SymVariant},
         exprs::{
             SymBinaryOp, SymByteLiteral, SymByteLiteralData, SymExpr, SymExprKind, SymLiteral,
             SymMatchArm, SymPlaceExpr, SymPlaceExprKind,
         },
-        functions::{SymFunction, SymInputOutput},
-        types::{SymGenericKind, SymGenericTerm, SymTy, SymTyKind, SymTyName},
+        functions::{SymFunction, SymFunctionSource, SymInputOutput},
+        types::{SymGenericKind, SymGenericTerm, SymPerm, SymPlace, SymTy, SymTyKind, SymTyName},
         variables::{FromVar, SymVariable},
     },
     prelude::CheckedSignature,
@@ -23,11 +24,12 @@ use crate::{
 };
 use dada_ir_ast::{
     ast::{
-        AstBinaryOp, AstExpr, AstExprKind, AstGenericTerm, Identifier, LiteralKind, PermissionOp,
+        AstBinaryOp, AstConstructorField, AstExpr, AstExprKind, AstGenericTerm, AstMatchArm,
+        AstPath, AstPattern, AstPatternKind, AstStringPart, Identifier, LiteralKind, PermissionOp,
         SpanVec, SpannedBinaryOp, SpannedIdentifier, UnaryOp,
     },
-    diagnostic::{Diagnostic, Err, Level, Reported},
-    span::{Span, Spanned},
+    diagnostic::{Diagnostic, DiagnosticCode, Err, Errors, Level, Reported},
+    span::{SourceSpanned, Span, Spanned},
 };
 use dada_parser::prelude::*;
 use dada_util::{FromImpls, boxed_async_fn};
@@ -36,11 +38,13 @@ use serde::Serialize;
 use super::{
     CheckExprInEnv, CheckTyInEnv,
     debug::TaskDescription,
+    literals::{check_char_literal, check_numeric_literal},
     live_places::LivePlaces,
+    places::element_ty,
     report::{
-        AwaitNonFuture, BadSubtermError, InvalidAssignmentType, InvalidReturnValue,
-        NumericTypeExpected, OperatorArgumentsMustHaveSameType, OperatorRequiresNumericType,
-        WhereClauseError,
+        AwaitNonFuture, BadSubtermError, IndexTypeRequired, InvalidAssignmentType,
+        InvalidReturnValue, NegationRequiresSignedType, OperatorArgumentsMustHaveSameType,
+        OperatorRequiresIntegerType, OperatorRequiresNumericType, WhereClauseError,
     },
     temporaries::Temporary,
 };
@@ -99,88 +103,13 @@ async fn check_expr<'db>(
 
         match &*expr.kind {
             AstExprKind::Literal(literal) => match literal.kind(db) {
-                LiteralKind::Integer => {
-                    let ty = env.fresh_ty_inference_var(expr_span);
-                    let bits = match str::parse(literal.text(db)) {
-                        Ok(v) => v,
-                        Err(e) => panic!("error: {e:?}"),
-                    };
-                    let sym_expr = SymExpr::new(
-                        db,
-                        expr_span,
-                        ty,
-                        SymExprKind::Primitive(SymLiteral::Integral { bits }),
-                    );
-                    env.spawn_require_my_numeric_type(
-                        LivePlaces::fixme(),
-                        ty,
-                        &NumericTypeExpected::new(sym_expr, ty),
-                    );
-                    ExprResult {
-                        temporaries: vec![],
-                        span: expr_span,
-                        kind: sym_expr.into(),
-                    }
+                LiteralKind::Integer | LiteralKind::Float => {
+                    check_numeric_literal(env, live_after, expr_span, literal.text(db), false)
                 }
 
-                LiteralKind::String => {
-                    // Generate `String.literal(b"...", length)`
-
-                    // Generate `b"..."`
-                    let bytes = literal.text(db).as_bytes();
-                    let byte_literal_expr = {
-                        let pointer_struct = match well_known::pointer_struct(db) {
-                            Ok(v) => v,
-                            Err(reported) => return ExprResult::err(db, reported),
-                        };
-                        let data = SymByteLiteralData::new(db, bytes);
-                        let byte_literal = SymByteLiteral::new(db, expr_span, data);
-                        SymExpr::new(
-                            db,
-                            expr_span,
-                            SymTy::named(db, pointer_struct.into(), vec![SymTy::u8(db).into()]),
-                            SymExprKind::ByteLiteral(byte_literal),
-                        )
-                    };
-
-                    // Generate `length`
-                    let len_literal_expr = {
-                        let value = bytes.len() as u64;
-                        SymExpr::new(
-                            db,
-                            expr_span,
-                            SymTy::u32(db),
-                            SymExprKind::Primitive(SymLiteral::Integral { bits: value }),
-                        )
-                    };
+                LiteralKind::Char => check_char_literal(env, expr_span, literal.text(db)),
 
-                    // Generate and return `String.literal(b"...", length)`
-                    let mut temporaries = vec![];
-                    let ctor_call_expr = {
-                        let literal_fn = match well_known::string_literal_fn(db) {
-                            Ok(v) => v,
-                            Err(reported) => return ExprResult::err(db, reported),
-                        };
-                        SymExpr::new(
-                            db,
-                            expr_span,
-                            SymTy::string(db),
-                            SymExprKind::Call {
-                                function: literal_fn,
-                                substitution: vec![],
-                                arg_temps: vec![
-                                    byte_literal_expr.into_temporary_var(db, &mut temporaries),
-                                    len_literal_expr.into_temporary_var(db, &mut temporaries),
-                                ],
-                            },
-                        )
-                    };
-                    ExprResult {
-                        temporaries,
-                        span: expr_span,
-                        kind: ctor_call_expr.into(),
-                    }
-                }
+                LiteralKind::String => check_string_literal(env, expr_span, literal.text(db)),
 
                 LiteralKind::Boolean => {
                     let bits = match &literal.text(db)[..] {
@@ -202,16 +131,25 @@ async fn check_expr<'db>(
                 }
             },
 
+            AstExprKind::InterpolatedString(parts) => {
+                check_interpolated_string(env, live_after, expr_span, parts).await
+            }
+
             AstExprKind::Tuple(span_vec) => {
                 let mut temporaries = vec![];
                 let mut exprs = vec![];
-                for element in &span_vec.values {
-                    exprs.push(
-                        element
-                            .check_in_env(env, LivePlaces::fixme())
-                            .await
-                            .into_expr(env, &mut temporaries),
-                    );
+                let live_after_elements =
+                    live_after.before_pending_sequence(env, span_vec.values.len());
+                for (i, element) in span_vec.values.iter().enumerate() {
+                    let element_result = element.check_in_env(env, live_after_elements[i]).await;
+                    exprs.push(match i.checked_sub(1) {
+                        Some(previous) => element_result.into_expr_resolving(
+                            env,
+                            &mut temporaries,
+                            live_after_elements[previous],
+                        ),
+                        None => element_result.into_expr(env, &mut temporaries),
+                    });
                 }
 
                 let ty = SymTy::new(
@@ -237,28 +175,54 @@ async fn check_expr<'db>(
             AstExprKind::BinaryOp(span_op, lhs, rhs) => {
                 let span_op: SpannedBinaryOp<'db> = *span_op;
                 match span_op.op {
-                    AstBinaryOp::Add | AstBinaryOp::Sub | AstBinaryOp::Mul | AstBinaryOp::Div => {
+                    AstBinaryOp::Add
+                    | AstBinaryOp::Sub
+                    | AstBinaryOp::Mul
+                    | AstBinaryOp::Div
+                    | AstBinaryOp::Rem
+                    | AstBinaryOp::BitAnd
+                    | AstBinaryOp::BitOr
+                    | AstBinaryOp::BitXor
+                    | AstBinaryOp::Shl
+                    | AstBinaryOp::Shr => {
                         let mut temporaries: Vec<Temporary<'db>> = vec![];
+                        let live_after_lhs = live_after.before_pending(env);
                         let lhs: SymExpr<'db> = lhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after_lhs)
                             .await
                             .into_expr(env, &mut temporaries);
                         let rhs: SymExpr<'db> = rhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after)
                             .await
-                            .into_expr(env, &mut temporaries);
+                            .into_expr_resolving(env, &mut temporaries, live_after_lhs);
 
                         // For now, let's do a dumb rule that operands must be
                         // of the same primitive (and scalar) type.
+                        // `%` and the bitwise operators only apply to integers.
 
-                        env.spawn_require_numeric_type(
-                            lhs.ty(db),
-                            &OperatorRequiresNumericType::new(span_op, lhs),
-                        );
-                        env.spawn_require_numeric_type(
-                            rhs.ty(db),
-                            &OperatorRequiresNumericType::new(span_op, rhs),
-                        );
+                        if let AstBinaryOp::Add
+                        | AstBinaryOp::Sub
+                        | AstBinaryOp::Mul
+                        | AstBinaryOp::Div = span_op.op
+                        {
+                            env.spawn_require_numeric_type(
+                                lhs.ty(db),
+                                &OperatorRequiresNumericType::new(span_op, lhs),
+                            );
+                            env.spawn_require_numeric_type(
+                                rhs.ty(db),
+                                &OperatorRequiresNumericType::new(span_op, rhs),
+                            );
+                        } else {
+                            env.spawn_require_integer_type(
+                                lhs.ty(db),
+                                &OperatorRequiresIntegerType::new(span_op, lhs),
+                            );
+                            env.spawn_require_integer_type(
+                                rhs.ty(db),
+                                &OperatorRequiresIntegerType::new(span_op, rhs),
+                            );
+                        }
                         env.spawn_if_not_never(&[lhs.ty(db), rhs.ty(db)], async move |env| {
                             env.spawn_require_equal_types(
                                 live_after,
@@ -291,15 +255,19 @@ async fn check_expr<'db>(
 
                     AstBinaryOp::AndAnd => {
                         let mut temporaries: Vec<Temporary<'db>> = vec![];
+                        let live_after_lhs = live_after.before_pending(env);
                         let lhs: SymExpr<'db> = lhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after_lhs)
                             .await
                             .into_expr(env, &mut temporaries);
+                        // The RHS is only evaluated if the LHS is true,
+                        // so its temporaries must not be hoisted out of the `if`.
                         let rhs: SymExpr<'db> = rhs
                             .check_in_env(env, live_after)
                             .await
-                            .into_expr(env, &mut temporaries);
-                        env.require_expr_has_bool_ty(LivePlaces::fixme(), lhs);
+                            .into_expr_with_enclosed_temporaries(env);
+                        live_after_lhs.resolve_pending(env, [rhs]);
+                        env.require_expr_has_bool_ty(live_after_lhs, lhs);
                         env.require_expr_has_bool_ty(live_after, rhs);
 
                         // construct an expression like
@@ -325,16 +293,20 @@ async fn check_expr<'db>(
 
                     AstBinaryOp::OrOr => {
                         let mut temporaries: Vec<Temporary<'db>> = vec![];
+                        let live_after_lhs = live_after.before_pending(env);
                         let lhs: SymExpr<'db> = lhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after_lhs)
                             .await
                             .into_expr(env, &mut temporaries);
+                        // The RHS is only evaluated if the LHS is false,
+                        // so its temporaries must not be hoisted out of the `if`.
                         let rhs: SymExpr<'db> = rhs
                             .check_in_env(env, live_after)
                             .await
-                            .into_expr(env, &mut temporaries);
+                            .into_expr_with_enclosed_temporaries(env);
+                        live_after_lhs.resolve_pending(env, [rhs]);
 
-                        env.require_expr_has_bool_ty(LivePlaces::fixme(), lhs);
+                        env.require_expr_has_bool_ty(live_after_lhs, lhs);
                         env.require_expr_has_bool_ty(live_after, rhs);
 
                         // construct an expression like
@@ -362,16 +334,18 @@ async fn check_expr<'db>(
                     | AstBinaryOp::LessThan
                     | AstBinaryOp::GreaterEqual
                     | AstBinaryOp::LessEqual
-                    | AstBinaryOp::EqualEqual => {
+                    | AstBinaryOp::EqualEqual
+                    | AstBinaryOp::NotEqual => {
                         let mut temporaries: Vec<Temporary<'db>> = vec![];
+                        let live_after_lhs = live_after.before_pending(env);
                         let lhs: SymExpr<'db> = lhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after_lhs)
                             .await
                             .into_expr(env, &mut temporaries);
                         let rhs: SymExpr<'db> = rhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after)
                             .await
-                            .into_expr(env, &mut temporaries);
+                            .into_expr_resolving(env, &mut temporaries, live_after_lhs);
 
                         // For now, let's do a dumb rule that operands must be
                         // of the same primitive (and scalar) type.
@@ -386,7 +360,7 @@ async fn check_expr<'db>(
                         );
                         env.spawn_if_not_never(&[lhs.ty(db), rhs.ty(db)], async move |env| {
                             env.spawn_require_equal_types(
-                                LivePlaces::fixme(),
+                                live_after,
                                 lhs.ty(db),
                                 rhs.ty(db),
                                 &OperatorArgumentsMustHaveSameType::new(span_op, lhs, rhs),
@@ -416,20 +390,21 @@ async fn check_expr<'db>(
 
                     AstBinaryOp::Assign => {
                         let mut temporaries: Vec<Temporary<'db>> = vec![];
+                        let live_after_place = live_after.before_pending(env);
                         let place: SymPlaceExpr<'db> = lhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after_place)
                             .await
                             .into_place_expr(env, &mut temporaries);
                         let value: SymExpr<'db> = rhs
-                            .check_in_env(env, LivePlaces::fixme())
+                            .check_in_env(env, live_after)
                             .await
-                            .into_expr(env, &mut temporaries);
+                            .into_expr_resolving(env, &mut temporaries, live_after_place);
 
                         // For now, let's do a dumb rule that operands must be
                         // of the same primitive (and scalar) type.
 
                         env.spawn_require_assignable_type(
-                            LivePlaces::fixme(),
+                            live_after,
                             value.ty(db),
                             place.ty(db),
                             &InvalidAssignmentType::new(place, value),
@@ -495,7 +470,16 @@ async fn check_expr<'db>(
             }
 
             AstExprKind::SquareBracketOp(owner, square_bracket_args) => {
-                let owner_result = owner.check_in_env(env, LivePlaces::fixme()).await;
+                // If this turns out to be an index expression, the index is evaluated after the owner.
+                let live_after_owner = live_after.before_pending(env);
+                let owner_result = owner.check_in_env(env, live_after_owner).await;
+                if !matches!(
+                    owner_result.kind,
+                    ExprResultKind::PlaceExpr(_) | ExprResultKind::Expr(_)
+                ) {
+                    // The contents of `[..]` are generic arguments; mentioning a place in a type is not a use.
+                    live_after_owner.resolve_pending(env, []);
+                }
                 match owner_result.kind {
                     ExprResultKind::Method {
                         self_expr: owner,
@@ -516,10 +500,17 @@ async fn check_expr<'db>(
                         }
                     }
 
-                    ExprResultKind::PlaceExpr(_) | ExprResultKind::Expr(_) => ExprResult::err(
-                        db,
-                        report_not_implemented(db, expr_span, "indexing expressions"),
-                    ),
+                    ExprResultKind::PlaceExpr(_) | ExprResultKind::Expr(_) => {
+                        check_index_expr(
+                            env,
+                            live_after,
+                            live_after_owner,
+                            expr_span,
+                            owner_result,
+                            *square_bracket_args,
+                        )
+                        .await
+                    }
 
                     // We see something like `foo.bar[][]` where `bar` is a method.
                     // The only correct thing here would be `foo.bar[]()[]`, i.e., call the method and then index.
@@ -638,13 +629,15 @@ async fn check_expr<'db>(
                 }
             }
 
-            AstExprKind::Constructor(_ast_path, _span_vec) => todo!(),
+            AstExprKind::Constructor(ast_path, ast_fields) => {
+                check_constructor(env, live_after, expr_span, ast_path, ast_fields).await
+            }
             AstExprKind::Return(ast_expr) => {
                 let mut temporaries = vec![];
 
                 let return_expr = if let Some(ast_expr) = ast_expr {
                     ast_expr
-                        .check_in_env(env, LivePlaces::none(env))
+                        .check_in_env(env, LivePlaces::none())
                         .await
                         .into_expr(env, &mut temporaries)
                 } else {
@@ -672,7 +665,7 @@ async fn check_expr<'db>(
                 };
 
                 env.spawn_require_assignable_type(
-                    LivePlaces::none(env),
+                    LivePlaces::none(),
                     return_expr.ty(db),
                     expected_return_ty,
                     &InvalidReturnValue::new(return_expr, expected_return_ty),
@@ -691,145 +684,1287 @@ async fn check_expr<'db>(
                 }
             }
 
-            AstExprKind::Await {
-                future,
-                await_keyword,
-            } => {
-                let await_span = *await_keyword;
-
-                let mut temporaries = vec![];
+            AstExprKind::Await {
+                future,
+                await_keyword,
+            } => {
+                let await_span = *await_keyword;
+
+                let mut temporaries = vec![];
+
+                let future_expr = future
+                    .check_in_env(env, live_after)
+                    .await
+                    .into_expr(env, &mut temporaries);
+                let future_ty = future_expr.ty(db);
+
+                let awaited_ty = env.fresh_ty_inference_var(await_span);
+
+                env.spawn_require_future_type(
+                    live_after,
+                    future_ty,
+                    awaited_ty,
+                    &AwaitNonFuture::new(await_span, future_expr),
+                );
+
+                ExprResult {
+                    temporaries,
+                    span: expr_span,
+                    kind: SymExpr::new(
+                        db,
+                        expr_span,
+                        awaited_ty,
+                        SymExprKind::Await {
+                            future: future_expr,
+                            await_keyword: await_span,
+                        },
+                    )
+                    .into(),
+                }
+            }
+            AstExprKind::UnaryOp(spanned_unary_op, ast_expr) => match spanned_unary_op.op {
+                UnaryOp::Not => {
+                    let mut temporaries = vec![];
+                    let operand = ast_expr
+                        .check_in_env(env, live_after)
+                        .await
+                        .into_expr(env, &mut temporaries);
+                    env.require_expr_has_bool_ty(live_after, operand);
+
+                    ExprResult {
+                        temporaries,
+                        span: expr_span,
+                        kind: SymExpr::new(
+                            db,
+                            expr_span,
+                            SymTy::boolean(db),
+                            SymExprKind::Not {
+                                operand,
+                                op_span: spanned_unary_op.span,
+                            },
+                        )
+                        .into(),
+                    }
+                }
+                UnaryOp::Negate => {
+                    let mut temporaries = vec![];
+                    let operand = match &*ast_expr.kind {
+                        // Check `-22` as a whole so that e.g. `-128` fits in an `i8`.
+                        AstExprKind::Literal(literal)
+                            if matches!(
+                                literal.kind(db),
+                                LiteralKind::Integer | LiteralKind::Float
+                            ) =>
+                        {
+                            check_numeric_literal(
+                                env,
+                                live_after,
+                                ast_expr.span,
+                                literal.text(db),
+                                true,
+                            )
+                        }
+                        _ => ast_expr.check_in_env(env, live_after).await,
+                    }
+                    .into_expr(env, &mut temporaries);
+                    env.spawn_require_signed_numeric_type(
+                        operand.ty(db),
+                        &NegationRequiresSignedType::new(spanned_unary_op.span, operand),
+                    );
+
+                    ExprResult {
+                        temporaries,
+                        span: expr_span,
+                        kind: SymExpr::new(
+                            db,
+                            expr_span,
+                            operand.ty(db),
+                            SymExprKind::Negate {
+                                operand,
+                                op_span: spanned_unary_op.span,
+                            },
+                        )
+                        .into(),
+                    }
+                }
+            },
+
+            AstExprKind::Block(ast_block) => ExprResult {
+                temporaries: vec![],
+                span: expr_span,
+                kind: ast_block.check_in_env(env, live_after).await.into(),
+            },
+
+            AstExprKind::Unsafe(ast_block) => {
+                let outer_unsafe_context = std::mem::replace(&mut env.unsafe_context, true);
+                let block = ast_block.check_in_env(env, live_after).await;
+                env.unsafe_context = outer_unsafe_context;
+
+                ExprResult {
+                    temporaries: vec![],
+                    span: expr_span,
+                    kind: block.into(),
+                }
+            }
+
+            AstExprKind::If(ast_arms) => {
+                // Conservatively, everything used by any arm is live after each condition.
+                let live_after_condition = live_after.before_pending(env);
+                let mut arms = vec![];
+                let mut has_else = false;
+                for arm in ast_arms {
+                    let condition = if let Some(c) = &arm.condition {
+                        let expr = c
+                            .check_in_env(env, live_after_condition)
+                            .await
+                            .into_expr_with_enclosed_temporaries(env);
+                        env.require_expr_has_bool_ty(live_after_condition, expr);
+                        Some(expr)
+                    } else {
+                        has_else = true;
+                        None
+                    };
+
+                    let body = arm.result.check_in_env(env, live_after).await;
+
+                    arms.push(SymMatchArm { condition, body });
+                }
+
+                let if_ty = if !has_else {
+                    SymTy::unit(db)
+                } else {
+                    env.fresh_ty_inference_var(expr_span)
+                };
+
+                for arm in &arms {
+                    env.spawn_require_assignable_type(
+                        live_after,
+                        arm.body.ty(db),
+                        if_ty,
+                        &BadSubtermError::new(arm.body.span(db), arm.body.ty(db), if_ty),
+                    );
+                }
+
+                let if_expr = SymExpr::new(db, expr_span, if_ty, SymExprKind::Match { arms });
+                live_after_condition.resolve_pending(env, [if_expr]);
+
+                ExprResult {
+                    temporaries: vec![],
+                    span: expr_span,
+                    kind: if_expr.into(),
+                }
+            }
+
+            AstExprKind::Match { scrutinee, arms } => {
+                check_match_expr(env, live_after, expr_span, scrutinee, arms).await
+            }
+
+            AstExprKind::Loop(ast_body) => {
+                // The type of the loop is determined by the `break` expressions within;
+                // if there are none, inference falls back to `!`.
+                let loop_ty = env.fresh_ty_inference_var(expr_span);
+
+                let outer_loop_ty = env.loop_ty.replace(loop_ty);
+                // Control may return to the top of the loop, so everything used
+                // by the loop is live after each iteration of its body.
+                let live_in_body = live_after.before_pending(env);
+                let body = ast_body.check_in_env(env, live_in_body).await;
+                env.loop_ty = outer_loop_ty;
+
+                let loop_expr = SymExpr::new(db, expr_span, loop_ty, SymExprKind::Loop { body });
+                live_in_body.resolve_pending(env, [loop_expr]);
+
+                ExprResult {
+                    temporaries: vec![],
+                    span: expr_span,
+                    kind: loop_expr.into(),
+                }
+            }
+
+            AstExprKind::While {
+                condition,
+                body: ast_body,
+            } => {
+                // Desugar `while cond { body }` to `loop { if cond { body } else { break } }`.
+                let outer_loop_ty = env.loop_ty.replace(SymTy::unit(db));
+                // Control may return to the top of the loop, so everything used
+                // by the loop is live after each iteration of its body.
+                let live_in_body = live_after.before_pending(env);
+                let condition = condition
+                    .check_in_env(env, live_in_body)
+                    .await
+                    .into_expr_with_enclosed_temporaries(env);
+                env.require_expr_has_bool_ty(live_in_body, condition);
+                let body = ast_body.check_in_env(env, live_in_body).await;
+                env.loop_ty = outer_loop_ty;
+
+                let break_expr = SymExpr::new(db, expr_span, SymTy::never(db), SymExprKind::Break);
+                let if_expr = SymExpr::new(
+                    db,
+                    expr_span,
+                    body.ty(db),
+                    SymExprKind::Match {
+                        arms: vec![
+                            SymMatchArm {
+                                condition: Some(condition),
+                                body,
+                            },
+                            SymMatchArm {
+                                condition: None,
+                                body: break_expr,
+                            },
+                        ],
+                    },
+                );
+
+                let loop_expr = SymExpr::new(
+                    db,
+                    expr_span,
+                    SymTy::unit(db),
+                    SymExprKind::Loop { body: if_expr },
+                );
+                live_in_body.resolve_pending(env, [loop_expr]);
+
+                ExprResult {
+                    temporaries: vec![],
+                    span: expr_span,
+                    kind: loop_expr.into(),
+                }
+            }
+
+            AstExprKind::Break | AstExprKind::Continue => {
+                let (keyword, kind) = match &*expr.kind {
+                    AstExprKind::Break => ("break", SymExprKind::Break),
+                    _ => ("continue", SymExprKind::Continue),
+                };
+
+                let Some(loop_ty) = env.loop_ty else {
+                    return ExprResult::err(
+                        db,
+                        env.report(
+                            Diagnostic::error(
+                                db,
+                                expr_span,
+                                format!("`{keyword}` outside of a loop"),
+                            )
+                            .label(
+                                db,
+                                Level::Error,
+                                expr_span,
+                                format!(
+                                    "I expected `{keyword}` to appear inside of a `while` or `loop`"
+                                ),
+                            ),
+                        ),
+                    );
+                };
+
+                // Exiting the loop makes it produce `()`.
+                if let SymExprKind::Break = kind {
+                    env.spawn_require_assignable_type(
+                        live_after,
+                        SymTy::unit(db),
+                        loop_ty,
+                        &BadSubtermError::new(expr_span, SymTy::unit(db), loop_ty),
+                    );
+                }
+
+                ExprResult {
+                    temporaries: vec![],
+                    span: expr_span,
+                    kind: SymExpr::new(db, expr_span, SymTy::never(db), kind).into(),
+                }
+            }
+
+            AstExprKind::PermissionOp { value, op } => {
+                let mut temporaries = vec![];
+                let value_result = value.check_in_env(env, live_after).await;
+                let place_expr = value_result.into_place_expr(env, &mut temporaries);
+                let sym_place = place_expr.into_sym_place(db);
+                ExprResult {
+                    temporaries,
+                    span: expr_span,
+                    kind: SymExpr::new(
+                        db,
+                        expr_span,
+                        match op {
+                            PermissionOp::Mutate => place_expr.ty(db).mutable(db, sym_place),
+                            PermissionOp::Reference => place_expr.ty(db).referenced(db, sym_place),
+                            PermissionOp::Give => place_expr.ty(db),
+                            PermissionOp::Share => place_expr.ty(db).shared(db),
+                        },
+                        SymExprKind::PermissionOp(*op, place_expr),
+                    )
+                    .into(),
+                }
+            }
+        }
+    })
+    .await
+}
+
+/// Generate `String.literal(b"...", length)` for a string literal with contents `text`.
+fn check_string_literal<'db>(env: &mut Env<'db>, span: Span<'db>, text: &str) -> ExprResult<'db> {
+    let db = env.db();
+
+    // Generate `b"..."`
+    let bytes = text.as_bytes();
+    let byte_literal_expr = {
+        let pointer_struct = match well_known::pointer_struct(db) {
+            Ok(v) => v,
+            Err(reported) => return ExprResult::err(db, reported),
+        };
+        let data = SymByteLiteralData::new(db, bytes);
+        let byte_literal = SymByteLiteral::new(db, span, data);
+        SymExpr::new(
+            db,
+            span,
+            SymTy::named(db, pointer_struct.into(), vec![SymTy::u8(db).into()]),
+            SymExprKind::ByteLiteral(byte_literal),
+        )
+    };
+
+    // Generate `length`
+    let len_literal_expr = {
+        let value = bytes.len() as u64;
+        SymExpr::new(
+            db,
+            span,
+            SymTy::u32(db),
+            SymExprKind::Primitive(SymLiteral::Integral { bits: value }),
+        )
+    };
+
+    // Generate and return `String.literal(b"...", length)`
+    let mut temporaries = vec![];
+    let ctor_call_expr = {
+        let literal_fn = match well_known::string_literal_fn(db) {
+            Ok(v) => v,
+            Err(reported) => return ExprResult::err(db, reported),
+        };
+        SymExpr::new(
+            db,
+            span,
+            SymTy::string(db),
+            SymExprKind::Call {
+                function: literal_fn,
+                substitution: vec![],
+                arg_temps: vec![
+                    byte_literal_expr.into_temporary_var(db, &mut temporaries),
+                    len_literal_expr.into_temporary_var(db, &mut temporaries),
+                ],
+            },
+        )
+    };
+    ExprResult {
+        temporaries,
+        span,
+        kind: ctor_call_expr.into(),
+    }
+}
+
+/// Lower a string literal like `"a{b}c"` into a chain of concatenations:
+///
+/// ```text
+/// String.literal("a").concat(to_string(b)).concat(String.literal("c"))
+/// ```
+///
+/// The interpolated expressions are evaluated left-to-right and each is
+/// converted to a `String` as described in [`check_interpolated_expr`][].
+#[boxed_async_fn]
+async fn check_interpolated_string<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    expr_span: Span<'db>,
+    parts: &[AstStringPart<'db>],
+) -> ExprResult<'db> {
+    let db = env.db();
+
+    let concat_fn = match well_known::string_concat_fn(db) {
+        Ok(v) => v,
+        Err(reported) => return ExprResult::err(db, reported),
+    };
+
+    let mut temporaries = vec![];
+    let mut string_expr: Option<SymExpr<'db>> = None;
+    let live_after_parts = live_after.before_pending_sequence(env, parts.len());
+    for (i, part) in parts.iter().enumerate() {
+        let part_result = match part {
+            AstStringPart::Text(text) => check_string_literal(env, expr_span, text),
+            AstStringPart::Expr(ast_expr) => {
+                check_interpolated_expr(env, live_after_parts[i], ast_expr).await
+            }
+        };
+        let part_expr = match i.checked_sub(1) {
+            Some(previous) => {
+                part_result.into_expr_resolving(env, &mut temporaries, live_after_parts[previous])
+            }
+            None => part_result.into_expr(env, &mut temporaries),
+        };
+
+        string_expr = Some(match string_expr {
+            None => part_expr,
+
+            // Generate `prefix.concat(part)`. The parts may be borrowed strings
+            // (e.g., an interpolated `ref` or `mut` string), so the permissions
+            // are inferred like for any other call.
+            Some(prefix_expr) => check_synthesized_call(
+                env,
+                live_after_parts[i],
+                expr_span,
+                concat_fn,
+                vec![prefix_expr, part_expr],
+                vec![],
+            )
+            .await
+            .into_expr(env, &mut temporaries),
+        });
+    }
+
+    match string_expr {
+        Some(string_expr) => ExprResult::from_expr(db, string_expr, temporaries),
+        None => check_string_literal(env, expr_span, ""),
+    }
+}
+
+/// Check an expression interpolated into a string literal and convert it to a `String`:
+///
+/// * values of type `String` are used as is;
+/// * primitive values are converted with `primitive_to_string` from the prelude;
+/// * other values are converted by calling their `to_string` method.
+#[boxed_async_fn]
+async fn check_interpolated_expr<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    ast_expr: &AstExpr<'db>,
+) -> ExprResult<'db> {
+    let db = env.db();
+    let expr_span = ast_expr.span;
+
+    let expr_result = ast_expr.check_in_env(env, live_after).await;
+    let expr_ty = expr_result.ty(env);
+
+    // Block until we know enough about the type to decide how to convert it.
+    let (lower_bound, _) = non_infer_lower_bound(env, expr_ty).await;
+    match lower_bound {
+        RedTy::Named(SymTyName::Aggregate(aggr), _) if well_known::string_class(db) == Ok(aggr) => {
+            expr_result
+        }
+
+        RedTy::Named(SymTyName::Primitive(_), _) => {
+            let to_string_fn = match well_known::primitive_to_string_fn(db) {
+                Ok(v) => v,
+                Err(reported) => return ExprResult::err(db, reported),
+            };
+            let mut temporaries = vec![];
+            let value_expr = expr_result.into_expr(env, &mut temporaries);
+            check_synthesized_call(
+                env,
+                live_after,
+                expr_span,
+                to_string_fn,
+                vec![value_expr],
+                temporaries,
+            )
+            .await
+        }
+
+        RedTy::Named(SymTyName::Aggregate(aggr), _)
+            if matches!(
+                aggr.inherent_member_str(db, "to_string"),
+                Some(SymClassMember::SymFunction(_))
+            ) =>
+        {
+            let to_string_id = SpannedIdentifier {
+                span: expr_span,
+                id: Identifier::new(db, "to_string"),
+            };
+            let method_result = MemberLookup::new(env)
+                .lookup_member(expr_result, to_string_id)
+                .await;
+            let ExprResult {
+                temporaries,
+                span: _,
+                kind:
+                    ExprResultKind::Method {
+                        self_expr,
+                        id_span,
+                        function,
+                        generics,
+                    },
+            } = method_result
+            else {
+                return method_result;
+            };
+            let call_result = check_method_call(
+                env,
+                live_after,
+                id_span,
+                expr_span,
+                function,
+                Some(self_expr),
+                &[],
+                generics,
+                temporaries,
+            )
+            .await;
+
+            // Whatever `to_string` returns will be concatenated, so it had better be a string.
+            let mut temporaries = vec![];
+            let call_expr = call_result.into_expr(env, &mut temporaries);
+            env.spawn_require_assignable_type(
+                live_after,
+                call_expr.ty(db),
+                SymTy::string(db),
+                &BadSubtermError::new(expr_span, call_expr.ty(db), SymTy::string(db)),
+            );
+            ExprResult::from_expr(db, call_expr, temporaries)
+        }
+
+        RedTy::Error(reported) => ExprResult::err(db, reported),
+
+        // `!` can be used anywhere.
+        RedTy::Never => expr_result,
+
+        _ => ExprResult::err(
+            db,
+            env.report(
+                Diagnostic::error(
+                    db,
+                    expr_span,
+                    "cannot interpolate this value into a string".to_string(),
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    expr_span,
+                    format!(
+                        "this has type `{ty}`, which cannot be converted to a string",
+                        ty = env.describe_ty(expr_ty)
+                    ),
+                )
+                .label(
+                    db,
+                    Level::Help,
+                    expr_span,
+                    "interpolated values must be a `String`, a primitive, \
+                     or have a `to_string` method"
+                        .to_string(),
+                ),
+            ),
+        ),
+    }
+}
+
+/// Checks `owner[index]`, where `owner` has already been checked.
+///
+/// The owner is stored into a place and the index into a temporary,
+/// yielding the place `owner[index]`. Only `Vec[T]` values can be indexed;
+/// the element type `T` carries the permissions of the owner, just like a field would.
+/// `live_after_owner` is the pending live point after the owner, which is resolved with the index.
+#[boxed_async_fn]
+async fn check_index_expr<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    live_after_owner: LivePlaces,
+    expr_span: Span<'db>,
+    owner_result: ExprResult<'db>,
+    square_bracket_args: dada_ir_ast::ast::SquareBracketArgs<'db>,
+) -> ExprResult<'db> {
+    let db = env.db();
+
+    let mut temporaries = vec![];
+    let owner = owner_result.into_place_expr(env, &mut temporaries);
+
+    let ast_index = match square_bracket_args.parse_as_index(db) {
+        Ok(ast_index) => ast_index,
+        Err(reported) => return ExprResult::err(db, reported),
+    };
+
+    // The owner is used once the index has been evaluated, so it is live in the meantime.
+    let live_after_index = live_after.before_pending(env);
+    let index = ast_index
+        .check_in_env(env, live_after_index)
+        .await
+        .into_expr_resolving(env, &mut temporaries, live_after_owner);
+    live_after_index.resolve_pending_places(env, [owner]);
+    env.spawn_require_assignable_type(
+        live_after_index,
+        index.ty(db),
+        SymTy::u32(db),
+        &IndexTypeRequired::new(index),
+    );
+    let index_var = index.into_temporary_var(db, &mut temporaries);
+
+    // Block until we know what kind of value is being indexed.
+    let (owner_red_ty, owner_perm) = non_infer_lower_bound(env, owner.ty(db)).await;
+    let element_ty = match owner_red_ty {
+        RedTy::Named(name, ref generics) => element_ty(db, name, generics),
+        RedTy::Error(reported) => return ExprResult::err(db, reported),
+        _ => None,
+    };
+    let Some(element_ty) = element_ty else {
+        return ExprResult::err(
+            db,
+            env.report(
+                Diagnostic::error(db, expr_span, "cannot index into this value".to_string())
+                    .code(DiagnosticCode::IndexableTypeExpected)
+                    .label(
+                        db,
+                        Level::Error,
+                        owner.span(db),
+                        format!(
+                            "this has type `{ty}`, which cannot be indexed",
+                            ty = env.describe_ty(owner.ty(db))
+                        ),
+                    )
+                    .label(
+                        db,
+                        Level::Help,
+                        expr_span,
+                        "only values of type `Vec` can be indexed".to_string(),
+                    ),
+            ),
+        );
+    };
+
+    let place_expr = SymPlaceExpr::new(
+        db,
+        expr_span,
+        owner_perm.apply_to(db, element_ty),
+        SymPlaceExprKind::Index(owner, index_var),
+    );
+    ExprResult::from_place_expr(db, place_expr, temporaries)
+}
+
+/// Checks `match scrutinee { arms }`.
+///
+/// The scrutinee is stored into a place and each arm becomes a [`SymMatchArm`][]
+/// whose condition tests the variant ([`SymExprKind::IsVariant`]) and whose body
+/// begins by binding each payload field to a reference to that field of the place.
+/// So `match s { Shape.Circle(r) => r, _ => 0 }` is checked roughly like
+/// `if <s is Circle> { let r = s.radius.ref; r } else { 0 }`. Because the bindings
+/// are references to the scrutinee, they carry its permissions, just like a field access would.
+#[boxed_async_fn]
+async fn check_match_expr<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    expr_span: Span<'db>,
+    ast_scrutinee: &AstExpr<'db>,
+    ast_arms: &SpanVec<'db, AstMatchArm<'db>>,
+) -> ExprResult<'db> {
+    let db = env.db();
+
+    let mut temporaries = vec![];
+    let live_after_scrutinee = live_after.before_pending(env);
+    let scrutinee = ast_scrutinee
+        .check_in_env(env, live_after_scrutinee)
+        .await
+        .into_place_expr(env, &mut temporaries);
+
+    // Block until we know what kind of value is being matched.
+    let (scrutinee_red_ty, scrutinee_perm) = non_infer_lower_bound(env, scrutinee.ty(db)).await;
+    let scrutinee_is_never = matches!(scrutinee_red_ty, RedTy::Never);
+    let scrutinee_enum = match scrutinee_red_ty {
+        RedTy::Named(SymTyName::Aggregate(aggr), generics) if aggr.is_enum(db) => {
+            Ok((aggr, generics))
+        }
+        RedTy::Error(reported) => Err(Some(reported)),
+        _ => Err(None),
+    };
+
+    let match_ty = if ast_arms.is_empty() {
+        SymTy::never(db)
+    } else {
+        env.fresh_ty_inference_var(expr_span)
+    };
+
+    let mut arms = vec![];
+    let mut covered_variants = vec![];
+    let mut catch_all = false;
+    for ast_arm in &ast_arms.values {
+        let pattern = &ast_arm.pattern;
+
+        // Bindings introduced by the pattern are only in scope within the arm.
+        let outer_scope = env.scope.clone();
+
+        let mut reachable = !catch_all;
+        let mut bindings = vec![];
+        let condition = match &pattern.kind {
+            AstPatternKind::Wildcard => {
+                catch_all = true;
+                None
+            }
+
+            AstPatternKind::Binding(id) => {
+                catch_all = true;
+                bindings.push(bind_pattern_variable(env, *id, scrutinee));
+                None
+            }
+
+            AstPatternKind::Variant(path, sub_patterns) => {
+                match resolve_variant_pattern(env, path, pattern.span, scrutinee, &scrutinee_enum) {
+                    Ok((variant, generics)) => {
+                        if covered_variants.contains(&variant) {
+                            reachable = false;
+                        } else {
+                            covered_variants.push(variant);
+                        }
+
+                        if let Some(sub_patterns) = sub_patterns {
+                            bind_variant_fields(
+                                env,
+                                variant,
+                                &generics,
+                                scrutinee,
+                                scrutinee_perm,
+                                sub_patterns,
+                                &mut bindings,
+                            );
+                        }
+
+                        Some(SymExpr::new(
+                            db,
+                            pattern.span,
+                            SymTy::boolean(db),
+                            SymExprKind::IsVariant {
+                                place: scrutinee,
+                                variant,
+                            },
+                        ))
+                    }
+                    Err(reported) => Some(SymExpr::err(db, reported)),
+                }
+            }
+        };
+
+        if !reachable {
+            Diagnostic::new(db, Level::Warning, pattern.span, "unreachable match arm")
+                .label(
+                    db,
+                    Level::Warning,
+                    pattern.span,
+                    "this arm will never be executed, as an earlier arm matches the same values",
+                )
+                .report(db);
+        }
+
+        let mut body = ast_arm
+            .result
+            .check_in_env(env, live_after)
+            .await
+            .into_expr_with_enclosed_temporaries(env);
+        for (lv, initializer) in bindings.into_iter().rev() {
+            body = SymExpr::new(
+                db,
+                body.span(db),
+                body.ty(db),
+                SymExprKind::LetIn {
+                    lv,
+                    ty: initializer.ty(db),
+                    initializer: Some(initializer),
+                    body,
+                },
+            );
+        }
+
+        env.scope = outer_scope;
+
+        env.spawn_require_assignable_type(
+            live_after,
+            body.ty(db),
+            match_ty,
+            &BadSubtermError::new(body.span(db), body.ty(db), match_ty),
+        );
+
+        arms.push(SymMatchArm { condition, body });
+    }
+    live_after_scrutinee.resolve_pending(
+        env,
+        arms.iter()
+            .flat_map(|arm| arm.condition.into_iter().chain([arm.body])),
+    );
+
+    // Check that every variant is covered.
+    if !catch_all && let Ok((aggr, _)) = &scrutinee_enum {
+        let missing_variants = aggr
+            .variants(db)
+            .iter()
+            .filter(|v| !covered_variants.contains(v))
+            .map(|v| format!("`{v}`"))
+            .collect::<Vec<_>>();
+        if !missing_variants.is_empty() {
+            let reported = Diagnostic::error(db, expr_span, "non-exhaustive match")
+                .label(
+                    db,
+                    Level::Error,
+                    ast_scrutinee.span,
+                    format!("I did not find an arm for {}", missing_variants.join(", ")),
+                )
+                .label(
+                    db,
+                    Level::Help,
+                    ast_arms.span,
+                    "add the missing arms or a `_` arm to match anything else",
+                )
+                .report(db);
+            return ExprResult::err(db, reported);
+        }
+
+        // Every value that reaches the final arm must match it,
+        // so there is no need to test its condition.
+        if let Some(last_arm) = arms.last_mut() {
+            last_arm.condition = None;
+        }
+    }
+
+    // Values that are not enums can only be matched by a `_` or binding arm
+    // (there are no values of type `!`, so those need no arms at all).
+    if !catch_all
+        && !scrutinee_is_never
+        && let Err(None) = scrutinee_enum
+    {
+        let reported = Diagnostic::error(db, expr_span, "non-exhaustive match")
+            .label(
+                db,
+                Level::Error,
+                ast_scrutinee.span,
+                format!(
+                    "I did not find an arm that matches every value of type `{}`",
+                    env.describe_ty(scrutinee.ty(db))
+                ),
+            )
+            .label(
+                db,
+                Level::Help,
+                ast_arms.span,
+                "add a `_` arm to match anything",
+            )
+            .report(db);
+        return ExprResult::err(db, reported);
+    }
+
+    ExprResult {
+        temporaries,
+        span: expr_span,
+        kind: SymExpr::new(db, expr_span, match_ty, SymExprKind::Match { arms }).into(),
+    }
+}
+
+/// Resolves the path in a pattern like `Shape.Circle(..)` to a variant of the
+/// enum being matched, returning the variant along with the generic arguments of the enum.
+fn resolve_variant_pattern<'db>(
+    env: &mut Env<'db>,
+    path: &AstPath<'db>,
+    pattern_span: Span<'db>,
+    scrutinee: SymPlaceExpr<'db>,
+    scrutinee_enum: &Result<(SymAggregate<'db>, Vec<SymGenericTerm<'db>>), Option<Reported>>,
+) -> Errors<(SymVariant<'db>, Vec<SymGenericTerm<'db>>)> {
+    let db = env.db();
+
+    let sym = path.resolve_to_sym(db, &env.scope)?;
+    let variant = match sym {
+        NameResolutionSym::SymFunction(function) => match function.source(db) {
+            SymFunctionSource::VariantConstructor(variant) => Some(variant),
+            _ => None,
+        },
+        _ => None,
+    };
+    let variant = match variant {
+        Some(variant) => variant,
+        None => {
+            return Err(
+                Diagnostic::error(db, path.span(db), "expected an enum variant")
+                    .label(
+                        db,
+                        Level::Error,
+                        path.span(db),
+                        format!(
+                            "I expected this to name an enum variant, but I found {}",
+                            sym.categorize(db)
+                        ),
+                    )
+                    .report(db),
+            );
+        }
+    };
+
+    match scrutinee_enum {
+        Ok((aggr, generics)) if *aggr == variant.aggregate(db) => Ok((variant, generics.clone())),
+        Ok((aggr, _)) => Err(Diagnostic::error(
+            db,
+            pattern_span,
+            format!("`{variant}` is not a variant of `{aggr}`"),
+        )
+        .label(
+            db,
+            Level::Error,
+            pattern_span,
+            format!(
+                "this pattern matches values of type `{}`",
+                variant.aggregate(db)
+            ),
+        )
+        .label(
+            db,
+            Level::Info,
+            scrutinee.span(db),
+            format!(
+                "but the value being matched has type `{}`",
+                env.describe_ty(scrutinee.ty(db))
+            ),
+        )
+        .report(db)),
+        Err(Some(reported)) => Err(*reported),
+        Err(None) => {
+            Err(
+                Diagnostic::error(db, pattern_span, "variant patterns can only match enums")
+                    .label(
+                        db,
+                        Level::Error,
+                        pattern_span,
+                        format!(
+                            "this pattern matches values of type `{}`",
+                            variant.aggregate(db)
+                        ),
+                    )
+                    .label(
+                        db,
+                        Level::Info,
+                        scrutinee.span(db),
+                        format!(
+                            "but the value being matched has type `{}`, which is not an enum",
+                            env.describe_ty(scrutinee.ty(db))
+                        ),
+                    )
+                    .report(db),
+            )
+        }
+    }
+}
+
+/// Binds the fields of `variant` stored in `scrutinee` to the variables named in `sub_patterns`,
+/// pushing the bindings onto `bindings` and bringing them into scope.
+fn bind_variant_fields<'db>(
+    env: &mut Env<'db>,
+    variant: SymVariant<'db>,
+    generics: &[SymGenericTerm<'db>],
+    scrutinee: SymPlaceExpr<'db>,
+    scrutinee_perm: SymPerm<'db>,
+    sub_patterns: &SpanVec<'db, AstPattern<'db>>,
+    bindings: &mut Vec<(SymVariable<'db>, SymExpr<'db>)>,
+) {
+    let db = env.db();
+    let fields = variant.fields(db);
+
+    if sub_patterns.len() != fields.len() {
+        Diagnostic::error(
+            db,
+            sub_patterns.span,
+            format!(
+                "expected {} field(s) for `{variant}`, found {}",
+                fields.len(),
+                sub_patterns.len()
+            ),
+        )
+        .label(
+            db,
+            Level::Error,
+            sub_patterns.span,
+            format!(
+                "`{variant}` has {} field(s), but this pattern has {}",
+                fields.len(),
+                sub_patterns.len()
+            ),
+        )
+        .label(
+            db,
+            Level::Info,
+            variant.source_span(db),
+            "the variant is declared here",
+        )
+        .report(db);
+    }
+
+    for (sub_pattern, &field) in sub_patterns.iter().zip(fields) {
+        match &sub_pattern.kind {
+            AstPatternKind::Wildcard => {}
+
+            AstPatternKind::Binding(id) => {
+                // The type of the field will be the declared type with
+                // the enum's generics and `self` substituted and the
+                // permission from the scrutinee applied, as for a field access.
+                let field_ty = field
+                    .checked_field_ty(db)
+                    .substitute(db, generics)
+                    .substitute(db, &[SymGenericTerm::Place(scrutinee.into_sym_place(db))]);
+                let field_ty = scrutinee_perm.apply_to(db, field_ty);
+                let field_place = SymPlaceExpr::new(
+                    db,
+                    id.span,
+                    field_ty,
+                    SymPlaceExprKind::Field(scrutinee, field),
+                );
+                bindings.push(bind_pattern_variable(env, *id, field_place));
+            }
+
+            AstPatternKind::Variant(..) => {
+                Diagnostic::error(
+                    db,
+                    sub_pattern.span,
+                    "nested patterns are not yet supported",
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    sub_pattern.span,
+                    "bind this field to a variable and `match` on that instead",
+                )
+                .report(db);
+            }
+        }
+    }
+}
+
+/// Creates a variable named `id` that references `place` and brings it into scope.
+/// Returns the variable and its initializer.
+fn bind_pattern_variable<'db>(
+    env: &mut Env<'db>,
+    id: SpannedIdentifier<'db>,
+    place: SymPlaceExpr<'db>,
+) -> (SymVariable<'db>, SymExpr<'db>) {
+    let db = env.db();
+    let lv = SymVariable::new_local(db, id.id, id.span);
+    let mut temporaries = vec![];
+    let initializer =
+        ExprResult::from_place_expr(db, place, vec![]).into_expr(env, &mut temporaries);
+    assert!(temporaries.is_empty());
+    env.push_program_variable_with_ty(lv, initializer.ty(db));
+    (lv, initializer)
+}
 
-                let future_expr = future
-                    .check_in_env(env, live_after)
-                    .await
-                    .into_expr(env, &mut temporaries);
-                let future_ty = future_expr.ty(db);
+/// Check a constructor expression like `Point { x: 1, y: 2 }`.
+///
+/// Each field value is stored into a temporary in the order it is written,
+/// and the aggregate is then built from those temporaries in declaration order,
+/// much like the automatic constructor that results from `class Point(x: u32, y: u32)`.
+#[boxed_async_fn]
+async fn check_constructor<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    expr_span: Span<'db>,
+    ast_path: &AstPath<'db>,
+    ast_fields: &SpanVec<'db, AstConstructorField<'db>>,
+) -> ExprResult<'db> {
+    let db = env.db();
+    let path_span = ast_path.span(db);
 
-                let awaited_ty = env.fresh_ty_inference_var(await_span);
+    let aggr = match resolve_constructor_path(env, ast_path) {
+        Ok(aggr) => aggr,
+        Err(reported) => {
+            for ast_field in ast_fields {
+                let _ = ast_field.value.check_in_env(env, LivePlaces::fixme()).await;
+            }
+            return ExprResult::err(db, reported);
+        }
+    };
 
-                env.spawn_require_future_type(
-                    live_after,
-                    future_ty,
-                    awaited_ty,
-                    &AwaitNonFuture::new(await_span, future_expr),
-                );
+    // The generic arguments are inferred from the field values.
+    let generics = env.existential_substitution(path_span, &aggr.transitive_generic_parameters(db));
+    let aggr_ty = SymTy::named(db, aggr.into(), generics.clone());
+
+    let fields = aggr.fields(db).collect::<Vec<_>>();
+
+    let mut temporaries = vec![];
+    let mut field_temps: Vec<Option<(SymVariable<'db>, SymTy<'db>)>> = vec![None; fields.len()];
+    let mut field_lets = vec![];
+    let mut reported = None;
+    let live_after_fields = live_after.before_pending_sequence(env, ast_fields.values.len());
+    for (i, ast_field) in ast_fields.iter().enumerate() {
+        let SpannedIdentifier {
+            span: name_span,
+            id,
+        } = ast_field.name;
+        let live_after_field = live_after_fields[i];
+        let value_result = ast_field.value.check_in_env(env, live_after_field).await;
+        let value = match i.checked_sub(1) {
+            Some(previous) => {
+                value_result.into_expr_resolving(env, &mut temporaries, live_after_fields[previous])
+            }
+            None => value_result.into_expr(env, &mut temporaries),
+        };
 
-                ExprResult {
-                    temporaries,
-                    span: expr_span,
-                    kind: SymExpr::new(
+        let Some(index) = fields.iter().position(|field| field.name(db) == id) else {
+            reported = Some(
+                Diagnostic::error(db, name_span, format!("unrecognized field `{id}`"))
+                    .code(DiagnosticCode::UnrecognizedField)
+                    .label(
                         db,
-                        expr_span,
-                        awaited_ty,
-                        SymExprKind::Await {
-                            future: future_expr,
-                            await_keyword: await_span,
-                        },
+                        Level::Error,
+                        name_span,
+                        format!("I could not find a field named `{id}` in `{aggr}`"),
                     )
-                    .into(),
-                }
-            }
-            AstExprKind::UnaryOp(spanned_unary_op, ast_expr) => match spanned_unary_op.op {
-                UnaryOp::Not => {
-                    let mut temporaries = vec![];
-                    let operand = ast_expr
-                        .check_in_env(env, live_after)
-                        .await
-                        .into_expr(env, &mut temporaries);
-                    env.require_expr_has_bool_ty(live_after, operand);
-
-                    ExprResult {
-                        temporaries,
-                        span: expr_span,
-                        kind: SymExpr::new(
-                            db,
-                            expr_span,
-                            SymTy::boolean(db),
-                            SymExprKind::Not {
-                                operand,
-                                op_span: spanned_unary_op.span,
-                            },
-                        )
-                        .into(),
-                    }
-                }
-                UnaryOp::Negate => todo!(),
-            },
+                    .label(
+                        db,
+                        Level::Info,
+                        aggr.name_span(db),
+                        format!("`{aggr}` is declared here"),
+                    )
+                    .report(db),
+            );
+            continue;
+        };
 
-            AstExprKind::Block(ast_block) => ExprResult {
-                temporaries: vec![],
-                span: expr_span,
-                kind: ast_block.check_in_env(env, live_after).await.into(),
-            },
+        if field_temps[index].is_some() {
+            let first_span = ast_fields
+                .iter()
+                .find(|f| f.name.id == id)
+                .unwrap()
+                .name
+                .span;
+            reported = Some(
+                Diagnostic::error(
+                    db,
+                    name_span,
+                    format!("field `{id}` specified more than once"),
+                )
+                .code(DiagnosticCode::DuplicateField)
+                .label(
+                    db,
+                    Level::Error,
+                    name_span,
+                    format!("I found a second value for `{id}` here"),
+                )
+                .label(
+                    db,
+                    Level::Info,
+                    first_span,
+                    format!("`{id}` was first given a value here"),
+                )
+                .report(db),
+            );
+            continue;
+        }
 
-            AstExprKind::If(ast_arms) => {
-                let mut arms = vec![];
-                let mut has_else = false;
-                for arm in ast_arms {
-                    let condition = if let Some(c) = &arm.condition {
-                        let expr = c
-                            .check_in_env(env, LivePlaces::fixme())
-                            .await
-                            .into_expr_with_enclosed_temporaries(env);
-                        env.require_expr_has_bool_ty(LivePlaces::fixme(), expr);
-                        Some(expr)
-                    } else {
-                        has_else = true;
-                        None
-                    };
+        // The expected type is the declared type of the field (including its permission)
+        // with the generics substituted. There is no place for `self` yet,
+        // since the object is still being constructed.
+        let field_ty = fields[index]
+            .checked_field_ty(db)
+            .substitute(db, &generics)
+            .substitute(db, &[SymGenericTerm::Place(SymPlace::erased(db))]);
+        env.spawn_require_assignable_type(
+            live_after_field,
+            value.ty(db),
+            field_ty,
+            &BadSubtermError::for_value(db, value, field_ty),
+        );
 
-                    let body = arm.result.check_in_env(env, live_after).await;
+        let temp = SymVariable::new(db, SymGenericKind::Place, None, ast_field.value.span);
+        field_temps[index] = Some((temp, value.ty(db)));
+        field_lets.push((temp, value));
+    }
 
-                    arms.push(SymMatchArm { condition, body });
-                }
+    let missing_fields = fields
+        .iter()
+        .zip(&field_temps)
+        .filter(|(_, temp)| temp.is_none())
+        .map(|(field, _)| format!("`{}`", field.name(db)))
+        .collect::<Vec<_>>();
+    if !missing_fields.is_empty() {
+        reported = Some(
+            Diagnostic::error(db, expr_span, format!("missing fields in `{aggr}`"))
+                .code(DiagnosticCode::MissingFields)
+                .label(
+                    db,
+                    Level::Error,
+                    path_span,
+                    format!("I did not find a value for {}", missing_fields.join(", ")),
+                )
+                .report(db),
+        );
+    }
 
-                let if_ty = if !has_else {
-                    SymTy::unit(db)
-                } else {
-                    env.fresh_ty_inference_var(expr_span)
-                };
+    if let Some(reported) = reported {
+        return ExprResult::err(db, reported);
+    }
 
-                for arm in &arms {
-                    env.spawn_require_assignable_type(
-                        live_after,
-                        arm.body.ty(db),
-                        if_ty,
-                        &BadSubtermError::new(arm.body.span(db), arm.body.ty(db), if_ty),
-                    );
-                }
+    // Create the resulting aggregate, which looks like
+    //
+    //     let tmp1 = value1 in
+    //     let tmp2 = value2 in
+    //     ...
+    //     Aggregate(tmp_f1, ..., tmp_fN)
+    //
+    // where the `let`s follow the order in the source and `tmp_fK` is
+    // the temporary holding the value of the `K`th field in declaration order.
+    let field_exprs = field_temps
+        .into_iter()
+        .map(|temp| {
+            let (temp, ty) = temp.unwrap();
+            SymPlaceExpr::new(db, temp.span(db), ty, SymPlaceExprKind::Var(temp)).give(db)
+        })
+        .collect();
+    let mut aggregate_expr = SymExpr::new(
+        db,
+        expr_span,
+        aggr_ty,
+        SymExprKind::Aggregate {
+            ty: aggr_ty,
+            fields: field_exprs,
+        },
+    );
+    for (temp, value) in field_lets.into_iter().rev() {
+        aggregate_expr = SymExpr::new(
+            db,
+            expr_span,
+            aggr_ty,
+            SymExprKind::LetIn {
+                lv: temp,
+                ty: value.ty(db),
+                initializer: Some(value),
+                body: aggregate_expr,
+            },
+        );
+    }
 
-                ExprResult {
-                    temporaries: vec![],
-                    span: expr_span,
-                    kind: SymExpr::new(db, expr_span, if_ty, SymExprKind::Match { arms }).into(),
-                }
-            }
+    ExprResult::from_expr(db, aggregate_expr, temporaries)
+}
 
-            AstExprKind::PermissionOp { value, op } => {
-                let mut temporaries = vec![];
-                let value_result = value.check_in_env(env, live_after).await;
-                let place_expr = value_result.into_place_expr(env, &mut temporaries);
-                let sym_place = place_expr.into_sym_place(db);
-                ExprResult {
-                    temporaries,
-                    span: expr_span,
-                    kind: SymExpr::new(
-                        db,
-                        expr_span,
-                        match op {
-                            PermissionOp::Mutate => place_expr.ty(db).mutable(db, sym_place),
-                            PermissionOp::Reference => place_expr.ty(db).referenced(db, sym_place),
-                            PermissionOp::Give => place_expr.ty(db),
-                            PermissionOp::Share => place_expr.ty(db).shared(db),
-                        },
-                        SymExprKind::PermissionOp(*op, place_expr),
-                    )
-                    .into(),
-                }
-            }
-        }
-    })
-    .await
+/// Resolves the path in a constructor like `Point { .. }` to a class or struct.
+fn resolve_constructor_path<'db>(env: &Env<'db>, path: &AstPath<'db>) -> Errors<SymAggregate<'db>> {
+    let db = env.db();
+    let path_span = path.span(db);
+    match path.resolve_to_sym(db, &env.scope)? {
+        NameResolutionSym::SymAggregate(aggr) if aggr.is_enum(db) => Err(Diagnostic::error(
+            db,
+            path_span,
+            format!("cannot construct the enum `{aggr}` from fields"),
+        )
+        .label(
+            db,
+            Level::Error,
+            path_span,
+            format!(
+                "`{aggr}` is an enum, so I expected one of its variants, like `{aggr}.Variant(..)`"
+            ),
+        )
+        .report(db)),
+        NameResolutionSym::SymAggregate(aggr) => Ok(aggr),
+        sym => Err(
+            Diagnostic::error(db, path_span, "expected a class or struct")
+                .label(
+                    db,
+                    Level::Error,
+                    path_span,
+                    format!(
+                        "I expected this to name a class or struct, but I found {}",
+                        sym.categorize(db)
+                    ),
+                )
+                .report(db),
+        ),
+    }
 }
 
 #[boxed_async_fn]
@@ -983,7 +2118,43 @@ async fn check_function_call<'db>(
         input_output,
         substitution,
         ast_args,
-        None,
+        vec![],
+        temporaries,
+    )
+    .await
+}
+
+/// Check a call synthesized by the compiler (e.g., the `concat` calls that implement
+/// string interpolation) whose arguments have already been checked.
+/// All generic arguments are inferred from the arguments.
+#[boxed_async_fn]
+async fn check_synthesized_call<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    span: Span<'db>,
+    function: SymFunction<'db>,
+    args: Vec<SymExpr<'db>>,
+    temporaries: Vec<Temporary<'db>>,
+) -> ExprResult<'db> {
+    let db = env.db();
+
+    let signature = match function.checked_signature(db) {
+        Ok(signature) => signature,
+        Err(reported) => return ExprResult::err(db, reported),
+    };
+    let input_output = signature.input_output(db);
+    let substitution = env.existential_substitution(span, &input_output.variables);
+
+    check_call_common(
+        env,
+        live_after,
+        function,
+        span,
+        span,
+        input_output,
+        substitution,
+        &[],
+        args,
         temporaries,
     )
     .await
@@ -1135,7 +2306,7 @@ async fn check_method_call<'db>(
         input_output,
         substitution,
         ast_args,
-        self_expr,
+        self_expr.into_iter().collect(),
         temporaries,
     )
     .await
@@ -1145,14 +2316,14 @@ async fn check_method_call<'db>(
 #[boxed_async_fn]
 async fn check_call_common<'db>(
     env: &mut Env<'db>,
-    _live_after: LivePlaces,
+    live_after: LivePlaces,
     function: SymFunction<'db>,
     expr_span: Span<'db>,
     callee_span: Span<'db>,
     input_output: &Binder<'db, Binder<'db, SymInputOutput<'db>>>,
     substitution: Vec<SymGenericTerm<'db>>,
     ast_args: &[AstExpr<'db>],
-    self_expr: Option<SymExpr<'db>>,
+    checked_args: Vec<SymExpr<'db>>,
     mut temporaries: Vec<Temporary<'db>>,
 ) -> ExprResult<'db> {
     let db = env.db();
@@ -1163,8 +2334,35 @@ async fn check_call_common<'db>(
     // Instantiate the input-output with the substitution.
     let input_output = input_output.substitute(db, &substitution);
 
+    // Unsafe functions may only be called from an unsafe function or an `unsafe` block.
+    // Calls synthesized by the compiler (e.g., `String.literal`) do not come through here.
+    if function.effects(db).unsafe_effect && !env.unsafe_context {
+        let function_name = function.name(db);
+        env.report(
+            Diagnostic::error(
+                db,
+                callee_span,
+                format!("call to unsafe function `{function_name}` requires an `unsafe` block"),
+            )
+            .code(DiagnosticCode::UnsafeCallOutsideUnsafe)
+            .label(
+                db,
+                Level::Error,
+                callee_span,
+                format!("`{function_name}` is declared `unsafe`, so I can only call it from an `unsafe` function or block"),
+            )
+            .label(
+                db,
+                Level::Info,
+                function.name_span(db),
+                format!("`{function_name}` declared `unsafe` here"),
+            ),
+        );
+    }
+
     // Check the arity of the actual arguments.
-    let self_args: usize = self_expr.is_some() as usize;
+    // The already-checked arguments (e.g., `self`) come before the AST arguments.
+    let self_args: usize = checked_args.len();
     let expected_inputs = input_output.bound_value.input_tys.len();
     let found_inputs = self_args + ast_args.len();
     if found_inputs != expected_inputs {
@@ -1196,7 +2394,7 @@ async fn check_call_common<'db>(
     // Create the temporaries that will hold the values for each argument.
     let arg_temp_span = |i: usize| {
         if i < self_args {
-            self_expr.unwrap().span(db)
+            checked_args[i].span(db)
         } else {
             ast_args
                 .get(i - self_args)
@@ -1219,24 +2417,29 @@ async fn check_call_common<'db>(
     env.log("arg_temp_terms", &[&arg_temp_terms]);
     env.log("input_output", &[&input_output]);
 
+    // Each argument is stored into a temporary before the call, so the places
+    // live afterwards are those used by the later arguments and by the code after the call.
+    let live_after_args = live_after.before_pending_sequence(env, found_inputs);
+
     // Function to type check a single argument and check it has the correct type.
     let check_arg = async |i: usize| -> ExprResult<'db> {
         let mut env = env.fork(|log| log.spawn(Location::caller(), TaskDescription::CheckArg(i)));
         let mut arg_temporaries = vec![];
+        let live_after_arg = live_after_args[i];
         let expr = if i < self_args {
-            self_expr.unwrap()
+            checked_args[i]
         } else {
             let ast_arg = &ast_args[i - self_args];
             ast_arg
-                .check_in_env(&mut env, LivePlaces::fixme())
+                .check_in_env(&mut env, live_after_arg)
                 .await
                 .into_expr(&mut env, &mut arg_temporaries)
         };
         env.spawn_require_assignable_type(
-            LivePlaces::fixme(),
+            live_after_arg,
             expr.ty(db),
             input_output.input_tys[i],
-            &BadSubtermError::new(expr.span(db), expr.ty(db), input_output.input_tys[i]),
+            &BadSubtermError::for_value(db, expr, input_output.input_tys[i]),
         );
         ExprResult::from_expr(env.db(), expr, arg_temporaries)
     };
@@ -1251,9 +2454,14 @@ async fn check_call_common<'db>(
 
     // Type check the arguments; these can proceed concurrently.
     let mut arg_exprs = vec![];
-    arg_exprs.extend(self_expr);
-    for arg_result in futures::future::join_all((0..found_inputs).map(check_arg)).await {
-        arg_exprs.push(arg_result.into_expr(env, &mut temporaries));
+    let arg_results = futures::future::join_all((0..found_inputs).map(check_arg)).await;
+    for (i, arg_result) in arg_results.into_iter().enumerate() {
+        arg_exprs.push(match i.checked_sub(1) {
+            Some(previous) => {
+                arg_result.into_expr_resolving(env, &mut temporaries, live_after_args[previous])
+            }
+            None => arg_result.into_expr(env, &mut temporaries),
+        });
     }
 
     // Create the resulting call, which always looks like
@@ -1327,6 +2535,7 @@ impl<'db> ExprResult<'db> {
             NameResolutionSym::SymFunction(_)
             | NameResolutionSym::SymModule(_)
             | NameResolutionSym::SymAggregate(_)
+            | NameResolutionSym::SymTrait(_)
             | NameResolutionSym::SymPrimitive(_)
             | NameResolutionSym::SymVariable(..) => Self {
                 temporaries: vec![],
@@ -1432,6 +2641,24 @@ impl<'db> ExprResult<'db> {
         }
     }
 
+    /// Like [`Self::into_expr`], but also resolves the pending live point `live_before`
+    /// (see [`LivePlaces::before_pending`]) with the code that evaluates the result,
+    /// including the initializers of its temporaries.
+    pub fn into_expr_resolving(
+        self,
+        env: &mut Env<'db>,
+        temporaries: &mut Vec<Temporary<'db>>,
+        live_before: LivePlaces,
+    ) -> SymExpr<'db> {
+        let first_temporary = temporaries.len();
+        let expr = self.into_expr(env, temporaries);
+        let initializers = temporaries[first_temporary..]
+            .iter()
+            .filter_map(|temporary| temporary.initializer);
+        live_before.resolve_pending(env, initializers.chain([expr]));
+        expr
+    }
+
     pub fn into_expr(
         self,
         env: &mut Env<'db>,
@@ -1466,17 +2693,6 @@ impl<'db> ExprResult<'db> {
     }
 }
 
-fn report_not_implemented<'db>(db: &'db dyn crate::Db, span: Span<'db>, what: &str) -> Reported {
-    Diagnostic::error(db, span, "not implemented yet :(".to_string())
-        .label(
-            db,
-            Level::Error,
-            span,
-            format!("sorry, but {what} have not been implemented yet :(",),
-        )
-        .report(db)
-}
-
 fn report_non_expr<'db>(
     db: &'db dyn crate::Db,
     owner_span: Span<'db>,
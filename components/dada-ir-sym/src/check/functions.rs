@@ -1,7 +1,7 @@
 use crate::{
     check::{CheckTyInEnv, signature::PreparedEnv},
     ir::{
-        classes::SymAggregate,
+        classes::{SymAggregate, SymVariant},
         functions::{SymFunction, SymFunctionSource},
     },
 };
@@ -35,6 +35,9 @@ pub(crate) fn check_function_body<'db>(
         SymFunctionSource::Constructor(sym_class, ast_class_item) => Some(
             check_function_body_class_constructor(db, function, sym_class, ast_class_item),
         ),
+        SymFunctionSource::VariantConstructor(variant) => Some(
+            check_function_body_variant_constructor(db, function, variant),
+        ),
     }
 }
 
@@ -51,7 +54,7 @@ fn check_main_function<'db>(
         async move |runtime| -> SymExpr<'db> {
             let mut env: Env<'db> = Env::new(runtime, function.scope(db));
             let statements = mfunc.statements(db);
-            let live_after = LivePlaces::none(&env);
+            let live_after = LivePlaces::none();
             check_block_statements(&mut env, live_after, statements.span, statements).await
         },
         |expr| expr,
@@ -130,6 +133,57 @@ fn check_function_body_class_constructor<'db>(
     )
 }
 
+/// Check the automatic constructor for an enum variant, like `Circle(radius: u32)`.
+fn check_function_body_variant_constructor<'db>(
+    db: &'db dyn crate::Db,
+    function: SymFunction<'db>,
+    variant: SymVariant<'db>,
+) -> SymExpr<'db> {
+    Runtime::execute(
+        db,
+        function.name_span(db),
+        "check_function_body_variant_constructor",
+        &[&function, &variant],
+        async move |runtime| -> SymExpr<'db> {
+            let PreparedEnv {
+                ref mut env,
+                input_symbols,
+                input_tys,
+                ..
+            } = prepare_env(db, runtime, function).await;
+
+            let scope = env.scope.clone();
+            let self_ty = variant
+                .aggregate(db)
+                .self_ty(db, &scope)
+                .check_in_env(env)
+                .await;
+            assert_eq!(input_symbols.len(), input_tys.len());
+            assert_eq!(input_symbols.len(), variant.fields(db).len());
+
+            let fields = input_symbols
+                .iter()
+                .zip(&input_tys)
+                .map(|(&v, &ty)| {
+                    SymPlaceExpr::new(db, v.span(db), ty, SymPlaceExprKind::Var(v)).give(db)
+                })
+                .collect();
+
+            SymExpr::new(
+                db,
+                variant.source(db).span(db),
+                self_ty,
+                SymExprKind::Variant {
+                    ty: self_ty,
+                    variant,
+                    fields,
+                },
+            )
+        },
+        |expr| expr,
+    )
+}
+
 fn check_function_body_ast_block<'db>(
     db: &'db dyn crate::Db,
     function: SymFunction<'db>,
@@ -148,7 +202,7 @@ fn check_function_body_ast_block<'db>(
             } = prepare_env(db, runtime, function).await;
 
             env.log("check_function_body_ast_block", &[&function, &body]);
-            let live_after = LivePlaces::none(&env);
+            let live_after = LivePlaces::none();
             let expr = body.check_in_env(&mut env, live_after).await;
             env.spawn_require_assignable_type(
                 live_after,
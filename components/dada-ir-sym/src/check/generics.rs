@@ -1,8 +1,15 @@
-use dada_ir_ast::ast::{AstWhereClause, AstWhereClauseKind};
+use dada_ir_ast::{
+    ast::{AstWhereClause, AstWhereClauseKind},
+    diagnostic::{Diagnostic, Level},
+    span::Spanned,
+};
 
-use crate::ir::generics::{SymWhereClause, SymWhereClauseKind};
+use crate::ir::{
+    generics::{SymWhereClause, SymWhereClauseKind},
+    types::SymGenericKind,
+};
 
-use super::{CheckTyInEnv, env::Env};
+use super::{CheckTyInEnv, env::Env, traits::check_trait_ref};
 
 pub async fn symbolify_ast_where_clause<'db>(
     env: &mut Env<'db>,
@@ -12,7 +19,7 @@ pub async fn symbolify_ast_where_clause<'db>(
     let db = env.db();
     let subject = ast_where_clause.subject(db).check_in_env(env).await;
     let mut push_kind =
-        |kind: SymWhereClauseKind| output.push(SymWhereClause::new(db, subject, kind));
+        |kind: SymWhereClauseKind<'db>| output.push(SymWhereClause::new(db, subject, kind));
 
     for kind in ast_where_clause.kinds(db) {
         match kind {
@@ -36,6 +43,25 @@ pub async fn symbolify_ast_where_clause<'db>(
             AstWhereClauseKind::Unique(_) => {
                 push_kind(SymWhereClauseKind::Unique);
             }
+            AstWhereClauseKind::Trait(path) => {
+                if !subject.has_kind(db, SymGenericKind::Type) {
+                    let span = ast_where_clause.subject(db).span(db);
+                    Diagnostic::error(db, span, "only types can implement traits")
+                        .label(
+                            db,
+                            Level::Error,
+                            span,
+                            "this is not a type, so it cannot implement a trait",
+                        )
+                        .report(db);
+                    continue;
+                }
+
+                // Errors are reported by `check_trait_ref`.
+                if let Ok(trait_ref) = check_trait_ref(env, *path).await {
+                    push_kind(SymWhereClauseKind::Trait(trait_ref));
+                }
+            }
         }
     }
 }
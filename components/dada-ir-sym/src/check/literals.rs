@@ -0,0 +1,328 @@
+use dada_ir_ast::{
+    diagnostic::{Diagnostic, DiagnosticCode, Err, Errors, Level},
+    span::Span,
+};
+use dada_util::boxed_async_fn;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    check::{
+        env::Env,
+        exprs::ExprResult,
+        inference::Direction,
+        live_places::LivePlaces,
+        red::RedTy,
+        report::{Because, LiteralOutOfRange, NumericTypeExpected, OrElse},
+        to_red::ToRedTy,
+    },
+    ir::{
+        exprs::{SymExpr, SymExprKind, SymLiteral},
+        primitive::SymPrimitiveKind,
+        types::{SymTy, SymTyName},
+    },
+};
+
+/// Check a numeric literal like `22`, `0xFF`, `1.5e3`, or `22_u8`.
+///
+/// If the literal has a type suffix, that is its type;
+/// otherwise its type is inferred and must be numeric.
+/// Either way, we spawn a task to check that the value fits in the type.
+/// If `negated` is true, the literal is the operand of a unary `-`,
+/// which allows one more than the maximum value of a signed type (e.g., `-128_i8`).
+pub(super) fn check_numeric_literal<'db>(
+    env: &mut Env<'db>,
+    live_after: LivePlaces,
+    span: Span<'db>,
+    text: &str,
+    negated: bool,
+) -> ExprResult<'db> {
+    let db = env.db();
+
+    let (literal, suffix) = match parse_numeric_literal(text) {
+        Ok(v) => v,
+        Err(InvalidNumericLiteral {
+            code,
+            message,
+            label,
+        }) => {
+            return ExprResult::err(
+                db,
+                Diagnostic::error(db, span, message)
+                    .code(code)
+                    .label(db, Level::Error, span, label)
+                    .report(db),
+            );
+        }
+    };
+
+    let ty = match suffix {
+        Some(kind) => SymTy::primitive(db, kind),
+        None => env.fresh_ty_inference_var(span),
+    };
+    let sym_expr = SymExpr::new(db, span, ty, SymExprKind::Primitive(literal));
+    env.spawn_require_my_numeric_type(live_after, ty, &NumericTypeExpected::new(sym_expr, ty));
+    env.spawn_require_literal_fits(sym_expr, literal, negated);
+
+    ExprResult {
+        temporaries: vec![],
+        span,
+        kind: sym_expr.into(),
+    }
+}
+
+/// Check a character literal like `'c'`; the tokenizer has already processed escapes.
+pub(super) fn check_char_literal<'db>(
+    env: &mut Env<'db>,
+    span: Span<'db>,
+    text: &str,
+) -> ExprResult<'db> {
+    let db = env.db();
+    let ch = text.chars().next().unwrap_or('\0');
+    ExprResult {
+        temporaries: vec![],
+        span,
+        kind: SymExpr::new(
+            db,
+            span,
+            SymTy::primitive(db, SymPrimitiveKind::Char),
+            SymExprKind::Primitive(SymLiteral::Integral {
+                bits: u64::from(u32::from(ch)),
+            }),
+        )
+        .into(),
+    }
+}
+
+/// Error from [`parse_numeric_literal`].
+struct InvalidNumericLiteral {
+    code: DiagnosticCode,
+    message: String,
+    label: String,
+}
+
+impl InvalidNumericLiteral {
+    fn new(code: DiagnosticCode, message: impl ToString, label: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            label: label.to_string(),
+        }
+    }
+}
+
+/// Parse the text of a numeric literal into its value and (optional) type suffix.
+fn parse_numeric_literal(
+    text: &str,
+) -> Result<(SymLiteral, Option<SymPrimitiveKind>), InvalidNumericLiteral> {
+    let (radix, radix_name, body) = if let Some(body) = text.strip_prefix("0x") {
+        (16, "hexadecimal", body)
+    } else if let Some(body) = text.strip_prefix("0o") {
+        (8, "octal", body)
+    } else if let Some(body) = text.strip_prefix("0b") {
+        (2, "binary", body)
+    } else {
+        (10, "decimal", text)
+    };
+    let prefix = &text[..text.len() - body.len()];
+
+    let digits_len = if radix == 10 {
+        decimal_len(body)
+    } else {
+        body.find(|c: char| !(c.is_digit(radix) || c == '_'))
+            .unwrap_or(body.len())
+    };
+    let (digits, suffix) = body.split_at(digits_len);
+
+    if !digits.chars().any(|c| c.is_digit(radix)) {
+        return Err(InvalidNumericLiteral::new(
+            DiagnosticCode::InvalidLiteral,
+            format!("missing digits in {radix_name} literal"),
+            format!("I expected {radix_name} digits after `{prefix}`"),
+        ));
+    }
+
+    let suffix = match suffix {
+        "" => None,
+        "i8" => Some(SymPrimitiveKind::Int { bits: 8 }),
+        "i16" => Some(SymPrimitiveKind::Int { bits: 16 }),
+        "i32" => Some(SymPrimitiveKind::Int { bits: 32 }),
+        "i64" => Some(SymPrimitiveKind::Int { bits: 64 }),
+        "isize" => Some(SymPrimitiveKind::Isize),
+        "u8" => Some(SymPrimitiveKind::Uint { bits: 8 }),
+        "u16" => Some(SymPrimitiveKind::Uint { bits: 16 }),
+        "u32" => Some(SymPrimitiveKind::Uint { bits: 32 }),
+        "u64" => Some(SymPrimitiveKind::Uint { bits: 64 }),
+        "usize" => Some(SymPrimitiveKind::Usize),
+        "f32" => Some(SymPrimitiveKind::Float { bits: 32 }),
+        "f64" => Some(SymPrimitiveKind::Float { bits: 64 }),
+        _ => {
+            return Err(match suffix.chars().next() {
+                Some(c) if c.is_ascii_digit() => InvalidNumericLiteral::new(
+                    DiagnosticCode::InvalidLiteral,
+                    format!("invalid digit in {radix_name} literal"),
+                    format!("`{c}` is not a {radix_name} digit"),
+                ),
+                _ => InvalidNumericLiteral::new(
+                    DiagnosticCode::InvalidLiteral,
+                    format!("invalid suffix `{suffix}` for numeric literal"),
+                    format!("`{suffix}` is not a numeric type"),
+                ),
+            });
+        }
+    };
+
+    let digits: String = digits.chars().filter(|&c| c != '_').collect();
+    let is_float =
+        digits.contains(['.', 'e', 'E']) || matches!(suffix, Some(SymPrimitiveKind::Float { .. }));
+
+    let literal = if is_float && radix == 10 {
+        match digits.parse::<f64>() {
+            Ok(value) => SymLiteral::Float {
+                bits: OrderedFloat(value),
+            },
+            Err(e) => {
+                return Err(InvalidNumericLiteral::new(
+                    DiagnosticCode::InvalidLiteral,
+                    "invalid float literal",
+                    e,
+                ));
+            }
+        }
+    } else {
+        match u64::from_str_radix(&digits, radix) {
+            Ok(bits) => SymLiteral::Integral { bits },
+            Err(_) => {
+                return Err(InvalidNumericLiteral::new(
+                    DiagnosticCode::LiteralOutOfRange,
+                    "integer literal is too large",
+                    format!("integer literals cannot be larger than {}", u64::MAX),
+                ));
+            }
+        }
+    };
+
+    Ok((literal, suffix))
+}
+
+/// Length of the decimal number at the start of `text`,
+/// including any fraction and exponent but not the type suffix.
+/// Mirrors the rules used by the tokenizer.
+fn decimal_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let digits = |mut len: usize| {
+        while bytes
+            .get(len)
+            .is_some_and(|&b| b.is_ascii_digit() || b == b'_')
+        {
+            len += 1;
+        }
+        len
+    };
+
+    let mut len = digits(0);
+
+    if bytes.get(len) == Some(&b'.') && bytes.get(len + 1).is_some_and(u8::is_ascii_digit) {
+        len = digits(len + 1);
+    }
+
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+        if bytes.get(len + 1 + sign).is_some_and(u8::is_ascii_digit) {
+            len = digits(len + 1 + sign);
+        }
+    }
+
+    len
+}
+
+/// Check that the value of `literal` can be represented by the type of `expr`,
+/// waiting for the type to be inferred if needed.
+pub(super) async fn require_literal_fits<'db>(
+    env: &mut Env<'db>,
+    expr: SymExpr<'db>,
+    literal: SymLiteral,
+    negated: bool,
+) -> Errors<()> {
+    let (red_ty, _) = expr.ty(env.db()).to_red_ty(env);
+    require_literal_fits_red_ty(env, expr, literal, negated, red_ty, Because::JustSo).await
+}
+
+#[boxed_async_fn]
+async fn require_literal_fits_red_ty<'db>(
+    env: &mut Env<'db>,
+    expr: SymExpr<'db>,
+    literal: SymLiteral,
+    negated: bool,
+    red_ty: RedTy<'db>,
+    because: Because<'db>,
+) -> Errors<()> {
+    let db = env.db();
+    match red_ty {
+        RedTy::Named(SymTyName::Primitive(primitive), _) => {
+            if literal_fits(literal, negated, primitive.kind(db)) {
+                Ok(())
+            } else {
+                Err(LiteralOutOfRange::new(expr, literal, primitive).report(env, because))
+            }
+        }
+
+        RedTy::Infer(infer) => {
+            // As in `require_numeric_type`, the lower bound of a numeric type
+            // is the type itself, so the first one we see is sufficient.
+            // If there is none, the missing type is reported elsewhere.
+            let Some((lower_red_ty, arc_or_else)) =
+                env.red_bound(infer, Direction::FromBelow).ty().await
+            else {
+                return Ok(());
+            };
+            require_literal_fits_red_ty(
+                env,
+                expr,
+                literal,
+                negated,
+                lower_red_ty.clone(),
+                Because::InferredLowerBound(lower_red_ty, arc_or_else),
+            )
+            .await
+        }
+
+        // Non-numeric types are reported by `require_numeric_type`.
+        RedTy::Error(_) | RedTy::Named(..) | RedTy::Never | RedTy::Var(_) | RedTy::Perm => Ok(()),
+    }
+}
+
+/// True if `literal` (or its negation, if `negated`) can be represented as a value of type `kind`.
+/// Negating unsigned values is reported separately, so we do not check it here.
+fn literal_fits(literal: SymLiteral, negated: bool, kind: SymPrimitiveKind) -> bool {
+    match literal {
+        SymLiteral::Integral { bits } => match kind {
+            SymPrimitiveKind::Float { .. } => true,
+            SymPrimitiveKind::Int { .. } | SymPrimitiveKind::Isize if negated => {
+                integral_max(kind).is_none_or(|max| bits <= max + 1)
+            }
+            _ => integral_max(kind).is_none_or(|max| bits <= max),
+        },
+        SymLiteral::Float { bits } => match kind {
+            SymPrimitiveKind::Float { bits: 32 } => (bits.0 as f32).is_finite(),
+            SymPrimitiveKind::Float { .. } => bits.0.is_finite(),
+            SymPrimitiveKind::Bool | SymPrimitiveKind::Char => true,
+            SymPrimitiveKind::Int { .. }
+            | SymPrimitiveKind::Isize
+            | SymPrimitiveKind::Uint { .. }
+            | SymPrimitiveKind::Usize => false,
+        },
+    }
+}
+
+/// Largest value of the integer type `kind`, or `None` if `kind` is not an integer type.
+/// `isize` and `usize` are 32 bits, as on our wasm target.
+pub(super) fn integral_max(kind: SymPrimitiveKind) -> Option<u64> {
+    match kind {
+        SymPrimitiveKind::Int { bits } => Some((1 << (bits - 1)) - 1),
+        SymPrimitiveKind::Isize => Some(i32::MAX as u64),
+        SymPrimitiveKind::Uint { bits: 64 } => Some(u64::MAX),
+        SymPrimitiveKind::Uint { bits } => Some((1 << bits) - 1),
+        SymPrimitiveKind::Usize => Some(u32::MAX as u64),
+        SymPrimitiveKind::Bool | SymPrimitiveKind::Char | SymPrimitiveKind::Float { .. } => None,
+    }
+}
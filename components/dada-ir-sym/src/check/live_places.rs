@@ -1,31 +1,366 @@
-use crate::ir::types::SymPlace;
+use std::sync::Arc;
+
+use dada_util::{Map, Set};
+
+use crate::ir::{
+    exprs::{SymExpr, SymExprKind, SymPlaceExpr, SymPlaceExprKind},
+    types::{SymPlace, SymPlaceKind},
+    variables::SymVariable,
+};
 
 use super::env::Env;
 
-/// Placeholder for the liveness computation we will be doing
-#[derive(Copy, Clone)]
-pub struct LivePlaces {}
+/// The set of places that may be used by the code that executes after some point.
+/// When a place `p` is not live, leases like `mut[p]` or `ref[p]` have ended,
+/// and so a value with type `mut[p] mut[q] String` can be used as if it were `mut[q] String`.
+///
+/// Liveness is tracked at the granularity of variables: the place `x.f` is live
+/// if some later expression mentions `x`, so a use of any field of `x` keeps all of `x` live.
+///
+/// We type check code forwards, but liveness depends on the code that comes *after*
+/// a given point, which has not been checked yet. Therefore each `LivePlaces` value is a handle
+/// to a *live point* stored in the [`Runtime`](`crate::check::runtime::Runtime`).
+/// Once the code that follows the point has been checked, the resulting [`SymExpr`]s are
+/// recorded with [`LivePlaces::resolve_pending`]. When the whole function body has been checked,
+/// liveness is computed once, backwards over the checked expressions, and [`LivePlaces::is_live`]
+/// (which blocks until then) can answer queries.
+#[derive(Copy, Clone, Debug)]
+pub struct LivePlaces {
+    kind: LivePlacesKind,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum LivePlacesKind {
+    /// Every place is considered live.
+    All,
+
+    /// No place is live.
+    None,
+
+    /// The places live at the given live point.
+    Point(LivePointIndex),
+}
+
+/// Identifies a live point created during type checking.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct LivePointIndex(usize);
 
-#[expect(unused_variables)]
 impl LivePlaces {
     /// Assume no places are live.
-    pub fn none<'db>(env: &Env<'db>) -> Self {
-        Self {}
+    pub fn none() -> Self {
+        Self {
+            kind: LivePlacesKind::None,
+        }
     }
 
     /// Special placeholder for when we relate bounds on inference variables.
     /// For permissions, these bounds are [`RedPerm`](`crate::check::red::RedPerm`)
     /// values and already contain liveness information.
     pub fn infer_bounds() -> Self {
-        Self {}
+        Self {
+            kind: LivePlacesKind::All,
+        }
     }
 
-    /// Used where we have to think about the right value
+    /// Used where we have to think about the right value.
+    /// Considers all places to be live.
     pub fn fixme() -> Self {
-        Self {}
+        Self {
+            kind: LivePlacesKind::All,
+        }
+    }
+
+    /// Places live before some code that has not yet been checked,
+    /// given that `self` are live once that code has executed.
+    ///
+    /// The caller must invoke [`Self::resolve_pending`] on the result once that code has been checked;
+    /// until then (or if it never does), all places are considered live.
+    pub fn before_pending(self, env: &Env<'_>) -> Self {
+        let index = env.runtime().live_points().push(self);
+        Self {
+            kind: LivePlacesKind::Point(index),
+        }
+    }
+
+    /// Places live after each of `len` pieces of code that execute in sequence,
+    /// given that `self` are live after the last one.
+    ///
+    /// All but the last entry are pending: once piece `i + 1` has been checked,
+    /// the caller must resolve entry `i` with it (see [`Self::resolve_pending`]).
+    pub fn before_pending_sequence(self, env: &Env<'_>, len: usize) -> Vec<Self> {
+        let mut live_after_each = vec![self; len];
+        for i in (0..len.saturating_sub(1)).rev() {
+            live_after_each[i] = live_after_each[i + 1].before_pending(env);
+        }
+        live_after_each
+    }
+
+    /// Record the checked form of the code that follows a point created by [`Self::before_pending`].
+    pub fn resolve_pending<'db>(
+        self,
+        env: &Env<'db>,
+        exprs: impl IntoIterator<Item = SymExpr<'db>>,
+    ) {
+        let LivePlacesKind::Point(index) = self.kind else {
+            panic!("`resolve_pending` invoked on `{self:?}`");
+        };
+        env.runtime()
+            .live_points()
+            .resolve(index, exprs.into_iter().map(LiveUse::Expr).collect());
+    }
+
+    /// Like [`Self::resolve_pending`], but for code that only uses the given place expressions.
+    pub fn resolve_pending_places<'db>(
+        self,
+        env: &Env<'db>,
+        places: impl IntoIterator<Item = SymPlaceExpr<'db>>,
+    ) {
+        let LivePlacesKind::Point(index) = self.kind else {
+            panic!("`resolve_pending_places` invoked on `{self:?}`");
+        };
+        env.runtime()
+            .live_points()
+            .resolve(index, places.into_iter().map(LiveUse::Place).collect());
+    }
+
+    /// True if `place` may be used later.
+    ///
+    /// Blocks until the liveness of the current function has been computed.
+    pub async fn is_live<'db>(&self, env: &Env<'db>, place: SymPlace<'db>) -> bool {
+        let index = match self.kind {
+            LivePlacesKind::All => return true,
+            LivePlacesKind::None => None,
+            LivePlacesKind::Point(index) => Some(index),
+        };
+
+        let db = env.db();
+        let Some(var) = place_variable(db, place) else {
+            return true;
+        };
+
+        // Unnamed variables are temporaries introduced by the type checker;
+        // their uses are not always visible in the checked expressions, so we assume they are live.
+        if var.name(db).is_none() {
+            return true;
+        }
+
+        let Some(index) = index else {
+            return false;
+        };
+
+        env.runtime().liveness_computed().await;
+        env.runtime().live_points().is_live(db, index, var)
+    }
+}
+
+/// Returns the variable whose value contains `place`, if any.
+fn place_variable<'db>(db: &'db dyn crate::Db, place: SymPlace<'db>) -> Option<SymVariable<'db>> {
+    match *place.kind(db) {
+        SymPlaceKind::Var(var) => Some(var),
+        SymPlaceKind::Field(owner, _) | SymPlaceKind::Index(owner) => place_variable(db, owner),
+        SymPlaceKind::Erased | SymPlaceKind::Error(_) => None,
     }
+}
+
+/// Code that executes after a live point.
+#[derive(Copy, Clone)]
+enum LiveUse<'db> {
+    Expr(SymExpr<'db>),
+    Place(SymPlaceExpr<'db>),
+}
+
+/// Variables that may be used at some point, or `None` if all variables may be used.
+type LiveVariables<'db> = Option<Arc<Set<SymVariable<'db>>>>;
+
+/// The live points created while checking a function, stored in the runtime.
+#[derive(Default)]
+pub(crate) struct LivePoints<'db> {
+    points: Vec<LivePoint<'db>>,
+
+    /// Variables used by each expression, computed bottom-up and shared by all points.
+    expr_variables: Map<SymExpr<'db>, Arc<Set<SymVariable<'db>>>>,
+}
+
+struct LivePoint<'db> {
+    /// Code that executes after this point, once it has been checked.
+    uses: Option<Vec<LiveUse<'db>>>,
+
+    /// Places live after `uses` have executed.
+    live_after: LivePlaces,
+
+    /// Cached result of [`LivePoints::live_variables`], once known.
+    variables: Option<Arc<Set<SymVariable<'db>>>>,
+}
+
+impl<'db> LivePoints<'db> {
+    fn push(&mut self, live_after: LivePlaces) -> LivePointIndex {
+        let index = LivePointIndex(self.points.len());
+        self.points.push(LivePoint {
+            uses: None,
+            live_after,
+            variables: None,
+        });
+        index
+    }
+
+    fn resolve(&mut self, index: LivePointIndex, uses: Vec<LiveUse<'db>>) {
+        let point = &mut self.points[index.0];
+        assert!(point.uses.is_none(), "live point resolved twice");
+        point.uses = Some(uses);
+    }
+
+    fn is_live(
+        &mut self,
+        db: &'db dyn crate::Db,
+        index: LivePointIndex,
+        var: SymVariable<'db>,
+    ) -> bool {
+        match self.live_variables(db, index) {
+            Some(variables) => variables.contains(&var),
+            None => true,
+        }
+    }
+
+    /// Variables used by the code that executes after the point `index`.
+    fn live_variables(
+        &mut self,
+        db: &'db dyn crate::Db,
+        index: LivePointIndex,
+    ) -> LiveVariables<'db> {
+        if let Some(variables) = &self.points[index.0].variables {
+            return Some(variables.clone());
+        }
+
+        let LivePoint {
+            uses, live_after, ..
+        } = &self.points[index.0];
+        let live_after = *live_after;
+        let Some(uses) = uses.clone() else {
+            // Not (yet) resolved: we have to assume that everything is live.
+            // We do not cache this, as the point may be resolved later.
+            return None;
+        };
+
+        let variables = match live_after.kind {
+            LivePlacesKind::All => None,
+            LivePlacesKind::None => Some(Set::default()),
+            LivePlacesKind::Point(after) => self
+                .live_variables(db, after)
+                .map(|variables| (*variables).clone()),
+        };
+
+        // If everything is live afterwards, everything is live here too.
+        // We do not cache this, as it may be due to a point that has not yet been resolved.
+        let mut variables = variables?;
+        for live_use in uses {
+            match live_use {
+                LiveUse::Expr(expr) => variables.extend(self.expr_variables(db, expr).iter()),
+                LiveUse::Place(place) => place_expr_variables(db, place, &mut variables),
+            }
+        }
+
+        let variables = Arc::new(variables);
+        self.points[index.0].variables = Some(variables.clone());
+        Some(variables)
+    }
+
+    /// Variables used by `expr`.
+    fn expr_variables(
+        &mut self,
+        db: &'db dyn crate::Db,
+        expr: SymExpr<'db>,
+    ) -> Arc<Set<SymVariable<'db>>> {
+        if let Some(variables) = self.expr_variables.get(&expr) {
+            return variables.clone();
+        }
+
+        let mut variables = Set::default();
+        let add_expr = |this: &mut Self, e: SymExpr<'db>, variables: &mut Set<_>| {
+            variables.extend(this.expr_variables(db, e).iter());
+        };
+        match expr.kind(db) {
+            SymExprKind::Semi(lhs, rhs) | SymExprKind::BinaryOp(_, lhs, rhs) => {
+                add_expr(self, *lhs, &mut variables);
+                add_expr(self, *rhs, &mut variables);
+            }
+
+            SymExprKind::Tuple(exprs)
+            | SymExprKind::Aggregate { fields: exprs, .. }
+            | SymExprKind::Variant { fields: exprs, .. } => {
+                for &e in exprs {
+                    add_expr(self, e, &mut variables);
+                }
+            }
+
+            SymExprKind::LetIn {
+                lv,
+                ty: _,
+                initializer,
+                body,
+            } => {
+                // The variable is not live before it is declared.
+                add_expr(self, *body, &mut variables);
+                variables.remove(lv);
+                if let Some(initializer) = initializer {
+                    add_expr(self, *initializer, &mut variables);
+                }
+            }
+
+            SymExprKind::Await { future: e, .. }
+            | SymExprKind::Return(e)
+            | SymExprKind::Not { operand: e, .. }
+            | SymExprKind::Negate { operand: e, .. }
+            | SymExprKind::Loop { body: e } => add_expr(self, *e, &mut variables),
+
+            SymExprKind::Assign { place, value } => {
+                place_expr_variables(db, *place, &mut variables);
+                add_expr(self, *value, &mut variables);
+            }
+
+            SymExprKind::PermissionOp(_, place) | SymExprKind::IsVariant { place, .. } => {
+                place_expr_variables(db, *place, &mut variables)
+            }
+
+            SymExprKind::Call { arg_temps, .. } => variables.extend(arg_temps.iter().copied()),
+
+            SymExprKind::Match { arms } => {
+                for arm in arms {
+                    if let Some(condition) = arm.condition {
+                        add_expr(self, condition, &mut variables);
+                    }
+                    add_expr(self, arm.body, &mut variables);
+                }
+            }
+
+            // Types may mention places too, but mentioning a place in a type is not a use.
+            SymExprKind::Primitive(_)
+            | SymExprKind::ByteLiteral(_)
+            | SymExprKind::Break
+            | SymExprKind::Continue
+            | SymExprKind::Error(_) => {}
+        }
+
+        let variables = Arc::new(variables);
+        self.expr_variables.insert(expr, variables.clone());
+        variables
+    }
+}
 
-    pub fn is_live<'db>(&self, env: &Env<'db>, place: SymPlace<'db>) -> bool {
-        true // FIXME
+/// Insert the variables used by the place expression `place`.
+fn place_expr_variables<'db>(
+    db: &'db dyn crate::Db,
+    place: SymPlaceExpr<'db>,
+    variables: &mut Set<SymVariable<'db>>,
+) {
+    match place.kind(db) {
+        SymPlaceExprKind::Var(var) => {
+            variables.insert(*var);
+        }
+        SymPlaceExprKind::Field(owner, _) => place_expr_variables(db, *owner, variables),
+        SymPlaceExprKind::Index(owner, index) => {
+            place_expr_variables(db, *owner, variables);
+            variables.insert(*index);
+        }
+        SymPlaceExprKind::Error(_) => {}
     }
 }
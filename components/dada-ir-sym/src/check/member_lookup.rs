@@ -3,6 +3,7 @@ use crate::ir::{
     classes::{SymAggregate, SymClassMember, SymField},
     functions::SymFunction,
     types::{SymGenericTerm, SymPerm, SymTy, SymTyName},
+    variables::SymVariable,
 };
 use dada_ir_ast::{
     ast::{Identifier, SpannedIdentifier},
@@ -12,8 +13,8 @@ use dada_ir_ast::{
 use dada_util::{debug, debug_heading};
 
 use crate::{
-    check::env::Env,
     check::exprs::{ExprResult, ExprResultKind},
+    check::{env::Env, scope_tree::ScopeTreeNode},
     ir::exprs::{SymPlaceExpr, SymPlaceExprKind},
     prelude::CheckedFieldTy,
 };
@@ -40,7 +41,7 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
         let (lower_bound, owner_perm) = non_infer_lower_bound(self.env, owner_ty).await;
 
         // The owner will be some supertype of `ty`.
-        match self.search_lower_bound_for_member(lower_bound, id.id) {
+        match self.search_lower_bound_for_member(lower_bound, id) {
             Ok(Some(member)) => self.confirm_member(owner, owner_perm, member, id),
             Ok(None) => {
                 // If there is no member, then since the owner must be a supertype of `ty`,
@@ -85,7 +86,7 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
                 );
                 ExprResult::from_place_expr(db, place_expr, temporaries)
             }
-            SearchResult::Method { owner: _, method } => {
+            SearchResult::Method { method } => {
                 let mut temporaries = vec![];
                 let owner = owner.into_expr(self.env, &mut temporaries);
                 ExprResult {
@@ -141,7 +142,7 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
     fn search_lower_bound_for_member(
         &mut self,
         lower_bound: RedTy<'db>,
-        id: Identifier<'db>,
+        id: SpannedIdentifier<'db>,
     ) -> Errors<Option<SearchResult<'db>>> {
         debug_heading!("search_lower_bound_for_member", lower_bound, id);
         match lower_bound {
@@ -161,7 +162,7 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
             RedTy::Error(reported) => Err(reported),
             RedTy::Never => Ok(None),
             RedTy::Infer(_) => panic!("did not expect inference variable"),
-            RedTy::Var(_) => Ok(None),
+            RedTy::Var(var) => self.search_trait_bounds_for_member(var, id),
             RedTy::Perm => panic!("did not expect permission red-ty"),
         }
     }
@@ -170,9 +171,10 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
         &mut self,
         owner: SymAggregate<'db>,
         generics: &[SymGenericTerm<'db>],
-        id: Identifier<'db>,
+        spanned_id: SpannedIdentifier<'db>,
     ) -> Errors<Option<SearchResult<'db>>> {
         let db = self.env.db();
+        let id = spanned_id.id;
         debug_heading!("search_class_for_member", id, owner);
 
         for &member in owner.members(db) {
@@ -193,7 +195,7 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
                 SymClassMember::SymFunction(method) => {
                     if method.name(db) == id {
                         debug!("found method", method);
-                        return Ok(Some(SearchResult::Method { owner, method }));
+                        return Ok(Some(SearchResult::Method { method }));
                     } else {
                         debug!("found method with wrong name", method.name(db));
                     }
@@ -201,7 +203,94 @@ impl<'member, 'db> MemberLookup<'member, 'db> {
             }
         }
 
-        Ok(None)
+        self.search_impls_for_member(owner, spanned_id)
+    }
+
+    /// Search the impls for `owner` for a method named `id`.
+    /// We consider the impls in the module that declares `owner` and in the current module.
+    fn search_impls_for_member(
+        &mut self,
+        owner: SymAggregate<'db>,
+        SpannedIdentifier { span: id_span, id }: SpannedIdentifier<'db>,
+    ) -> Errors<Option<SearchResult<'db>>> {
+        let db = self.env.db();
+        let owner_module = owner.module(db);
+        let current_module = self.env.scope.module().filter(|&m| m != owner_module);
+        let methods: Vec<SymFunction<'db>> = std::iter::once(owner_module)
+            .chain(current_module)
+            .flat_map(|m| m.impls_for(db, owner))
+            .filter_map(|i| i.method_named(db, id))
+            .collect();
+
+        match &methods[..] {
+            [] => Ok(None),
+            &[method] => {
+                debug!("found impl method", method);
+                Ok(Some(SearchResult::Method { method }))
+            }
+            _ => {
+                let mut diagnostic = Diagnostic::error(
+                    db,
+                    id_span,
+                    format!("multiple methods named `{id}` found"),
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    id_span,
+                    format!(
+                        "`{owner_name}` implements several traits with a method named `{id}`",
+                        owner_name = owner.name(db)
+                    ),
+                );
+                for method in &methods {
+                    diagnostic = diagnostic.label(
+                        db,
+                        Level::Info,
+                        method.name_span(db),
+                        "one candidate is here",
+                    );
+                }
+                Err(diagnostic.report(db))
+            }
+        }
+    }
+
+    /// Search the traits that the generic type `var` is declared to implement
+    /// for a method named `id`.
+    fn search_trait_bounds_for_member(
+        &mut self,
+        var: SymVariable<'db>,
+        SpannedIdentifier { span: id_span, id }: SpannedIdentifier<'db>,
+    ) -> Errors<Option<SearchResult<'db>>> {
+        let db = self.env.db();
+        let Some((sym_trait, method)) = self
+            .env
+            .trait_bounds(var)
+            .find_map(|b| Some((b.trait_(db), b.trait_(db).method_named(db, id)?)))
+        else {
+            return Ok(None);
+        };
+
+        if !sym_trait.associated_ty_vars(db).is_empty() {
+            return Err(Diagnostic::error(
+                db,
+                id_span,
+                "associated types of generic types are not yet supported",
+            )
+            .label(
+                db,
+                Level::Error,
+                id_span,
+                format!(
+                    "`{var}` implements `{sym_trait}`, but I cannot call `{id}` because `{sym_trait}` has associated types"
+                ),
+            )
+            .report(db));
+        }
+
+        debug!("found trait method", method);
+        Ok(Some(SearchResult::Method { method }))
     }
 }
 
@@ -213,7 +302,6 @@ enum SearchResult<'db> {
         field_ty: Binder<'db, SymTy<'db>>,
     },
     Method {
-        owner: SymAggregate<'db>,
         method: SymFunction<'db>,
     },
 }
@@ -224,7 +312,7 @@ enum SearchResult<'db> {
 /// # Returns
 ///
 /// A [`RedTy`][] that is a lower bound for `ty` and which is not an inference variable.
-async fn non_infer_lower_bound<'db>(
+pub(crate) async fn non_infer_lower_bound<'db>(
     env: &mut Env<'db>,
     ty: SymTy<'db>,
 ) -> (RedTy<'db>, SymPerm<'db>) {
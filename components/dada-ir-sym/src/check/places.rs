@@ -5,9 +5,10 @@ use crate::{
     check::env::Env,
     ir::{
         classes::SymField,
-        types::{SymGenericTerm, SymPerm, SymPlace, SymPlaceKind, SymTy},
+        types::{SymGenericTerm, SymPerm, SymPlace, SymPlaceKind, SymTy, SymTyName},
     },
     prelude::CheckedFieldTy,
+    well_known,
 };
 
 use super::{inference::Direction, red::RedTy, to_red::ToRedTy};
@@ -26,8 +27,10 @@ impl<'db> PlaceTy<'db> for SymPlace<'db> {
                 let (owner_red_ty, owner_perm) = owner_ty.to_red_ty(env);
                 field_ty(env, owner_place, owner_perm, owner_red_ty, sym_field)
             }
-            SymPlaceKind::Index(_sym_place) => {
-                todo!()
+            SymPlaceKind::Index(owner_place) => {
+                let owner_ty = owner_place.place_ty(env).await;
+                let (owner_red_ty, owner_perm) = owner_ty.to_red_ty(env);
+                index_ty(env, owner_perm, owner_red_ty)
             }
             SymPlaceKind::Error(reported) => SymTy::err(env.db(), reported),
             SymPlaceKind::Erased => panic!("cannot compute type of an erased place"),
@@ -70,3 +73,47 @@ fn field_ty<'db>(
         }
     }
 }
+
+fn index_ty<'db>(
+    env: &mut Env<'db>,
+    owner_perm: SymPerm<'db>,
+    owner_red_ty: RedTy<'db>,
+) -> SymTy<'db> {
+    let db = env.db();
+    match owner_red_ty {
+        RedTy::Error(reported) => SymTy::err(db, reported),
+
+        RedTy::Named(name, generics) => match element_ty(db, name, &generics) {
+            Some(element_ty) => owner_perm.apply_to(db, element_ty),
+            None => unreachable!("cannot index into a {name:?}"),
+        },
+
+        RedTy::Infer(infer) => {
+            // As with fields, the index was checked against a valid inference bound
+            let (infer_red_ty, _) = env
+                .red_bound(infer, Direction::FromBelow)
+                .peek_ty()
+                .unwrap();
+            index_ty(env, owner_perm, infer_red_ty)
+        }
+
+        RedTy::Perm | RedTy::Var(_) | RedTy::Never => {
+            unreachable!("cannot index into a {owner_red_ty:?}")
+        }
+    }
+}
+
+/// If values of the type named `name` (applied to `generics`) can be indexed,
+/// returns the type of their elements. Only the `Vec` class from libdada can be indexed.
+pub(super) fn element_ty<'db>(
+    db: &'db dyn crate::Db,
+    name: SymTyName<'db>,
+    generics: &[SymGenericTerm<'db>],
+) -> Option<SymTy<'db>> {
+    match name {
+        SymTyName::Aggregate(aggr) if well_known::vec_class(db) == Ok(aggr) => {
+            Some(generics[0].assert_type(db))
+        }
+        _ => None,
+    }
+}
@@ -115,7 +115,15 @@ pub(crate) async fn perm_is_provably_lent<'db>(
         SymPermKind::Infer(infer) => {
             infer_is_provably(env, SymPerm::my(db), infer, Predicate::Lent).await
         }
-        SymPermKind::Or(_, _) => todo!(),
+        // A value with permission `lhs | rhs` may have either permission,
+        // so both must be lent.
+        SymPermKind::Or(lhs, rhs) => {
+            env.both(
+                async |env| perm_is_provably_lent(env, lhs).await,
+                async |env| perm_is_provably_lent(env, rhs).await,
+            )
+            .await
+        }
     }
 }
 
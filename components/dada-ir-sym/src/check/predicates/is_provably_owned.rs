@@ -96,7 +96,15 @@ pub(crate) async fn perm_is_provably_owned<'db>(
             infer_is_provably(env, SymPerm::my(db), infer, Predicate::Owned).await
         }
 
-        SymPermKind::Or(_, _) => todo!(),
+        // A value with permission `lhs | rhs` may have either permission,
+        // so both must be owned.
+        SymPermKind::Or(lhs, rhs) => {
+            env.both(
+                async |env| perm_is_provably_owned(env, lhs).await,
+                async |env| perm_is_provably_owned(env, rhs).await,
+            )
+            .await
+        }
     }
 }
 
@@ -81,7 +81,15 @@ pub(crate) async fn perm_is_provably_shared<'db>(
         SymPermKind::Infer(infer) => {
             infer_is_provably(env, SymPerm::my(db), infer, Predicate::Shared).await
         }
-        SymPermKind::Or(_, _) => todo!(),
+        // A value with permission `lhs | rhs` may have either permission,
+        // so both must be shared.
+        SymPermKind::Or(lhs, rhs) => {
+            env.both(
+                async |env| perm_is_provably_shared(env, lhs).await,
+                async |env| perm_is_provably_shared(env, rhs).await,
+            )
+            .await
+        }
     }
 }
 
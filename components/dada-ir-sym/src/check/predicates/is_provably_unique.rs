@@ -96,7 +96,15 @@ pub(crate) async fn perm_is_provably_unique<'db>(
             infer_is_provably(env, SymPerm::my(db), infer, Predicate::Unique).await
         }
 
-        SymPermKind::Or(_, _) => todo!(),
+        // A value with permission `lhs | rhs` may have either permission,
+        // so both must be unique.
+        SymPermKind::Or(lhs, rhs) => {
+            env.both(
+                async |env| perm_is_provably_unique(env, lhs).await,
+                async |env| perm_is_provably_unique(env, rhs).await,
+            )
+            .await
+        }
     }
 }
 
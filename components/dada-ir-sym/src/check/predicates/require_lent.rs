@@ -177,7 +177,14 @@ async fn require_perm_is_lent<'db>(
             SymPermKind::Infer(infer) => {
                 require_infer_is(env, SymPerm::my(db), infer, Predicate::Lent, or_else).await
             }
-            SymPermKind::Or(_, _) => todo!(),
+            // Or: the value may have either permission, so both must be lent
+            SymPermKind::Or(lhs, rhs) => {
+                env.require_both(
+                    async |env| require_perm_is_lent(env, lhs, or_else).await,
+                    async |env| require_perm_is_lent(env, rhs, or_else).await,
+                )
+                .await
+            }
         }
     })
     .await
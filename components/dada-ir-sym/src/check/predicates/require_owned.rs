@@ -169,7 +169,14 @@ async fn require_perm_is_owned<'db>(
                 require_infer_is(env, SymPerm::my(db), infer, Predicate::Owned, or_else).await
             }
 
-            SymPermKind::Or(_, _) => todo!(),
+            // Or: the value may have either permission, so both must be owned
+            SymPermKind::Or(lhs, rhs) => {
+                env.require_both(
+                    async |env| require_perm_is_owned(env, lhs, or_else).await,
+                    async |env| require_perm_is_owned(env, rhs, or_else).await,
+                )
+                .await
+            }
         }
     })
     .await
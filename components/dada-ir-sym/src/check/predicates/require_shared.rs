@@ -172,7 +172,14 @@ pub(super) async fn require_perm_is_shared<'db>(
                 require_infer_is(env, SymPerm::my(db), infer, Predicate::Shared, or_else).await
             }
 
-            SymPermKind::Or(_, _) => todo!(),
+            // Or: the value may have either permission, so both must be shared
+            SymPermKind::Or(lhs, rhs) => {
+                env.require_both(
+                    async |env| require_perm_is_shared(env, lhs, or_else).await,
+                    async |env| require_perm_is_shared(env, rhs, or_else).await,
+                )
+                .await
+            }
         }
     })
     .await
@@ -0,0 +1,143 @@
+use dada_ir_ast::{diagnostic::Errors, span::Spanned};
+use dada_util::boxed_async_fn;
+
+use crate::{
+    check::{
+        env::Env,
+        inference::Direction,
+        live_places::LivePlaces,
+        red::RedTy,
+        report::{Because, OrElse},
+        subtype::terms::require_sub_terms,
+        to_red::ToRedTy,
+    },
+    ir::{
+        traits::SymTraitRef,
+        types::{SymGenericTerm, SymTy, SymTyName},
+    },
+    prelude::CheckedImplHeader,
+};
+
+pub(crate) async fn require_term_implements<'db>(
+    env: &mut Env<'db>,
+    term: SymGenericTerm<'db>,
+    trait_ref: SymTraitRef<'db>,
+    or_else: &dyn OrElse<'db>,
+) -> Errors<()> {
+    match term {
+        SymGenericTerm::Type(sym_ty) => {
+            require_ty_implements(env, sym_ty, trait_ref, or_else).await
+        }
+        SymGenericTerm::Perm(perm) => panic!("unexpected perm term: {perm:?}"),
+        SymGenericTerm::Place(place) => panic!("unexpected place term: {place:?}"),
+        SymGenericTerm::Error(reported) => Err(reported),
+    }
+}
+
+/// Requires that `ty` implements the trait `trait_ref`.
+/// Permissions on `ty` are ignored, since impls are always for a class or struct.
+#[boxed_async_fn]
+async fn require_ty_implements<'db>(
+    env: &mut Env<'db>,
+    ty: SymTy<'db>,
+    trait_ref: SymTraitRef<'db>,
+    or_else: &dyn OrElse<'db>,
+) -> Errors<()> {
+    env.indent("require_ty_implements", &[&ty, &trait_ref], async |env| {
+        let db = env.db();
+        let sym_trait = trait_ref.trait_(db);
+
+        // Wait until we know enough about the type to find an impl.
+        let (red_ty, _perm) = ty.to_red_ty(env);
+        let red_ty = if let RedTy::Infer(infer) = red_ty {
+            match env.red_bound(infer, Direction::FromBelow).ty().await {
+                Some((bound_red_ty, _)) => bound_red_ty,
+                None => return Err(or_else.report(env, Because::UnconstrainedInfer(infer))),
+            }
+        } else {
+            red_ty
+        };
+
+        match red_ty {
+            RedTy::Error(reported) => Err(reported),
+
+            RedTy::Never => Ok(()),
+
+            RedTy::Named(SymTyName::Aggregate(aggr), generics) => {
+                let Some(sym_impl) = sym_trait.impl_for(db, aggr) else {
+                    return Err(or_else.report(env, Because::NoImpl(ty, trait_ref)));
+                };
+
+                // Instantiate the impl's generics with inference variables and
+                // relate the impl header to the type and trait reference we were given.
+                let header = sym_impl.checked_header(db)?;
+                let substitution =
+                    env.existential_substitution(sym_impl.span(db), &header.variables);
+                let header = header.substitute(db, &substitution);
+
+                let mut pairs: Vec<(SymGenericTerm<'db>, SymGenericTerm<'db>)> = vec![(
+                    SymTy::named(db, aggr.into(), generics).into(),
+                    header.self_ty.into(),
+                )];
+                pairs.extend(
+                    trait_ref
+                        .args(db)
+                        .iter()
+                        .copied()
+                        .zip(header.trait_ref.args(db).iter().copied()),
+                );
+                pairs.extend(
+                    trait_ref
+                        .associated_tys(db)
+                        .iter()
+                        .zip(&header.associated_tys)
+                        .map(|(&a, &b)| (a.into(), b.into())),
+                );
+                require_equal_pairs(env, pairs, or_else).await
+            }
+
+            RedTy::Var(var) => {
+                let bound = env.trait_bounds(var).find(|b| b.trait_(db) == sym_trait);
+                let Some(bound) = bound else {
+                    return Err(or_else.report(env, Because::NoImpl(ty, trait_ref)));
+                };
+
+                let mut pairs: Vec<(SymGenericTerm<'db>, SymGenericTerm<'db>)> = trait_ref
+                    .args(db)
+                    .iter()
+                    .copied()
+                    .zip(bound.args(db).iter().copied())
+                    .collect();
+                pairs.extend(
+                    trait_ref
+                        .associated_tys(db)
+                        .iter()
+                        .zip(bound.associated_tys(db))
+                        .map(|(&a, &b)| (a.into(), b.into())),
+                );
+                require_equal_pairs(env, pairs, or_else).await
+            }
+
+            RedTy::Named(..) | RedTy::Perm | RedTy::Infer(_) => {
+                Err(or_else.report(env, Because::NoImpl(ty, trait_ref)))
+            }
+        }
+    })
+    .await
+}
+
+/// Requires that the terms in each pair are equal.
+async fn require_equal_pairs<'db>(
+    env: &mut Env<'db>,
+    pairs: Vec<(SymGenericTerm<'db>, SymGenericTerm<'db>)>,
+    or_else: &dyn OrElse<'db>,
+) -> Errors<()> {
+    env.require_for_all(pairs, async |env, (a, b)| {
+        env.require_both(
+            async |env| require_sub_terms(env, LivePlaces::fixme(), a, b, or_else).await,
+            async |env| require_sub_terms(env, LivePlaces::fixme(), b, a, or_else).await,
+        )
+        .await
+    })
+    .await
+}
@@ -144,7 +144,14 @@ async fn require_perm_is_unique<'db>(
                 require_infer_is(env, SymPerm::my(db), infer, Predicate::Unique, or_else).await
             }
 
-            SymPermKind::Or(_, _) => todo!(),
+            // Or: the value may have either permission, so both must be unique
+            SymPermKind::Or(lhs, rhs) => {
+                env.require_both(
+                    async |env| require_perm_is_unique(env, lhs, or_else).await,
+                    async |env| require_perm_is_unique(env, rhs, or_else).await,
+                )
+                .await
+            }
         }
     })
     .await
@@ -97,6 +97,43 @@ impl<'db> RedChain<'db> {
     }
 }
 
+impl std::fmt::Display for RedPerm<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| {
+            let db: &dyn crate::Db = db.as_view();
+            for (i, chain) in self.chains(db).iter().enumerate() {
+                if i > 0 {
+                    write!(f, " | ")?;
+                }
+                write!(f, "{chain}")?;
+            }
+            Ok(())
+        })
+        .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
+/// Displays the chain in surface syntax, e.g., `ref[x] mut[y]`; the empty chain is `my`.
+impl std::fmt::Display for RedChain<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| {
+            let db: &dyn crate::Db = db.as_view();
+            let links = self.links(db);
+            if links.is_empty() {
+                return write!(f, "my");
+            }
+            for (i, link) in links.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{link}")?;
+            }
+            Ok(())
+        })
+        .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
 impl<'db> Err<'db> for RedChain<'db> {
     fn err(db: &'db dyn dada_ir_ast::Db, reported: Reported) -> Self {
         RedChain::new(db, vec![RedLink::err(db, reported)])
@@ -197,6 +234,18 @@ impl<'db> RedLink<'db> {
     }
 }
 
+impl std::fmt::Display for RedLink<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedLink::Our => write!(f, "our"),
+            RedLink::Ref(_, place) => write!(f, "ref[{place}]"),
+            RedLink::Mut(_, place) => write!(f, "mut[{place}]"),
+            RedLink::Var(v) => write!(f, "{v}"),
+            RedLink::Err(_) => write!(f, "<error>"),
+        }
+    }
+}
+
 impl<'db> Err<'db> for RedLink<'db> {
     fn err(_db: &'db dyn dada_ir_ast::Db, reported: Reported) -> Self {
         RedLink::Err(reported)
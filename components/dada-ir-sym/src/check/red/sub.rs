@@ -2,7 +2,7 @@ use dada_ir_ast::diagnostic::Errors;
 
 use crate::check::env::Env;
 
-use super::{RedChain, RedLink};
+use super::{Live, RedChain, RedLink};
 
 pub fn chain_sub_chain<'db>(
     env: &Env<'db>,
@@ -78,6 +78,18 @@ fn links_sub_links<'db>(
             var_l == var_u && links_sub_links(env, tail_l, tail_u)?
         },
 
+        // If `p` is dead, the lease `mut[p]` has ended, so `mut[p] mut[q]`
+        // can be treated as `mut[q]`.
+        ([RedLink::Mut(Live(false), _), tail_l @ ..], links_u) => {
+            matches!(tail_l.first(), Some(RedLink::Mut(..)))
+            && links_sub_links(env, tail_l, links_u)?
+        },
+
+        // Similarly, if `p` is dead, `ref[p] mut[q]` can be treated as `ref[q]`.
+        ([RedLink::Ref(Live(false), _), RedLink::Mut(live_l, place_l), tail_l @ ..], links_u) => {
+            let links_l = [&[RedLink::Ref(*live_l, *place_l)][..], tail_l].concat();
+            links_sub_links(env, &links_l, links_u)?
+        },
 
     }
 }
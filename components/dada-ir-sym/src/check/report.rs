@@ -1,25 +1,32 @@
 use std::{panic::Location, sync::Arc};
 
 use dada_ir_ast::{
-    ast::SpannedBinaryOp,
-    diagnostic::{Diagnostic, Level, Reported},
-    span::Span,
+    ast::{AstFunctionInput, PermissionOp, SpannedBinaryOp},
+    diagnostic::{Diagnostic, DiagnosticCode, Level, Reported},
+    span::{Span, Spanned},
 };
 use serde::Serialize;
 
 use crate::{
     check::{debug::export, env::Env, predicates::Predicate},
     ir::{
-        exprs::{SymExpr, SymPlaceExpr},
+        exprs::{SymExpr, SymExprKind, SymLiteral, SymPlaceExpr},
         generics::SymWhereClause,
         indices::InferVarIndex,
-        types::{SymGenericTerm, SymPerm, SymPlace, SymTy, SymTyName},
+        populate::{self_arg_requires_default_perm, variable_decl_requires_default_perm},
+        primitive::{SymPrimitive, SymPrimitiveKind},
+        traits::SymTraitRef,
+        types::{
+            AnonymousPermSymbol, SymGenericTerm, SymPerm, SymPermKind, SymPlace, SymTy, SymTyKind,
+            SymTyName,
+        },
         variables::SymVariable,
     },
 };
 
 use super::{
     inference::{Direction, InferVarKind},
+    literals::integral_max,
     red::{RedPerm, RedTy},
     to_red::RedTyExt,
 };
@@ -200,6 +207,9 @@ pub enum Because<'db> {
 
     /// The given inference variable needs more constraints
     UnconstrainedInfer(InferVarIndex),
+
+    /// The type does not implement the trait
+    NoImpl(SymTy<'db>, SymTraitRef<'db>),
 }
 
 impl<'db> Because<'db> {
@@ -217,12 +227,17 @@ impl<'db> Because<'db> {
         let db = env.db();
         match self {
             Because::JustSo => None,
-            Because::VarNotDeclaredToBe(v, predicate) => Some(Diagnostic::info(
-                db,
-                span,
-                format!(
-                    "to conclude that `{v}` is `{predicate}`, I would need you to add a declaration"
+            Because::VarNotDeclaredToBe(v, predicate) => Some(suggest_parameter_perm(
+                env,
+                Diagnostic::info(
+                    db,
+                    span,
+                    format!(
+                        "to conclude that `{v}` is `{predicate}`, I would need you to add a declaration"
+                    ),
                 ),
+                *v,
+                *predicate,
             )),
             Because::NeverIsNotCopy => Some(Diagnostic::info(
                 db,
@@ -272,7 +287,7 @@ impl<'db> Because<'db> {
                                         Direction::FromBelow => "assignable from",
                                         Direction::FromAbove => "assignable to",
                                     },
-                                    bound = format!("{red_perm:?}"), // FIXME
+                                    bound = red_perm,
                                 ),
                             )
                             .child(or_else_diagnostic),
@@ -295,10 +310,22 @@ impl<'db> Because<'db> {
                 env.infer_var_span(*infer),
                 "this error might well be bogus, I just can't infer the type here".to_string(),
             )),
-            Because::NoWhereClause(var, predicate) => Some(Diagnostic::info(
+            Because::NoImpl(ty, trait_ref) => Some(Diagnostic::info(
                 db,
                 span,
-                format!("the variable `{var}` needs a where-clause to be considered `{predicate}`"),
+                format!("`{ty}` does not implement `{trait_ref}`"),
+            )),
+            Because::NoWhereClause(var, predicate) => Some(suggest_parameter_perm(
+                env,
+                Diagnostic::info(
+                    db,
+                    span,
+                    format!(
+                        "the variable `{var}` needs a where-clause to be considered `{predicate}`"
+                    ),
+                ),
+                *var,
+                *predicate,
             )),
             Because::StructsAreNotLent(s) => Some(Diagnostic::info(
                 db,
@@ -341,6 +368,10 @@ pub struct BadSubtermError<'db> {
     span: Span<'db>,
     lower: SymGenericTerm<'db>,
     upper: SymGenericTerm<'db>,
+
+    /// The value whose type is `lower`, if known; used to suggest fixes.
+    value: Option<SymExpr<'db>>,
+
     compiler_location: &'static Location<'static>,
 }
 
@@ -355,6 +386,19 @@ impl<'db> BadSubtermError<'db> {
             span,
             lower: lower.into(),
             upper: upper.into(),
+            value: None,
+            compiler_location: Location::caller(),
+        }
+    }
+
+    /// Error for when `value` is not assignable to `upper`.
+    #[track_caller]
+    pub fn for_value(db: &'db dyn crate::Db, value: SymExpr<'db>, upper: SymTy<'db>) -> Self {
+        Self {
+            span: value.span(db),
+            lower: value.ty(db).into(),
+            upper: upper.into(),
+            value: Some(value),
             compiler_location: Location::caller(),
         }
     }
@@ -367,17 +411,23 @@ impl<'db> OrElse<'db> for BadSubtermError<'db> {
             span,
             lower,
             upper,
+            value,
             compiler_location: _,
         } = *self;
-        because.annotate_diagnostic(
-            env,
-            Diagnostic::error(db, span, "subtype expected".to_string()).label(
+        let mut diagnostic = Diagnostic::error(db, span, "subtype expected".to_string())
+            .code(DiagnosticCode::SubtypeExpected)
+            .label(
                 db,
                 Level::Error,
                 span,
                 format!("expected `{upper}`, found `{lower}`"),
-            ),
-        )
+            );
+        if let Some(value) = value
+            && let SymGenericTerm::Type(upper) = upper
+        {
+            diagnostic = suggest_permission_op(env, diagnostic, value, upper);
+        }
+        because.annotate_diagnostic(env, diagnostic)
     }
 
     fn to_arc(&self) -> ArcOrElse<'db> {
@@ -425,12 +475,8 @@ impl<'db> OrElse<'db> for WhereClauseError<'db> {
                 span,
                 "where clause on function not satisfied".to_string(),
             )
-            .label(
-                db,
-                Level::Error,
-                span,
-                format!("expected `{where_clause:?}`"),
-            ),
+            .code(DiagnosticCode::WhereClauseNotSatisfied)
+            .label(db, Level::Error, span, format!("expected `{where_clause}`")),
         )
     }
 
@@ -474,33 +520,33 @@ impl<'db> OrElse<'db> for InvalidInitializerType<'db> {
     fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
         let db = env.db();
         let initializer_ty = self.initializer.ty(db);
-        because.annotate_diagnostic(
-            env,
-            Diagnostic::error(
-                db,
-                self.initializer.span(db),
-                format!(
-                    "variable `{v}` initialized with value of wrong type",
-                    v = self.variable
-                ),
-            )
-            .label(
-                db,
-                Level::Error,
-                self.initializer.span(db),
-                format!("initializer has type `{initializer_ty}`"),
-            )
-            .label(
-                db,
-                Level::Info,
-                self.variable_span,
-                format!(
-                    "`{v}` has type `{variable_ty}`",
-                    v = self.variable,
-                    variable_ty = self.variable_ty
-                ),
+        let diagnostic = Diagnostic::error(
+            db,
+            self.initializer.span(db),
+            format!(
+                "variable `{v}` initialized with value of wrong type",
+                v = self.variable
             ),
         )
+        .code(DiagnosticCode::InitializerWrongType)
+        .label(
+            db,
+            Level::Error,
+            self.initializer.span(db),
+            format!("initializer has type `{initializer_ty}`"),
+        )
+        .label(
+            db,
+            Level::Info,
+            self.variable_span,
+            format!(
+                "`{v}` has type `{variable_ty}`",
+                v = self.variable,
+                variable_ty = self.variable_ty
+            ),
+        );
+        let diagnostic = suggest_permission_op(env, diagnostic, self.initializer, self.variable_ty);
+        because.annotate_diagnostic(env, diagnostic)
     }
 
     fn to_arc(&self) -> ArcOrElse<'db> {
@@ -535,26 +581,26 @@ impl<'db> OrElse<'db> for InvalidAssignmentType<'db> {
         let db = env.db();
         let lhs_ty = self.lhs.ty(db);
         let rhs_ty = self.rhs.ty(db);
-        because.annotate_diagnostic(
-            env,
-            Diagnostic::error(
-                db,
-                self.rhs.span(db),
-                "wrong type in assignment".to_string(),
-            )
-            .label(
-                db,
-                Level::Error,
-                self.rhs.span(db),
-                format!("this expression has type `{rhs_ty}`"),
-            )
-            .label(
-                db,
-                Level::Info,
-                self.lhs.span(db),
-                format!("I expected something assignable to this, which has type `{lhs_ty}`",),
-            ),
+        let diagnostic = Diagnostic::error(
+            db,
+            self.rhs.span(db),
+            "wrong type in assignment".to_string(),
+        )
+        .code(DiagnosticCode::AssignmentWrongType)
+        .label(
+            db,
+            Level::Error,
+            self.rhs.span(db),
+            format!("this expression has type `{rhs_ty}`"),
         )
+        .label(
+            db,
+            Level::Info,
+            self.lhs.span(db),
+            format!("I expected something assignable to this, which has type `{lhs_ty}`",),
+        );
+        let diagnostic = suggest_permission_op(env, diagnostic, self.rhs, lhs_ty);
+        because.annotate_diagnostic(env, diagnostic)
     }
 
     fn to_arc(&self) -> ArcOrElse<'db> {
@@ -588,9 +634,9 @@ impl<'db> OrElse<'db> for InvalidReturnValue<'db> {
     fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
         let db = env.db();
         let value_ty = self.value.ty(db);
-        because.annotate_diagnostic(
-            env,
+        let diagnostic =
             Diagnostic::error(db, self.value.span(db), "invalid return value".to_string())
+                .code(DiagnosticCode::InvalidReturnValue)
                 .label(
                     db,
                     Level::Error,
@@ -608,8 +654,9 @@ impl<'db> OrElse<'db> for InvalidReturnValue<'db> {
                         "the return type is declared to be `{return_ty}`",
                         return_ty = self.return_ty,
                     ),
-                ),
-        )
+                );
+        let diagnostic = suggest_permission_op(env, diagnostic, self.value, self.return_ty);
+        because.annotate_diagnostic(env, diagnostic)
     }
 
     fn to_arc(&self) -> ArcOrElse<'db> {
@@ -654,6 +701,7 @@ impl<'db> OrElse<'db> for AwaitNonFuture<'db> {
                 await_span,
                 "`await` can only be used on futures".to_string(),
             )
+            .code(DiagnosticCode::AwaitNonFuture)
             .label(
                 db,
                 Level::Error,
@@ -707,6 +755,7 @@ impl<'db> OrElse<'db> for BooleanTypeRequired<'db> {
                 self.expr.span(db),
                 "boolean expression required".to_string(),
             )
+            .code(DiagnosticCode::BooleanExpected)
             .label(
                 db,
                 Level::Error,
@@ -728,6 +777,54 @@ impl<'db> OrElse<'db> for BooleanTypeRequired<'db> {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct IndexTypeRequired<'db> {
+    expr: SymExpr<'db>,
+    compiler_location: &'static Location<'static>,
+}
+
+impl<'db> IndexTypeRequired<'db> {
+    #[track_caller]
+    pub fn new(expr: SymExpr<'db>) -> Self {
+        Self {
+            expr,
+            compiler_location: Location::caller(),
+        }
+    }
+}
+
+impl<'db> OrElse<'db> for IndexTypeRequired<'db> {
+    fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
+        let db = env.db();
+        because.annotate_diagnostic(
+            env,
+            Diagnostic::error(
+                db,
+                self.expr.span(db),
+                "index must have type `u32`".to_string(),
+            )
+            .code(DiagnosticCode::IndexTypeExpected)
+            .label(
+                db,
+                Level::Error,
+                self.expr.span(db),
+                format!(
+                    "I expected this index to have type `u32`, but it has the type `{}`",
+                    self.expr.ty(db)
+                ),
+            ),
+        )
+    }
+
+    fn to_arc(&self) -> ArcOrElse<'db> {
+        Arc::new(*self).into()
+    }
+
+    fn compiler_location(&self) -> &'static Location<'static> {
+        self.compiler_location
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct NumericTypeExpected<'db> {
     expr: SymExpr<'db>,
@@ -751,12 +848,14 @@ impl<'db> OrElse<'db> for NumericTypeExpected<'db> {
         let db = env.db();
         because.annotate_diagnostic(
             env,
-            Diagnostic::error(db, self.expr.span(db), "numeric type expected").label(
-                db,
-                Level::Error,
-                self.expr.span(db),
-                format!("I expected a numeric type but I found `{}`", self.ty),
-            ),
+            Diagnostic::error(db, self.expr.span(db), "numeric type expected")
+                .code(DiagnosticCode::NumericTypeExpected)
+                .label(
+                    db,
+                    Level::Error,
+                    self.expr.span(db),
+                    format!("I expected a numeric type but I found `{}`", self.ty),
+                ),
         )
     }
 
@@ -769,6 +868,85 @@ impl<'db> OrElse<'db> for NumericTypeExpected<'db> {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct LiteralOutOfRange<'db> {
+    expr: SymExpr<'db>,
+    literal: SymLiteral,
+    primitive: SymPrimitive<'db>,
+    compiler_location: &'static Location<'static>,
+}
+
+impl<'db> LiteralOutOfRange<'db> {
+    #[track_caller]
+    pub fn new(expr: SymExpr<'db>, literal: SymLiteral, primitive: SymPrimitive<'db>) -> Self {
+        Self {
+            expr,
+            literal,
+            primitive,
+            compiler_location: Location::caller(),
+        }
+    }
+}
+
+impl<'db> OrElse<'db> for LiteralOutOfRange<'db> {
+    fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
+        let db = env.db();
+        let Self {
+            expr,
+            literal,
+            primitive,
+            compiler_location: _,
+        } = *self;
+        let span = expr.span(db);
+        let kind = primitive.kind(db);
+
+        let diagnostic = match (literal, integral_max(kind)) {
+            (SymLiteral::Float { .. }, Some(_)) => {
+                Diagnostic::error(db, span, format!("float literal used as `{primitive}`")).label(
+                    db,
+                    Level::Error,
+                    span,
+                    format!("I expected an integer of type `{primitive}`, but this literal has a fractional part or exponent"),
+                )
+            }
+            (_, Some(max)) => {
+                let range = match kind {
+                    SymPrimitiveKind::Int { .. } | SymPrimitiveKind::Isize => {
+                        format!("-{} to {max}", u128::from(max) + 1)
+                    }
+                    _ => format!("0 to {max}"),
+                };
+                Diagnostic::error(db, span, format!("literal out of range for `{primitive}`"))
+                    .label(
+                        db,
+                        Level::Error,
+                        span,
+                        format!("`{primitive}` values range from {range}"),
+                    )
+            }
+            (_, None) => {
+                Diagnostic::error(db, span, format!("literal out of range for `{primitive}`"))
+                    .label(
+                        db,
+                        Level::Error,
+                        span,
+                        format!("this value is too large to be represented as `{primitive}`"),
+                    )
+            }
+        };
+
+        because.annotate_diagnostic(env, diagnostic.code(DiagnosticCode::LiteralOutOfRange))
+    }
+
+    fn to_arc(&self) -> ArcOrElse<'db> {
+        Arc::new(*self).into()
+    }
+
+    fn compiler_location(&self) -> &'static Location<'static> {
+        self.compiler_location
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct OperatorRequiresNumericType<'db> {
     op: SpannedBinaryOp<'db>,
@@ -799,6 +977,7 @@ impl<'db> OrElse<'db> for OperatorRequiresNumericType<'db> {
         because.annotate_diagnostic(
             env,
             Diagnostic::error(db, expr.span(db), "numeric type expected")
+                .code(DiagnosticCode::NumericTypeExpected)
                 .label(
                     db,
                     Level::Error,
@@ -826,6 +1005,122 @@ impl<'db> OrElse<'db> for OperatorRequiresNumericType<'db> {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct OperatorRequiresIntegerType<'db> {
+    op: SpannedBinaryOp<'db>,
+    expr: SymExpr<'db>,
+    compiler_location: &'static Location<'static>,
+}
+
+impl<'db> OperatorRequiresIntegerType<'db> {
+    #[track_caller]
+    pub fn new(op: SpannedBinaryOp<'db>, expr: SymExpr<'db>) -> Self {
+        Self {
+            op,
+            expr,
+            compiler_location: Location::caller(),
+        }
+    }
+}
+
+impl<'db> OrElse<'db> for OperatorRequiresIntegerType<'db> {
+    fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
+        let db = env.db();
+        let Self {
+            op: SpannedBinaryOp { span: op_span, op },
+            expr,
+            compiler_location: _,
+        } = *self;
+
+        because.annotate_diagnostic(
+            env,
+            Diagnostic::error(db, expr.span(db), "integer type expected")
+                .code(DiagnosticCode::IntegerTypeExpected)
+                .label(
+                    db,
+                    Level::Error,
+                    expr.span(db),
+                    format!(
+                        "I expected this to have an integer type but it had the type `{}`",
+                        expr.ty(db)
+                    ),
+                )
+                .label(
+                    db,
+                    Level::Info,
+                    op_span,
+                    format!("the operator `{op}` requires integer arguments"),
+                ),
+        )
+    }
+
+    fn to_arc(&self) -> ArcOrElse<'db> {
+        Arc::new(*self).into()
+    }
+
+    fn compiler_location(&self) -> &'static Location<'static> {
+        self.compiler_location
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct NegationRequiresSignedType<'db> {
+    op_span: Span<'db>,
+    expr: SymExpr<'db>,
+    compiler_location: &'static Location<'static>,
+}
+
+impl<'db> NegationRequiresSignedType<'db> {
+    #[track_caller]
+    pub fn new(op_span: Span<'db>, expr: SymExpr<'db>) -> Self {
+        Self {
+            op_span,
+            expr,
+            compiler_location: Location::caller(),
+        }
+    }
+}
+
+impl<'db> OrElse<'db> for NegationRequiresSignedType<'db> {
+    fn or_else(&self, env: &mut Env<'db>, because: Because<'db>) -> Diagnostic {
+        let db = env.db();
+        let Self {
+            op_span,
+            expr,
+            compiler_location: _,
+        } = *self;
+
+        because.annotate_diagnostic(
+            env,
+            Diagnostic::error(db, expr.span(db), "signed type expected")
+                .code(DiagnosticCode::SignedTypeExpected)
+                .label(
+                    db,
+                    Level::Error,
+                    expr.span(db),
+                    format!(
+                        "I expected this to have a signed integer or float type but it had the type `{}`",
+                        expr.ty(db)
+                    ),
+                )
+                .label(
+                    db,
+                    Level::Info,
+                    op_span,
+                    "values of unsigned types cannot be negated",
+                ),
+        )
+    }
+
+    fn to_arc(&self) -> ArcOrElse<'db> {
+        Arc::new(*self).into()
+    }
+
+    fn compiler_location(&self) -> &'static Location<'static> {
+        self.compiler_location
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct OperatorArgumentsMustHaveSameType<'db> {
     op: SpannedBinaryOp<'db>,
@@ -859,6 +1154,7 @@ impl<'db> OrElse<'db> for OperatorArgumentsMustHaveSameType<'db> {
         because.annotate_diagnostic(
             env,
             Diagnostic::error(db, op_span, "same types expected")
+                .code(DiagnosticCode::SameTypesExpected)
                 .label(
                     db,
                     Level::Error,
@@ -921,12 +1217,14 @@ impl<'db> OrElse<'db> for InferenceFallback<'db> {
 
         because.annotate_diagnostic(
             env,
-            Diagnostic::error(db, span, "unconstrained inference variable").label(
-                db,
-                Level::Error,
-                span,
-                format!("I resolved this {kind} to {term}",),
-            ),
+            Diagnostic::error(db, span, "unconstrained inference variable")
+                .code(DiagnosticCode::UnconstrainedInference)
+                .label(
+                    db,
+                    Level::Error,
+                    span,
+                    format!("I resolved this {kind} to {term}",),
+                ),
         )
     }
 
@@ -938,3 +1236,104 @@ impl<'db> OrElse<'db> for InferenceFallback<'db> {
         self.compiler_location
     }
 }
+
+/// If `value` is a place (like `x` or `x.ref`) and `expected_ty` asks for
+/// a particular permission, suggest the permission operation that produces it
+/// (e.g., `x.mut` when a `mut` value is expected).
+fn suggest_permission_op<'db>(
+    env: &Env<'db>,
+    diagnostic: Diagnostic,
+    value: SymExpr<'db>,
+    expected_ty: SymTy<'db>,
+) -> Diagnostic {
+    let db = env.db();
+    let &SymExprKind::PermissionOp(op, place_expr) = value.kind(db) else {
+        return diagnostic;
+    };
+    let Some(expected_op) = permission_op_producing(env, expected_ty) else {
+        return diagnostic;
+    };
+    if op == expected_op {
+        return diagnostic;
+    }
+
+    // Replace whatever follows the place, like the `.ref` in `x.ref`.
+    // For a bare place like `x`, this is an empty span, so we insert after it.
+    let op_span = value.span(db).start_from(place_expr.span(db).at_end());
+    let place = place_expr.into_sym_place(db);
+    let (keyword, message) = match expected_op {
+        PermissionOp::Mutate => ("mut", format!("use `.mut` to lease `{place}` mutably")),
+        PermissionOp::Reference => ("ref", format!("use `.ref` to reference `{place}`")),
+        PermissionOp::Give => ("give", format!("use `.give` to give away `{place}`")),
+        PermissionOp::Share => ("share", format!("use `.share` to share `{place}`")),
+    };
+    diagnostic.suggest(db, op_span, format!(".{keyword}"), message)
+}
+
+/// The permission operation whose result has the permission of `ty`, if `ty` makes that clear.
+fn permission_op_producing<'db>(env: &Env<'db>, ty: SymTy<'db>) -> Option<PermissionOp> {
+    let db = env.db();
+    let SymTyKind::Perm(perm, _) = ty.kind(db) else {
+        return None;
+    };
+    match perm.kind(db) {
+        SymPermKind::My => Some(PermissionOp::Give),
+        SymPermKind::Our => Some(PermissionOp::Share),
+        SymPermKind::Referenced(_) => Some(PermissionOp::Reference),
+        SymPermKind::Mutable(_) => Some(PermissionOp::Mutate),
+        &SymPermKind::Var(var) => {
+            let is = |predicate| env.var_is_declared_to_be(var, predicate);
+            if is(Predicate::Lent) && is(Predicate::Unique) {
+                Some(PermissionOp::Mutate)
+            } else if is(Predicate::Lent) && is(Predicate::Shared) {
+                Some(PermissionOp::Reference)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// If `var` is the default permission of a parameter declared without one
+/// (e.g., the `x: String` parameter of a function or a `self` in a class),
+/// suggest declaring the parameter with a permission that is `predicate`.
+fn suggest_parameter_perm<'db>(
+    env: &Env<'db>,
+    diagnostic: Diagnostic,
+    var: SymVariable<'db>,
+    predicate: Predicate,
+) -> Diagnostic {
+    let db = env.db();
+    let keyword = match predicate {
+        Predicate::Unique => "mut",
+        Predicate::Owned => "my",
+        Predicate::Shared | Predicate::Lent => return diagnostic,
+    };
+    let Some(function) = env.function else {
+        return diagnostic;
+    };
+
+    let scope = function.scope(db);
+    let source = function.source(db);
+    let insert_span = source.inputs(db).iter().find_map(|input| match *input {
+        AstFunctionInput::SelfArg(self_arg) => {
+            (self_arg_requires_default_perm(db, self_arg, &scope)
+                && self_arg.anonymous_perm_symbol(db) == var)
+                .then(|| self_arg.span(db).at_start())
+        }
+        AstFunctionInput::Variable(decl) => (variable_decl_requires_default_perm(db, decl, &scope)
+            && decl.anonymous_perm_symbol(db) == var)
+            .then(|| decl.base_ty(db).span(db).at_start()),
+    });
+    let Some(insert_span) = insert_span else {
+        return diagnostic;
+    };
+
+    diagnostic.suggest(
+        db,
+        insert_span,
+        format!("{keyword} "),
+        format!("declare the parameter with the `{keyword}` permission"),
+    )
+}
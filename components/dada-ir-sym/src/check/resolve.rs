@@ -2,7 +2,7 @@
 
 use std::collections::hash_map::Entry;
 
-use dada_ir_ast::diagnostic::{Diagnostic, Err, Reported};
+use dada_ir_ast::diagnostic::{Diagnostic, DiagnosticCode, Err, Reported};
 use dada_util::Map;
 
 use crate::ir::{
@@ -175,6 +175,7 @@ impl<'env, 'db> Resolver<'env, 'db> {
     fn report(&self, infer: InferVarIndex, _err: ResolverCycle) -> Reported {
         let span = self.env.infer_var_span(infer);
         Diagnostic::error(self.db, span, "cyclic bounds found for inference variable")
+            .code(DiagnosticCode::CyclicInferenceBounds)
             .report(self.db)
     }
 }
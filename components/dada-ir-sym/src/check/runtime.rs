@@ -6,7 +6,7 @@ use std::{
     panic::Location,
     rc::Rc,
     sync::{
-        Arc, Mutex, RwLock,
+        Arc, Mutex, MutexGuard, RwLock,
         atomic::{AtomicBool, AtomicU64, Ordering},
     },
     task::{Context, Poll, Waker},
@@ -21,7 +21,7 @@ use dada_ir_ast::{
 use dada_util::{Map, Set, vecext::VecExt};
 use serde::Serialize;
 
-use crate::{check::env::Env, check::inference::InferenceVarData};
+use crate::{check::env::Env, check::inference::InferenceVarData, check::live_places::LivePoints};
 
 use super::{
     debug::{LogHandle, RootTaskDescription, TaskDescription, event_argument},
@@ -54,6 +54,17 @@ pub(crate) struct RuntimeData<'db> {
     /// If true, inference state is frozen and will not change further.
     complete: AtomicBool,
 
+    /// Program points at which we may ask which places are live.
+    /// See [`LivePlaces`](`crate::check::live_places::LivePlaces`).
+    live_points: Mutex<LivePoints<'db>>,
+
+    /// If true, the code following each live point has been checked,
+    /// so liveness queries can be answered.
+    liveness_computed: AtomicBool,
+
+    /// Tasks blocked until `liveness_computed` becomes true.
+    waiting_on_liveness: Mutex<Vec<EqWaker>>,
+
     /// Integer indicating the next task id; each task gets a unique id.
     next_task_id: AtomicU64,
 
@@ -117,6 +128,7 @@ impl<'db> Runtime<'db> {
             let runtime = runtime.clone();
             async move {
                 let result = constrain(&runtime).await;
+                runtime.mark_liveness_computed();
                 channel_tx.send(result).unwrap();
             }
         });
@@ -124,6 +136,11 @@ impl<'db> Runtime<'db> {
         // Run all spawned tasks until no more progress can be made.
         runtime.drain();
 
+        // If the main task did not finish, some live points may never be resolved.
+        // Unblock tasks waiting on liveness anyway; unresolved points consider all places live.
+        runtime.mark_liveness_computed();
+        runtime.drain();
+
         // Mark inference as done and drain again. This may generate fresh errors.
         runtime.mark_complete();
         runtime.drain();
@@ -155,6 +172,9 @@ impl<'db> Runtime<'db> {
             data: Rc::new(RuntimeData {
                 db,
                 complete: Default::default(),
+                live_points: Default::default(),
+                liveness_computed: Default::default(),
+                waiting_on_liveness: Default::default(),
                 inference_vars: Default::default(),
                 sub_inference_var_pairs: Default::default(),
                 ready_to_execute: Default::default(),
@@ -218,6 +238,37 @@ impl<'db> Runtime<'db> {
         }
     }
 
+    /// Mark the code following each live point as checked and wake tasks waiting on liveness.
+    fn mark_liveness_computed(&self) {
+        self.liveness_computed.store(true, Ordering::Relaxed);
+
+        let wakers = std::mem::take(&mut *self.waiting_on_liveness.lock().unwrap());
+        for EqWaker { waker } in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Access the live points created thus far.
+    pub(crate) fn live_points(&self) -> MutexGuard<'_, LivePoints<'db>> {
+        self.live_points.lock().unwrap()
+    }
+
+    /// Returns a future that blocks the current task until liveness can be computed,
+    /// i.e., until the code following each live point has been checked.
+    pub(crate) fn liveness_computed(&self) -> impl Future<Output = ()> {
+        std::future::poll_fn(move |cx| {
+            if self.liveness_computed.load(Ordering::Relaxed) {
+                Poll::Ready(())
+            } else {
+                self.waiting_on_liveness
+                    .lock()
+                    .unwrap()
+                    .push_if_not_contained(EqWaker::new(cx.waker()));
+                Poll::Pending
+            }
+        })
+    }
+
     /// Returns `true` if we have fully constructed the object IR for a given function.
     /// Once this returns true, no more bounds will be added to inference variables.
     pub fn check_complete(&self) -> bool {
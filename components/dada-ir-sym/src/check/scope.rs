@@ -2,7 +2,7 @@ use std::{borrow::Cow, fmt::Display};
 
 use dada_ir_ast::{
     ast::{AstGenericTerm, AstPath, AstPathKind, AstUse, Identifier, SpanVec, SpannedIdentifier},
-    diagnostic::{Diagnostic, Errors, Level, Reported},
+    diagnostic::{Diagnostic, DiagnosticCode, Errors, Level, Reported},
     inputs::Krate,
     span::{Span, Spanned},
 };
@@ -18,6 +18,7 @@ use crate::{
         functions::SymFunction,
         module::SymModule,
         primitive::{SymPrimitive, primitives},
+        traits::{SymImpl, SymTrait},
         types::{SymGenericKind, SymGenericTerm},
         variables::SymVariable,
     },
@@ -81,6 +82,7 @@ impl<'scope, 'db> Scope<'scope, 'db> {
             Ok(sym) => {
                 let span = sym.span(db).unwrap_or(span);
                 Diagnostic::error(db, span, "prelude is not a module".to_string())
+                    .code(DiagnosticCode::PreludeNotModule)
                     .label(
                         db,
                         Level::Error,
@@ -117,6 +119,52 @@ impl<'scope, 'db> Scope<'scope, 'db> {
         None
     }
 
+    /// Return the innermost trait in scope (if any).
+    pub fn trait_(&self) -> Option<SymTrait<'db>> {
+        for link in self.chain.iter() {
+            if let ScopeChainKind::SymTrait(sym_trait) = &link.kind {
+                return Some(*sym_trait);
+            }
+        }
+        None
+    }
+
+    /// Return the innermost impl in scope (if any).
+    pub fn impl_(&self) -> Option<SymImpl<'db>> {
+        for link in self.chain.iter() {
+            if let ScopeChainKind::SymImpl(sym_impl) = &link.kind {
+                return Some(*sym_impl);
+            }
+        }
+        None
+    }
+
+    /// Return the innermost module in scope.
+    /// (The prelude modules are further out than the module being checked.)
+    pub fn module(&self) -> Option<SymModule<'db>> {
+        for link in self.chain.iter() {
+            if let ScopeChainKind::SymModule(sym_module) = &link.kind {
+                return Some(*sym_module);
+            }
+        }
+        None
+    }
+
+    /// If `var` is the `Self` type of the innermost trait in scope,
+    /// returns the associated type of that trait named `id` (if any).
+    fn associated_ty_of(
+        &self,
+        db: &'db dyn crate::Db,
+        var: SymVariable<'db>,
+        id: Identifier<'db>,
+    ) -> Option<SymVariable<'db>> {
+        let sym_trait = self.trait_()?;
+        if sym_trait.self_var(db) != var {
+            return None;
+        }
+        sym_trait.associated_ty_named(db, id)
+    }
+
     /// Resolve identifier `id` (found at `span`) in the scope.
     /// Reports errors if nothing is found and returns `Err(Reported)`.
     pub(crate) fn resolve_name(
@@ -131,6 +179,7 @@ impl<'scope, 'db> Scope<'scope, 'db> {
 
         Err(
             Diagnostic::error(db, span, format!("could not find anything named `{id}`",))
+                .code(DiagnosticCode::NameNotFound)
                 .label(
                     db,
                     Level::Error,
@@ -141,6 +190,21 @@ impl<'scope, 'db> Scope<'scope, 'db> {
         )
     }
 
+    /// Resolve identifier `id` in the scope without reporting errors and return the span
+    /// of the name in its declaration (e.g., for "go to definition").
+    /// Returns `None` if nothing is found or if the name has no declaration (e.g., a primitive).
+    pub fn definition_span(
+        &self,
+        db: &'db dyn crate::Db,
+        id: Identifier<'db>,
+    ) -> Option<Span<'db>> {
+        let NameResolution { sym, generics: _ } = self
+            .chain
+            .iter()
+            .find_map(|link| link.resolve_name(db, id))?;
+        sym.span(db)
+    }
+
     /// True if `sym` is in scope.
     pub fn generic_sym_in_scope(&self, db: &'db dyn crate::Db, sym: SymVariable<'db>) -> bool {
         self.chain.iter().any(|link| link.binds_symbol(db, sym))
@@ -212,7 +276,9 @@ impl<'scope, 'db> Scope<'scope, 'db> {
             match &link.kind {
                 ScopeChainKind::Primitives
                 | ScopeChainKind::SymModule(_)
-                | ScopeChainKind::SymAggr(_) => {}
+                | ScopeChainKind::SymAggr(_)
+                | ScopeChainKind::SymTrait(_)
+                | ScopeChainKind::SymImpl(_) => {}
                 ScopeChainKind::ForAll(cow) => {
                     vec.push(cow.iter().copied().collect());
                 }
@@ -248,6 +314,12 @@ pub enum ScopeChainKind<'scope, 'db> {
     /// Records that we are in the scope of a class
     SymAggr(SymAggregate<'db>),
 
+    /// Records that we are in the scope of a trait
+    SymTrait(SymTrait<'db>),
+
+    /// Records that we are in the scope of an impl
+    SymImpl(SymImpl<'db>),
+
     /// Introduces the given symbols into scope.
     ForAll(Cow<'scope, [SymVariable<'db>]>),
 }
@@ -323,6 +395,7 @@ impl<'db> NameResolution<'db> {
                 extra_span,
                 "extra generic arguments provided".to_string(),
             )
+            .code(DiagnosticCode::ExtraGenericArguments)
             .label(
                 db,
                 Level::Error,
@@ -354,6 +427,7 @@ impl<'db> NameResolution<'db> {
 pub enum NameResolutionSym<'db> {
     SymModule(SymModule<'db>),
     SymAggregate(SymAggregate<'db>),
+    SymTrait(SymTrait<'db>),
     SymFunction(SymFunction<'db>),
     SymPrimitive(SymPrimitive<'db>),
     SymVariable(SymVariable<'db>),
@@ -365,6 +439,7 @@ impl<'db> NameResolutionSym<'db> {
         match self {
             NameResolutionSym::SymModule(_) => Box::new("a module") as Box<dyn Display + 'db>,
             NameResolutionSym::SymAggregate(_) => Box::new("a class"),
+            NameResolutionSym::SymTrait(_) => Box::new("a trait"),
             NameResolutionSym::SymFunction(_) => Box::new("a function"),
             NameResolutionSym::SymVariable(var) => match var.kind(db) {
                 SymGenericKind::Type => Box::new("a generic type"),
@@ -399,6 +474,7 @@ impl<'db> NameResolutionSym<'db> {
                 Some(sym) => Ok(Ok(sym)),
                 None => Err(
                     Diagnostic::error(db, id.span, "nothing named `{}` found in module")
+                        .code(DiagnosticCode::NameNotFoundInModule)
                         .label(
                             db,
                             Level::Error,
@@ -413,10 +489,8 @@ impl<'db> NameResolutionSym<'db> {
                 ),
             },
 
-            // FIXME: When we add traits, we have to decide how we want to manage trait member lookup.
-            // * Does this mean we have to merge name resolution plus type checking?
-            // * Do we not support `SomeClass.TraitMember` and instead prefer `SomeTrait.Member[SomeClass]`?
-            // * Do we only support `SomeClass.TraitMember` in expression contexts?
+            // Trait methods are not found here: they are resolved by type checking
+            // when invoked as methods (see `member_lookup.rs`).
             NameResolutionSym::SymAggregate(sym_class) => {
                 match sym_class.inherent_member(db, id.id) {
                     Some(class_member) => match class_member {
@@ -442,6 +516,9 @@ impl<'db> NameResolutionSym<'db> {
             NameResolutionSym::SymAggregate(sym_class) => {
                 format!("a class named `{}`", sym_class.name(db))
             }
+            NameResolutionSym::SymTrait(sym_trait) => {
+                format!("a trait named `{}`", sym_trait.name(db))
+            }
             NameResolutionSym::SymFunction(sym_function) => {
                 format!("a function named `{}`", sym_function.name(db))
             }
@@ -459,6 +536,8 @@ impl<'db> NameResolutionSym<'db> {
         match self {
             NameResolutionSym::SymModule(sym) => sym.expected_generic_parameters(db),
             NameResolutionSym::SymAggregate(sym) => sym.expected_generic_parameters(db),
+            // `Self` and the associated types are never given explicitly.
+            NameResolutionSym::SymTrait(sym) => sym.len_generics(db),
             NameResolutionSym::SymFunction(sym) => sym.expected_generic_parameters(db),
             NameResolutionSym::SymPrimitive(_) => 0,
             NameResolutionSym::SymVariable(_) => 0,
@@ -469,6 +548,7 @@ impl<'db> NameResolutionSym<'db> {
         match self {
             NameResolutionSym::SymModule(sym) => Some(sym.span(db)),
             NameResolutionSym::SymAggregate(sym) => Some(sym.span(db)),
+            NameResolutionSym::SymTrait(sym) => Some(sym.span(db)),
             NameResolutionSym::SymFunction(sym) => Some(sym.span(db)),
             NameResolutionSym::SymPrimitive(_) => None,
             NameResolutionSym::SymVariable(sym) => Some(sym.span(db)),
@@ -481,6 +561,7 @@ impl<'db> NameResolutionSym<'db> {
         match self {
             NameResolutionSym::SymModule(_) => None,
             NameResolutionSym::SymAggregate(aggr) => Some(aggr.style(db)),
+            NameResolutionSym::SymTrait(_) => None,
             NameResolutionSym::SymFunction(_) => None,
             NameResolutionSym::SymPrimitive(_) => None,
             NameResolutionSym::SymVariable(_) => None,
@@ -515,6 +596,12 @@ impl<'db> ResolveToSym<'db> for AstPath<'db> {
                 let base = path.resolve_to_sym(db, scope)?;
                 match base.resolve_relative_id(db, *id)? {
                     Ok(r) => Ok(r),
+                    Err(NameResolutionSym::SymVariable(var)) => {
+                        match scope.associated_ty_of(db, var, id.id) {
+                            Some(assoc_var) => Ok(NameResolutionSym::SymVariable(assoc_var)),
+                            None => Err(report_path_referencing_field(db, id, base)),
+                        }
+                    }
                     Err(base) => Err(report_path_referencing_field(db, id, base)),
                 }
             }
@@ -530,6 +617,7 @@ fn report_path_referencing_field<'db>(
     base: NameResolutionSym<'_>,
 ) -> Reported {
     Diagnostic::error(db, id.span, "unexpected `.` in path")
+        .code(DiagnosticCode::UnexpectedDotInPath)
         .label(
             db,
             Level::Error,
@@ -567,6 +655,20 @@ impl<'db> Resolve<'db> for AstPath<'db> {
                 let base = path.resolve_in(env).await?;
                 match base.resolve_relative_id(db, *id)? {
                     Ok(r) => Ok(r),
+                    Err(NameResolution {
+                        generics,
+                        sym: NameResolutionSym::SymVariable(var),
+                    }) => match env.scope.associated_ty_of(db, var, id.id) {
+                        Some(assoc_var) => Ok(NameResolution {
+                            generics,
+                            sym: NameResolutionSym::SymVariable(assoc_var),
+                        }),
+                        None => Err(report_path_referencing_field(
+                            db,
+                            id,
+                            NameResolutionSym::SymVariable(var),
+                        )),
+                    },
                     Err(base) => Err(report_path_referencing_field(db, id, base.sym)),
                 }
             }
@@ -634,7 +736,9 @@ impl<'scope, 'db> ScopeChain<'scope, 'db> {
                 })
                 .next(),
 
-            ScopeChainKind::SymAggr(_) => None,
+            ScopeChainKind::SymAggr(_)
+            | ScopeChainKind::SymTrait(_)
+            | ScopeChainKind::SymImpl(_) => None,
 
             ScopeChainKind::SymModule(sym) => {
                 // Somewhat subtle: we give definitions precedence over uses. If the same name appears
@@ -648,6 +752,9 @@ impl<'scope, 'db> ScopeChain<'scope, 'db> {
                         NameResolutionSym::SymAggregate(sym) => {
                             Some(self.internal_module_item(db, sym))
                         }
+                        NameResolutionSym::SymTrait(sym) => {
+                            Some(self.internal_module_item(db, sym))
+                        }
                         NameResolutionSym::SymFunction(sym) => {
                             Some(self.internal_module_item(db, sym))
                         }
@@ -691,6 +798,8 @@ impl<'scope, 'db> ScopeChain<'scope, 'db> {
     fn binds_symbol(&self, _db: &'db dyn crate::Db, sym: SymVariable<'db>) -> bool {
         match &self.kind {
             ScopeChainKind::SymAggr(_)
+            | ScopeChainKind::SymTrait(_)
+            | ScopeChainKind::SymImpl(_)
             | ScopeChainKind::Primitives
             | ScopeChainKind::SymModule(_) => false,
 
@@ -709,6 +818,10 @@ impl<'db> SymModule<'db> {
             return Some(v.into());
         }
 
+        if let Some(&v) = self.trait_map(db).get(&id) {
+            return Some(v.into());
+        }
+
         if let Some(&v) = self.function_map(db).get(&id) {
             return Some(v.into());
         }
@@ -741,6 +854,7 @@ fn resolve_ast_use<'db>(
                 ast_use.crate_name(db).id
             ),
         )
+        .code(DiagnosticCode::CrateNotFound)
         .label(
             db,
             Level::Error,
@@ -768,7 +882,9 @@ fn resolve_name_against_crate<'db>(
             Ok(sym_module.into())
         }
 
-        Err(message) => Err(Diagnostic::new(db, Level::Error, id.span(db), message).report(db)),
+        Err(message) => Err(Diagnostic::new(db, Level::Error, id.span(db), message)
+            .code(DiagnosticCode::UnreadableSourceFile)
+            .report(db)),
     }
 }
 
@@ -781,11 +897,32 @@ trait ResolveAgainst<'db> {
 }
 
 impl<'db> ResolveAgainst<'db> for AstPath<'db> {
+    /// Resolve a path like `a.b.c` where `op` resolves the first identifier `a`
+    /// (e.g., against the modules of a crate). Errors are reported and yield `None`.
+    /// Generic arguments are ignored, as in [`ResolveToSym`].
     fn resolve_against(
         self,
-        _db: &'db dyn crate::Db,
-        _op: impl FnOnce(SpannedIdentifier<'db>) -> Errors<NameResolutionSym<'db>>,
+        db: &'db dyn crate::Db,
+        op: impl FnOnce(SpannedIdentifier<'db>) -> Errors<NameResolutionSym<'db>>,
     ) -> Option<NameResolution<'db>> {
-        todo!()
+        let sym = match self.kind(db) {
+            AstPathKind::Identifier(first_id) => op(*first_id).ok()?,
+            AstPathKind::GenericArgs { path, args: _ } => return path.resolve_against(db, op),
+            AstPathKind::Member { path, id } => {
+                let base = path.resolve_against(db, op)?;
+                match base.sym.resolve_relative_id(db, *id).ok()? {
+                    Ok(sym) => sym,
+                    Err(base) => {
+                        report_path_referencing_field(db, id, base);
+                        return None;
+                    }
+                }
+            }
+        };
+
+        Some(NameResolution {
+            generics: vec![],
+            sym,
+        })
     }
 }
@@ -4,8 +4,13 @@ use salsa::Update;
 use serde::Serialize;
 
 use crate::{
-    check::scope::Scope, ir::classes::SymAggregate, ir::functions::SymFunction,
-    ir::module::SymModule, ir::variables::SymVariable,
+    check::scope::Scope,
+    ir::classes::SymAggregate,
+    ir::functions::SymFunction,
+    ir::module::SymModule,
+    ir::traits::{SymImpl, SymTrait},
+    ir::variables::SymVariable,
+    prelude::Symbol,
 };
 
 /// A `ScopeItem` defines a name resolution scope.
@@ -22,6 +27,12 @@ pub enum ScopeItem<'db> {
     /// A class or other aggregate
     Class(SymAggregate<'db>),
 
+    /// A trait
+    Trait(SymTrait<'db>),
+
+    /// An impl of a trait
+    Impl(SymImpl<'db>),
+
     /// A function or method
     SymFunction(SymFunction<'db>),
 }
@@ -79,6 +90,20 @@ pub trait ScopeTreeNode<'db>: Sized + Into<ScopeItem<'db>> {
         self.iter_super_scopes(db)
             .for_each(|s| s.push_direct_ast_where_clauses(db, out));
     }
+
+    /// The module in which this item is (transitively) declared.
+    fn module(self, db: &'db dyn crate::Db) -> SymModule<'db> {
+        self.iter_super_scopes(db)
+            .find_map(|s| match s {
+                ScopeItem::AstModule(m) => Some(m.symbol(db)),
+                ScopeItem::SymModule(m) => Some(m),
+                ScopeItem::Class(_)
+                | ScopeItem::Trait(_)
+                | ScopeItem::Impl(_)
+                | ScopeItem::SymFunction(_) => None,
+            })
+            .unwrap()
+    }
 }
 
 impl<'db> ScopeTreeNode<'db> for ScopeItem<'db> {
@@ -87,6 +112,8 @@ impl<'db> ScopeTreeNode<'db> for ScopeItem<'db> {
             ScopeItem::AstModule(sym) => sym.direct_super_scope(db),
             ScopeItem::SymModule(sym) => sym.direct_super_scope(db),
             ScopeItem::Class(sym) => sym.direct_super_scope(db),
+            ScopeItem::Trait(sym) => sym.direct_super_scope(db),
+            ScopeItem::Impl(sym) => sym.direct_super_scope(db),
             ScopeItem::SymFunction(sym) => sym.direct_super_scope(db),
         }
     }
@@ -96,6 +123,8 @@ impl<'db> ScopeTreeNode<'db> for ScopeItem<'db> {
             ScopeItem::AstModule(sym) => sym.direct_generic_parameters(db),
             ScopeItem::SymModule(sym) => sym.direct_generic_parameters(db),
             ScopeItem::Class(sym) => sym.direct_generic_parameters(db),
+            ScopeItem::Trait(sym) => sym.direct_generic_parameters(db),
+            ScopeItem::Impl(sym) => sym.direct_generic_parameters(db),
             ScopeItem::SymFunction(sym) => sym.direct_generic_parameters(db),
         }
     }
@@ -105,6 +134,8 @@ impl<'db> ScopeTreeNode<'db> for ScopeItem<'db> {
             ScopeItem::AstModule(sym) => sym.into_scope(db),
             ScopeItem::SymModule(sym) => sym.into_scope(db),
             ScopeItem::Class(sym) => sym.into_scope(db),
+            ScopeItem::Trait(sym) => sym.into_scope(db),
+            ScopeItem::Impl(sym) => sym.into_scope(db),
             ScopeItem::SymFunction(sym) => sym.into_scope(db),
         }
     }
@@ -120,6 +151,12 @@ impl<'db> ScopeTreeNode<'db> for ScopeItem<'db> {
             ScopeItem::Class(sym) => {
                 sym.push_direct_ast_where_clauses(db, out);
             }
+            ScopeItem::Trait(sym) => {
+                sym.push_direct_ast_where_clauses(db, out);
+            }
+            ScopeItem::Impl(sym) => {
+                sym.push_direct_ast_where_clauses(db, out);
+            }
             ScopeItem::SymFunction(sym) => {
                 sym.push_direct_ast_where_clauses(db, out);
             }
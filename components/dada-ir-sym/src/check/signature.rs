@@ -8,7 +8,7 @@ use crate::{
     check::{env::Env, runtime::Runtime},
     ir::{
         functions::{SymFunction, SymFunctionSignature, SymFunctionSource, SymInputOutput},
-        generics::SymWhereClause,
+        generics::{SymWhereClause, SymWhereClauseKind},
         populate::self_arg_requires_default_perm,
         types::{AnonymousPermSymbol, SymPerm, SymTy, SymTyName},
         variables::SymVariable,
@@ -91,6 +91,7 @@ pub async fn prepare_env<'db>(
         .collect::<Vec<_>>();
 
     let mut env: Env<'db> = Env::new(runtime, function.scope(db));
+    env.function = Some(function);
 
     // Set the AST types for the inputs.
     for i in source.inputs(db).iter() {
@@ -107,6 +108,7 @@ pub async fn prepare_env<'db>(
     // Symbolify the output type.
     let output_ty_body: SymTy<'db> = output_ty(&mut env, &function).await;
     env.set_return_ty(output_ty_body);
+    env.unsafe_context = function.effects(db).unsafe_effect;
 
     let output_ty_caller = if function.effects(db).async_effect {
         SymTy::named(db, SymTyName::Future, vec![output_ty_body.into()])
@@ -122,6 +124,16 @@ pub async fn prepare_env<'db>(
         symbolify_ast_where_clause(&mut env, ast_where_clause, &mut where_clauses).await;
     }
 
+    // Methods declared in a trait have an implicit where-clause `Self is Trait`.
+    if let Some(sym_trait) = function.owning_trait(db) {
+        where_clauses.push(SymWhereClause::new(
+            db,
+            SymTy::var(db, sym_trait.self_var(db)).into(),
+            SymWhereClauseKind::Trait(sym_trait.identity_ref(db)),
+        ));
+    }
+    env.assume_where_clauses(&where_clauses);
+
     PreparedEnv {
         env,
         input_symbols,
@@ -137,8 +149,7 @@ async fn set_variable_ty_from_input<'db>(env: &mut Env<'db>, input: &AstFunction
     let lv = input.symbol(db);
     match input {
         AstFunctionInput::SelfArg(arg) => {
-            let self_ty = if let Some(aggregate) = env.scope.aggregate() {
-                let aggr_ty = aggregate.self_ty(db, &env.scope);
+            let self_ty = if let Some(aggr_ty) = self_ty(env).await {
                 if let Some(ast_perm) = arg.perm(db) {
                     let sym_perm = ast_perm.check_in_env(env).await;
                     SymTy::perm(db, sym_perm, aggr_ty)
@@ -154,7 +165,7 @@ async fn set_variable_ty_from_input<'db>(env: &mut Env<'db>, input: &AstFunction
                     Diagnostic::error(
                         db,
                         arg.span(db),
-                        "self parameter is only permitted within a class definition",
+                        "self parameter is only permitted within a class, trait, or impl definition",
                     )
                     .report(db),
                 )
@@ -165,6 +176,22 @@ async fn set_variable_ty_from_input<'db>(env: &mut Env<'db>, input: &AstFunction
     }
 }
 
+/// The type of `self` (without permissions) for methods in the current scope, if any.
+/// Within a trait, this is the `Self` type variable; within an impl, it is the type
+/// the trait is implemented for.
+async fn self_ty<'db>(env: &mut Env<'db>) -> Option<SymTy<'db>> {
+    let db = env.db();
+    if let Some(sym_impl) = env.scope.impl_() {
+        Some(sym_impl.source(db).self_ty(db).check_in_env(env).await)
+    } else if let Some(sym_trait) = env.scope.trait_() {
+        Some(SymTy::var(db, sym_trait.self_var(db)))
+    } else {
+        env.scope
+            .aggregate()
+            .map(|aggregate| aggregate.self_ty(db, &env.scope))
+    }
+}
+
 async fn output_ty<'db>(env: &mut Env<'db>, function: &SymFunction<'db>) -> SymTy<'db> {
     let db = env.db();
     match function.source(db) {
@@ -176,5 +203,8 @@ async fn output_ty<'db>(env: &mut Env<'db>, function: &SymFunction<'db>) -> SymT
         SymFunctionSource::Constructor(sym_aggregate, _ast_aggregate) => {
             sym_aggregate.self_ty(db, &env.scope)
         }
+        SymFunctionSource::VariantConstructor(variant) => {
+            variant.aggregate(db).self_ty(db, &env.scope)
+        }
     }
 }
@@ -35,16 +35,17 @@ pub async fn check_block_statements<'db>(
                 None => env.fresh_ty_inference_var(s.name(db).span),
             };
 
+            let live_after_initializer = live_after.before_pending(env);
             let (initializer, body) = env
                 .join(
                     async |env| match s.initializer(db) {
                         Some(initializer) => {
                             let initializer = initializer
-                                .check_in_env(env, LivePlaces::fixme())
+                                .check_in_env(env, live_after_initializer)
                                 .await
                                 .into_expr_with_enclosed_temporaries(env);
                             env.spawn_require_assignable_type(
-                                LivePlaces::fixme(),
+                                live_after_initializer,
                                 initializer.ty(db),
                                 ty,
                                 &InvalidInitializerType::new(lv, s.name(db).span, ty, initializer),
@@ -56,10 +57,11 @@ pub async fn check_block_statements<'db>(
                     },
                     async |env| {
                         env.push_program_variable_with_ty(lv, ty);
-                        check_block_statements(env, LivePlaces::fixme(), block_span, rest).await
+                        check_block_statements(env, live_after, block_span, rest).await
                     },
                 )
                 .await;
+            live_after_initializer.resolve_pending(env, [body]);
 
             // Create `let lv: ty = lv = initializer; remainder`
             let span = s.span(db).to(db, body.span(db));
@@ -77,21 +79,24 @@ pub async fn check_block_statements<'db>(
         }
 
         AstStatement::Expr(e) => {
-            let check_e = async |env: &mut Env<'db>| {
-                e.check_in_env(env, LivePlaces::fixme())
+            let check_e = async |env: &mut Env<'db>, live_after_e: LivePlaces| {
+                e.check_in_env(env, live_after_e)
                     .await
                     .into_expr_with_enclosed_temporaries(env)
             };
             if rest.is_empty() {
                 // Subtle-ish: if this is the last statement in the block,
                 // it becomes the result of the block.
-                check_e(env).await
+                check_e(env, live_after).await
             } else {
+                let live_after_e = live_after.before_pending(env);
                 let (ce, re) = env
-                    .join(check_e, async |env| {
-                        check_block_statements(env, live_after, block_span, rest).await
-                    })
+                    .join(
+                        async |env| check_e(env, live_after_e).await,
+                        async |env| check_block_statements(env, live_after, block_span, rest).await,
+                    )
                     .await;
+                live_after_e.resolve_pending(env, [re]);
                 SymExpr::new(
                     db,
                     ce.span(db).to(db, re.span(db)),
@@ -113,6 +113,10 @@ impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymExprKind<'db> {
                 operand: operand.subst_with(db, bound_vars, subst_fns),
                 op_span: op_span.subst_with(db, bound_vars, subst_fns),
             },
+            SymExprKind::Negate { operand, op_span } => SymExprKind::Negate {
+                operand: operand.subst_with(db, bound_vars, subst_fns),
+                op_span: op_span.subst_with(db, bound_vars, subst_fns),
+            },
             SymExprKind::BinaryOp(sym_binary_op, sym_expr, sym_expr1) => SymExprKind::BinaryOp(
                 sym_binary_op.subst_with(db, bound_vars, subst_fns),
                 sym_expr.subst_with(db, bound_vars, subst_fns),
@@ -122,9 +126,27 @@ impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymExprKind<'db> {
                 ty: ty.subst_with(db, bound_vars, subst_fns),
                 fields: fields.subst_with(db, bound_vars, subst_fns),
             },
+            SymExprKind::Variant {
+                ty,
+                variant,
+                fields,
+            } => SymExprKind::Variant {
+                ty: ty.subst_with(db, bound_vars, subst_fns),
+                variant: variant.subst_with(db, bound_vars, subst_fns),
+                fields: fields.subst_with(db, bound_vars, subst_fns),
+            },
+            SymExprKind::IsVariant { place, variant } => SymExprKind::IsVariant {
+                place: place.subst_with(db, bound_vars, subst_fns),
+                variant: variant.subst_with(db, bound_vars, subst_fns),
+            },
             SymExprKind::Match { arms } => SymExprKind::Match {
                 arms: arms.subst_with(db, bound_vars, subst_fns),
             },
+            SymExprKind::Loop { body } => SymExprKind::Loop {
+                body: body.subst_with(db, bound_vars, subst_fns),
+            },
+            SymExprKind::Break => SymExprKind::Break,
+            SymExprKind::Continue => SymExprKind::Continue,
             SymExprKind::Error(reported) => {
                 SymExprKind::Error(reported.subst_with(db, bound_vars, subst_fns))
             }
@@ -179,6 +201,10 @@ impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymPlaceExprKind<'db> {
                 sym_place_expr.subst_with(db, bound_vars, subst_fns),
                 sym_field.subst_with(db, bound_vars, subst_fns),
             ),
+            SymPlaceExprKind::Index(sym_place_expr, index) => SymPlaceExprKind::Index(
+                sym_place_expr.subst_with(db, bound_vars, subst_fns),
+                assert_bound_variable(db, index, bound_vars),
+            ),
             SymPlaceExprKind::Error(reported) => {
                 SymPlaceExprKind::Error(reported.subst_with(db, bound_vars, subst_fns))
             }
@@ -28,7 +28,7 @@ pub async fn require_my_numeric_type<'db>(
     let my_perm = SymPerm::my(env.db());
     env.require_both(
         async |env| require_sub_perms(env, live_after, my_perm, perm, or_else).await,
-        async |env| require_numeric_red_type(env, red_ty, or_else).await,
+        async |env| require_numeric_red_type(env, red_ty, Numeric::Any, or_else).await,
     )
     .await
 }
@@ -39,29 +39,72 @@ pub async fn require_numeric_type<'db>(
     or_else: &dyn OrElse<'db>,
 ) -> Errors<()> {
     let (red_ty, _) = ty.to_red_ty(env);
-    require_numeric_red_type(env, red_ty, or_else).await
+    require_numeric_red_type(env, red_ty, Numeric::Any, or_else).await
+}
+
+/// Like [`require_numeric_type`] but floating-point types are not accepted.
+pub async fn require_integer_type<'db>(
+    env: &mut Env<'db>,
+    ty: SymTy<'db>,
+    or_else: &dyn OrElse<'db>,
+) -> Errors<()> {
+    let (red_ty, _) = ty.to_red_ty(env);
+    require_numeric_red_type(env, red_ty, Numeric::Integer, or_else).await
+}
+
+/// Like [`require_numeric_type`] but unsigned integer types are not accepted.
+pub async fn require_signed_numeric_type<'db>(
+    env: &mut Env<'db>,
+    ty: SymTy<'db>,
+    or_else: &dyn OrElse<'db>,
+) -> Errors<()> {
+    let (red_ty, _) = ty.to_red_ty(env);
+    require_numeric_red_type(env, red_ty, Numeric::Signed, or_else).await
+}
+
+/// The set of primitive types accepted by [`require_numeric_red_type`].
+#[derive(Copy, Clone, Debug)]
+enum Numeric {
+    Any,
+    Integer,
+    Signed,
+}
+
+impl Numeric {
+    fn accepts(self, kind: SymPrimitiveKind) -> bool {
+        match kind {
+            SymPrimitiveKind::Bool | SymPrimitiveKind::Char => false,
+            SymPrimitiveKind::Int { bits: _ } | SymPrimitiveKind::Isize => true,
+            SymPrimitiveKind::Uint { bits: _ } | SymPrimitiveKind::Usize => match self {
+                Numeric::Any | Numeric::Integer => true,
+                Numeric::Signed => false,
+            },
+            SymPrimitiveKind::Float { bits: _ } => match self {
+                Numeric::Any | Numeric::Signed => true,
+                Numeric::Integer => false,
+            },
+        }
+    }
 }
 
 #[boxed_async_fn]
 async fn require_numeric_red_type<'db>(
     env: &mut Env<'db>,
     red_ty: RedTy<'db>,
+    numeric: Numeric,
     or_else: &dyn OrElse<'db>,
 ) -> Errors<()> {
     let db = env.db();
     match red_ty {
         RedTy::Error(reported) => Err(reported),
         RedTy::Named(sym_ty_name, _) => match sym_ty_name {
-            SymTyName::Primitive(sym_primitive) => match sym_primitive.kind(db) {
-                SymPrimitiveKind::Bool | SymPrimitiveKind::Char => {
+            SymTyName::Primitive(sym_primitive) => {
+                if numeric.accepts(sym_primitive.kind(db)) {
+                    Ok(())
+                } else {
                     Err(or_else.report(env, Because::JustSo))
                 }
-                SymPrimitiveKind::Int { bits: _ }
-                | SymPrimitiveKind::Isize
-                | SymPrimitiveKind::Uint { bits: _ }
-                | SymPrimitiveKind::Usize
-                | SymPrimitiveKind::Float { bits: _ } => Ok(()),
-            },
+            }
             SymTyName::Aggregate(_) | SymTyName::Future | SymTyName::Tuple { arity: _ } => {
                 Err(or_else.report(env, Because::JustSo))
             }
@@ -81,6 +124,7 @@ async fn require_numeric_red_type<'db>(
             require_numeric_red_type(
                 env,
                 lower_red_ty.clone(),
+                numeric,
                 &or_else.map_because(move |_| {
                     Because::InferredLowerBound(lower_red_ty.clone(), arc_or_else.clone())
                 }),
@@ -31,6 +31,10 @@ use crate::{
 // * `X C0 <= X C1 if C0 <= C1`
 // * `X <= our if X is copy+owned`
 // * `X <= my if X is move+owned`
+//
+// There is no rule for `given[place]`: it is desugared to the permission of `place`
+// when the type is checked (see `given_places_to_sym_perm`), so `given[place0] C0 <= C1`
+// holds exactly when `P0 C0 <= C1`, where `P0` is the permission of `place0`.
 
 pub async fn require_sub_perms<'db>(
     env: &mut Env<'db>,
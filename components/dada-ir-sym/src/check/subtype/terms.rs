@@ -444,8 +444,8 @@ async fn propagate_inverse_bound<'db>(
                     Some(generalize(env, red_ty, span)?)
                 }
                 SymTyName::Aggregate(_sym_aggregate) => {
-                    // FIXME(#241): check if `sym_aggregate` is an enum
-                    // in which case we need to adjust based on `direction`
+                    // Even if `sym_aggregate` is an enum, its variants are not types
+                    // of their own, so there is no subtyping to adjust for based on `direction`.
                     Some(generalize(env, red_ty, span)?)
                 }
             },
@@ -7,7 +7,7 @@ use dada_util::boxed_async_fn;
 
 use crate::ir::{
     indices::FromInfer,
-    types::{SymGenericTerm, SymPerm, SymPermKind, SymPlace, SymTy, SymTyKind},
+    types::{SymGenericTerm, SymPerm, SymPermKind, SymPlace, SymTy, SymTyKind, write_named_ty},
 };
 
 use super::{
@@ -38,12 +38,12 @@ impl<'db> RedTyExt<'db> for RedTy<'db> {
                 match &self.ty {
                     RedTy::Error(_reported) => write!(f, "<error>"),
                     RedTy::Named(sym_ty_name, sym_generic_terms) => {
-                        write!(f, "{sym_ty_name}[{sym_generic_terms:?}]")
+                        write_named_ty(f, sym_ty_name, sym_generic_terms)
                     }
                     RedTy::Never => write!(f, "!"),
 
                     // FIXME: do better by querying the env state
-                    RedTy::Infer(v) => write!(f, "?{}", v.as_usize()),
+                    RedTy::Infer(v) => write!(f, "?T{}", v.as_usize()),
 
                     RedTy::Var(sym_variable) => write!(f, "{sym_variable}"),
                     RedTy::Perm => write!(f, "<perm>"),
@@ -324,19 +324,19 @@ impl<'db> ToRedLinkVecs<'db> for SymPerm<'db> {
             SymPermKind::My => consumer.consume(env, vec![vec![]]).await,
             SymPermKind::Our => consumer.consume(env, vec![vec![RedLink::Our]]).await,
             SymPermKind::Referenced(ref places) => {
-                let links = places
-                    .iter()
-                    .map(|&place| RedLink::Ref(Live(live_after.is_live(env, place)), place))
-                    .map(|link| vec![link])
-                    .collect::<Vec<_>>();
+                let mut links = vec![];
+                for &place in places {
+                    let live = Live(live_after.is_live(env, place).await);
+                    links.push(vec![RedLink::Ref(live, place)]);
+                }
                 consumer.consume(env, links).await
             }
             SymPermKind::Mutable(ref places) => {
-                let links = places
-                    .iter()
-                    .map(|&place| RedLink::Mut(Live(live_after.is_live(env, place)), place))
-                    .map(|link| vec![link])
-                    .collect::<Vec<_>>();
+                let mut links = vec![];
+                for &place in places {
+                    let live = Live(live_after.is_live(env, place).await);
+                    links.push(vec![RedLink::Mut(live, place)]);
+                }
                 consumer.consume(env, links).await
             }
             SymPermKind::Apply(lhs, rhs) => {
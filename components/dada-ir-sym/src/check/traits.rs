@@ -0,0 +1,395 @@
+use dada_ir_ast::{
+    ast::AstPath,
+    diagnostic::{Diagnostic, Err, Errors, Level, ordinal},
+    span::Spanned,
+};
+
+use crate::{
+    check::{env::Env, runtime::Runtime, scope::NameResolutionSym, scope_tree::ScopeTreeNode},
+    ir::{
+        binder::Binder,
+        functions::{SymFunction, SymFunctionSource},
+        traits::{SymImpl, SymImplHeader, SymTrait, SymTraitRef},
+        types::{SymTy, SymTyKind, SymTyName},
+    },
+    prelude::{CheckMembers, CheckedImplHeader},
+};
+
+use super::{CheckTyInEnv, scope::Resolve};
+
+/// Resolve `path` to a reference to a trait, like `Place[my]`,
+/// checking that the generic arguments match what the trait expects.
+pub(crate) async fn check_trait_ref<'db>(
+    env: &mut Env<'db>,
+    path: AstPath<'db>,
+) -> Errors<SymTraitRef<'db>> {
+    let db = env.db();
+    let name_resolution = path.resolve_in(env).await?;
+    let NameResolutionSym::SymTrait(sym_trait) = name_resolution.sym else {
+        return Err(Diagnostic::error(
+            db,
+            path.span(db),
+            format!("expected a trait, found {}", name_resolution.categorize(db)),
+        )
+        .label(
+            db,
+            Level::Error,
+            path.span(db),
+            format!(
+                "I expected the name of a trait here, but I found {}",
+                name_resolution.sym.describe(db)
+            ),
+        )
+        .report(db));
+    };
+
+    let name = sym_trait.name(db);
+    let generics = name_resolution.generics;
+    let expected = sym_trait.len_generics(db);
+    let found = generics.len();
+    if found != expected {
+        return Err(Diagnostic::error(
+            db,
+            path.span(db),
+            format!("expected {expected} generic arguments, found {found}"),
+        )
+        .label(
+            db,
+            Level::Error,
+            path.span(db),
+            format!("`{name}` expects {expected} generic arguments, but I found {found}"),
+        )
+        .label(
+            db,
+            Level::Info,
+            sym_trait.generics_span(db),
+            format!("generic arguments for `{name}` are declared here"),
+        )
+        .report(db));
+    }
+
+    for ((expected_kind, &generic), index) in sym_trait.generic_kinds(db).zip(&generics).zip(0..) {
+        if !generic.has_kind(db, expected_kind) {
+            let found_kind = generic.kind().unwrap();
+            return Err(Diagnostic::error(
+                db,
+                path.span(db),
+                format!("expected a `{expected_kind}`, found a `{found_kind}`"),
+            )
+            .label(
+                db,
+                Level::Error,
+                path.span(db),
+                format!(
+                    "`{name}` expects a `{expected_kind}` for its {ith} generic argument, but I found a `{found_kind}`",
+                    ith = ordinal(index + 1),
+                ),
+            )
+            .report(db));
+        }
+    }
+
+    Ok(SymTraitRef::new(db, sym_trait, generics, vec![]))
+}
+
+/// Check the header of an impl, like `impl[perm P] Place[P] for Foo { type Output = String }`.
+/// The result has one binder, for the generics declared on the impl.
+pub(crate) fn check_impl_header<'db>(
+    db: &'db dyn crate::Db,
+    sym_impl: SymImpl<'db>,
+) -> Errors<Binder<'db, SymImplHeader<'db>>> {
+    Runtime::execute(
+        db,
+        sym_impl.impl_span(db),
+        "check_impl_header",
+        &[&sym_impl],
+        async move |runtime| -> Errors<Binder<'db, SymImplHeader<'db>>> {
+            let mut env = Env::new(runtime, sym_impl.impl_scope(db));
+            let source = sym_impl.source(db);
+
+            let trait_ref = check_trait_ref(&mut env, source.trait_path(db)).await?;
+            let sym_trait = trait_ref.trait_(db);
+
+            let ast_self_ty = source.self_ty(db);
+            let self_ty = ast_self_ty.check_in_env(&mut env).await;
+            match self_ty.kind(db) {
+                SymTyKind::Named(SymTyName::Aggregate(_), _) | SymTyKind::Error(_) => {}
+                _ => {
+                    Diagnostic::error(
+                        db,
+                        ast_self_ty.span(db),
+                        "impls must be for a class, struct, or enum",
+                    )
+                    .label(
+                        db,
+                        Level::Error,
+                        ast_self_ty.span(db),
+                        format!(
+                            "I expected a class, struct, or enum here, but I found `{self_ty}`"
+                        ),
+                    )
+                    .report(db);
+                }
+            }
+
+            // Find the value for each associated type, in the order the trait declares them.
+            let mut associated_tys = vec![];
+            for trait_associated_ty in sym_trait.associated_tys(db) {
+                let id = trait_associated_ty.name(db).id;
+                let impl_associated_ty = sym_impl.associated_tys(db).find(|a| a.name(db).id == id);
+                let ty = match impl_associated_ty.and_then(|a| a.value(db)) {
+                    Some(ast_ty) => ast_ty.check_in_env(&mut env).await,
+                    None => SymTy::err(
+                        db,
+                        Diagnostic::error(
+                            db,
+                            sym_impl.impl_span(db),
+                            format!("missing value for associated type `{id}`"),
+                        )
+                        .label(
+                            db,
+                            Level::Error,
+                            impl_associated_ty
+                                .map(|a| a.span(db))
+                                .unwrap_or(sym_impl.impl_span(db)),
+                            format!("I expected a value for `{id}`, like `type {id} = ...`"),
+                        )
+                        .label(
+                            db,
+                            Level::Info,
+                            trait_associated_ty.span(db),
+                            format!("`{id}` is declared in the trait `{sym_trait}` here"),
+                        )
+                        .report(db),
+                    ),
+                };
+                associated_tys.push(ty);
+            }
+
+            for impl_associated_ty in sym_impl.associated_tys(db) {
+                let name = impl_associated_ty.name(db);
+                if sym_trait.associated_ty_named(db, name.id).is_none() {
+                    Diagnostic::error(
+                        db,
+                        name.span,
+                        format!(
+                            "`{id}` is not an associated type of the trait `{sym_trait}`",
+                            id = name.id
+                        ),
+                    )
+                    .label(
+                        db,
+                        Level::Error,
+                        name.span,
+                        format!("the trait `{sym_trait}` does not declare an associated type with this name"),
+                    )
+                    .report(db);
+                }
+            }
+
+            Ok(env.into_scope().into_bound_value(
+                db,
+                SymImplHeader {
+                    trait_ref,
+                    self_ty,
+                    associated_tys,
+                },
+            ))
+        },
+        |header| header,
+    )
+}
+
+/// Checks that the members of a trait are well-formed.
+/// This is executed by the `dada-check` crate.
+#[salsa::tracked]
+impl<'db> CheckMembers<'db> for SymTrait<'db> {
+    #[salsa::tracked]
+    fn check_members(self, db: &'db dyn crate::Db) {
+        for &method in self.methods(db) {
+            if let SymFunctionSource::Function(ast_function) = method.source(db)
+                && ast_function.body(db).is_some()
+            {
+                Diagnostic::error(
+                    db,
+                    method.name_span(db),
+                    "default method bodies are not yet supported",
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    method.name_span(db),
+                    "methods declared in a trait cannot have a body; put the body in an impl",
+                )
+                .report(db);
+            }
+        }
+
+        for associated_ty in self.associated_tys(db) {
+            if let Some(value) = associated_ty.value(db) {
+                Diagnostic::error(
+                    db,
+                    value.span(db),
+                    "default values for associated types are not yet supported",
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    value.span(db),
+                    "associated types declared in a trait cannot have a value",
+                )
+                .report(db);
+            }
+        }
+    }
+}
+
+/// Checks that the members of an impl match the trait being implemented
+/// and that the impl is permitted where it appears.
+/// This is executed by the `dada-check` crate.
+#[salsa::tracked]
+impl<'db> CheckMembers<'db> for SymImpl<'db> {
+    #[salsa::tracked]
+    fn check_members(self, db: &'db dyn crate::Db) {
+        let _ = self.checked_header(db);
+
+        let Ok(sym_trait) = self.trait_(db) else {
+            return;
+        };
+
+        if let Some(aggr) = self.self_aggregate(db) {
+            let aggr_name = aggr.name(db);
+            let impl_module = self.module(db);
+            if impl_module != sym_trait.module(db) && impl_module != aggr.module(db) {
+                Diagnostic::error(
+                    db,
+                    self.impl_span(db),
+                    format!("impl of `{sym_trait}` for `{aggr_name}` is not permitted here"),
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    self.impl_span(db),
+                    format!(
+                        "an impl must appear in the module that declares `{sym_trait}` or the module that declares `{aggr_name}`"
+                    ),
+                )
+                .report(db);
+            } else if let Some(other_impl) = sym_trait.impl_for(db, aggr)
+                && other_impl != self
+            {
+                Diagnostic::error(
+                    db,
+                    self.impl_span(db),
+                    format!("conflicting impls of `{sym_trait}` for `{aggr_name}`"),
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    self.impl_span(db),
+                    format!("`{aggr_name}` already implements `{sym_trait}`"),
+                )
+                .label(
+                    db,
+                    Level::Info,
+                    other_impl.impl_span(db),
+                    "the other impl is here",
+                )
+                .report(db);
+            }
+        }
+
+        for &trait_method in sym_trait.methods(db) {
+            let name = trait_method.name(db);
+            match self.method_named(db, name) {
+                Some(impl_method) => check_impl_method(db, sym_trait, trait_method, impl_method),
+                None => {
+                    Diagnostic::error(
+                        db,
+                        self.impl_span(db),
+                        format!("missing method `{name}` in impl of `{sym_trait}`"),
+                    )
+                    .label(
+                        db,
+                        Level::Error,
+                        self.impl_span(db),
+                        format!("I expected this impl to define the method `{name}`"),
+                    )
+                    .label(
+                        db,
+                        Level::Info,
+                        trait_method.name_span(db),
+                        format!("`{name}` is declared in the trait `{sym_trait}` here"),
+                    )
+                    .report(db);
+                }
+            }
+        }
+
+        for &impl_method in self.methods(db) {
+            let name = impl_method.name(db);
+            if sym_trait.method_named(db, name).is_none() {
+                Diagnostic::error(
+                    db,
+                    impl_method.name_span(db),
+                    format!("method `{name}` is not a member of the trait `{sym_trait}`"),
+                )
+                .label(
+                    db,
+                    Level::Error,
+                    impl_method.name_span(db),
+                    format!("the trait `{sym_trait}` does not declare a method with this name"),
+                )
+                .report(db);
+            }
+        }
+    }
+}
+
+/// Check that the method `impl_method` has the same shape as `trait_method`.
+/// We only check the number of generic parameters and inputs; the types
+/// themselves are checked when the impl method's body is type checked.
+fn check_impl_method<'db>(
+    db: &'db dyn crate::Db,
+    sym_trait: SymTrait<'db>,
+    trait_method: SymFunction<'db>,
+    impl_method: SymFunction<'db>,
+) {
+    let name = trait_method.name(db);
+    let trait_symbols = trait_method.symbols(db);
+    let impl_symbols = impl_method.symbols(db);
+
+    for (what, expected, found) in [
+        (
+            "generic parameters",
+            trait_symbols.generic_variables.len(),
+            impl_symbols.generic_variables.len(),
+        ),
+        (
+            "parameters",
+            trait_symbols.input_variables.len(),
+            impl_symbols.input_variables.len(),
+        ),
+    ] {
+        if expected != found {
+            Diagnostic::error(
+                db,
+                impl_method.name_span(db),
+                format!("expected {expected} {what}, found {found}"),
+            )
+            .label(
+                db,
+                Level::Error,
+                impl_method.name_span(db),
+                format!("the trait `{sym_trait}` declares `{name}` with {expected} {what}, but I found {found}"),
+            )
+            .label(
+                db,
+                Level::Info,
+                trait_method.name_span(db),
+                format!("`{name}` is declared in the trait `{sym_trait}` here"),
+            )
+            .report(db);
+        }
+    }
+}
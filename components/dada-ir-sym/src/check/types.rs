@@ -9,12 +9,14 @@ use crate::{
     check::{
         env::Env,
         exprs::ExprResultKind,
+        places::PlaceTy,
         scope::{NameResolution, NameResolutionSym, Resolve},
+        to_red::ToRedTy,
     },
     ir::{
         types::{
             AnonymousPermSymbol, HasKind, SymGenericKind, SymGenericTerm, SymPerm, SymPermKind,
-            SymPlace, SymTy,
+            SymPlace, SymPlaceKind, SymTy,
         },
         variables::FromVar,
     },
@@ -217,6 +219,20 @@ fn name_resolution_to_sym_ty<'db>(
                 )
                 .report(db),
         ),
+        NameResolutionSym::SymTrait(sym_trait) => SymTy::err(
+            db,
+            Diagnostic::error(db, source.span(db), "traits are not valid types")
+                .label(
+                    db,
+                    Level::Error,
+                    source.span(db),
+                    format!(
+                        "I expected a type here, but `{}` is a trait",
+                        sym_trait.name(db)
+                    ),
+                )
+                .report(db),
+        ),
         NameResolutionSym::SymFunction(sym_function) => SymTy::err(
             db,
             Diagnostic::error(db, source.span(db), "modules are not valid types")
@@ -279,7 +295,10 @@ impl<'db> CheckTyInEnv<'db> for AstPerm<'db> {
                 let places = paths_to_sym_places(env, paths).await;
                 SymPerm::new(db, SymPermKind::Mutable(places))
             }
-            AstPermKind::Given(Some(ref _span_vec)) => todo!(),
+            AstPermKind::Given(Some(ref paths)) => {
+                let places = paths_to_sym_places(env, paths).await;
+                given_places_to_sym_perm(env, places).await
+            }
             AstPermKind::Referenced(None)
             | AstPermKind::Mutable(None)
             | AstPermKind::Given(None) => {
@@ -314,6 +333,7 @@ fn name_resolution_to_sym_perm<'db>(
 
         NameResolutionSym::SymModule(_)
         | NameResolutionSym::SymAggregate(_)
+        | NameResolutionSym::SymTrait(_)
         | NameResolutionSym::SymFunction(_)
         | NameResolutionSym::SymVariable(_)
         | NameResolutionSym::SymPrimitive(_) => SymPerm::err(
@@ -351,6 +371,31 @@ async fn paths_to_sym_places<'db>(
     places
 }
 
+/// `given[p]` is the permission that was given to the place `p`,
+/// so we desugar it to the permission from the type of `p`.
+/// With multiple places (`given[p, q]`), the permission may have come from any of them,
+/// so we desugar to `P | Q`. Because this happens before subtyping or the red-perm
+/// lattice ever see the permission, they need no special rules for `given`.
+async fn given_places_to_sym_perm<'db>(
+    env: &mut Env<'db>,
+    places: Vec<SymPlace<'db>>,
+) -> SymPerm<'db> {
+    let db = env.db();
+    let mut perms = vec![];
+    for place in places {
+        if let SymPlaceKind::Error(reported) = *place.kind(db) {
+            return SymPerm::err(db, reported);
+        }
+        let ty = place.place_ty(env).await;
+        let (_, perm) = ty.to_red_ty(env);
+        perms.push(perm);
+    }
+    perms
+        .into_iter()
+        .reduce(|perm1, perm2| SymPerm::or(db, perm1, perm2))
+        .unwrap_or_else(|| SymPerm::my(db))
+}
+
 async fn path_to_sym_place<'db>(env: &mut Env<'db>, path: AstPath<'db>) -> SymPlace<'db> {
     let db = env.db();
     let ExprResult {
@@ -12,5 +12,6 @@ pub mod module;
 pub(crate) mod populate;
 pub mod primitive;
 pub mod subst;
+pub mod traits;
 pub mod types;
 pub mod variables;
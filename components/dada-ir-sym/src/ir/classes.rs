@@ -1,7 +1,10 @@
 use std::borrow::Cow;
 
 use dada_ir_ast::{
-    ast::{AstAggregate, AstAggregateKind, AstFieldDecl, AstMember, Identifier, SpannedIdentifier},
+    ast::{
+        AstAggregate, AstAggregateKind, AstFieldDecl, AstMember, AstVariant, Identifier,
+        SpannedIdentifier,
+    },
     span::{SourceSpanned, Span, Spanned},
 };
 use dada_parser::prelude::*;
@@ -41,10 +44,17 @@ impl<'db> SymAggregate<'db> {
     pub fn style(self, db: &'db dyn crate::Db) -> SymAggregateStyle {
         match self.source(db).kind(db) {
             AstAggregateKind::Class => SymAggregateStyle::Class,
-            AstAggregateKind::Struct => SymAggregateStyle::Struct,
+
+            // Enums are value types, just like structs.
+            AstAggregateKind::Struct | AstAggregateKind::Enum => SymAggregateStyle::Struct,
         }
     }
 
+    /// True if this is an enum
+    pub fn is_enum(self, db: &'db dyn crate::Db) -> bool {
+        self.source(db).kind(db) == AstAggregateKind::Enum
+    }
+
     /// True if this is a struct
     pub fn is_struct(self, db: &'db dyn crate::Db) -> bool {
         self.style(db) == SymAggregateStyle::Struct
@@ -189,11 +199,40 @@ impl<'db> SymAggregate<'db> {
             AstMember::Function(ast_function) => {
                 SymFunction::new(db, self.into(), ast_function.into()).into()
             }
+            AstMember::Variant(ast_variant) => {
+                let variant = self
+                    .variants(db)
+                    .iter()
+                    .copied()
+                    .find(|v| v.source(db) == ast_variant)
+                    .unwrap();
+                SymFunction::new(
+                    db,
+                    self.into(),
+                    SymFunctionSource::VariantConstructor(variant),
+                )
+                .into()
+            }
         });
 
         ctor_members.chain(explicit_members).collect()
     }
 
+    /// Tracked list of enum variants (empty for classes and structs).
+    #[salsa::tracked(return_ref)]
+    pub fn variants(self, db: &'db dyn crate::Db) -> Vec<SymVariant<'db>> {
+        self.source(db)
+            .members(db)
+            .iter()
+            .filter_map(|m| match *m {
+                AstMember::Variant(ast_variant) => Some(ast_variant),
+                AstMember::Field(_) | AstMember::Function(_) => None,
+            })
+            .enumerate()
+            .map(|(index, ast_variant)| SymVariant::new(db, self, index, ast_variant))
+            .collect()
+    }
+
     /// Returns the member with the given name, if it exists.
     #[salsa::tracked]
     pub fn inherent_member(
@@ -320,6 +359,70 @@ impl<'db> SourceSpanned<'db> for SymClassMember<'db> {
     }
 }
 
+/// Symbol for a variant of an enum.
+/// Each variant also has a constructor function with the same name.
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct SymVariant<'db> {
+    /// The enum in which this variant is declared.
+    pub aggregate: SymAggregate<'db>,
+
+    /// Position of this variant in the enum's list of variants.
+    /// Used as the tag that identifies the variant at runtime.
+    pub index: usize,
+
+    /// AST for variant declaration
+    pub source: AstVariant<'db>,
+}
+
+#[salsa::tracked]
+impl<'db> SymVariant<'db> {
+    /// Name of the variant.
+    pub fn name(self, db: &'db dyn crate::Db) -> Identifier<'db> {
+        self.source(db).name(db).id
+    }
+
+    /// Span of the variant name.
+    pub fn name_span(self, db: &'db dyn crate::Db) -> Span<'db> {
+        self.source(db).name(db).span
+    }
+
+    /// Tracked list of the fields in the variant's payload.
+    #[salsa::tracked(return_ref)]
+    pub fn fields(self, db: &'db dyn crate::Db) -> Vec<SymField<'db>> {
+        self.source(db)
+            .fields(db)
+            .iter()
+            .flatten()
+            .map(|field_decl| {
+                let SpannedIdentifier { span, id } = field_decl.variable(db).name(db);
+                SymField::new(db, self.aggregate(db).into(), id, span, *field_decl)
+            })
+            .collect()
+    }
+}
+
+impl<'db> Spanned<'db> for SymVariant<'db> {
+    fn span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).name(db).span
+    }
+}
+
+impl<'db> SourceSpanned<'db> for SymVariant<'db> {
+    fn source_span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).span(db)
+    }
+}
+
+impl std::fmt::Display for SymVariant<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| {
+            write!(f, "{}.{}", self.aggregate(db).name(db), self.name(db))
+        })
+        .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
 /// Symbol for a field of a class, struct, or enum
 #[derive(SalsaSerialize)]
 #[salsa::tracked(debug)]
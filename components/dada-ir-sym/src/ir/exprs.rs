@@ -14,7 +14,7 @@
 //! The object IR gives us enough information to make those determinations.
 
 use crate::{
-    ir::classes::SymField,
+    ir::classes::{SymField, SymVariant},
     ir::functions::SymFunction,
     ir::types::{SymGenericTerm, SymPlace, SymTy},
     ir::variables::{FromVar, SymVariable},
@@ -164,6 +164,12 @@ pub enum SymExprKind<'db> {
         op_span: Span<'db>,
     },
 
+    /// Arithmetic negation, `-x`
+    Negate {
+        operand: SymExpr<'db>,
+        op_span: Span<'db>,
+    },
+
     /// `a + b` etc
     BinaryOp(SymBinaryOp, SymExpr<'db>, SymExpr<'db>),
 
@@ -173,9 +179,33 @@ pub enum SymExprKind<'db> {
         fields: Vec<SymExpr<'db>>,
     },
 
+    /// Construct an instance of the enum `ty` using the given variant and payload
+    Variant {
+        ty: SymTy<'db>,
+        variant: SymVariant<'db>,
+        fields: Vec<SymExpr<'db>>,
+    },
+
+    /// True if the enum stored in `place` is an instance of `variant`.
+    /// Used when desugaring `match` expressions.
+    IsVariant {
+        place: SymPlaceExpr<'db>,
+        variant: SymVariant<'db>,
+    },
+
     /// Match, if/else-if chain, etc
     Match { arms: Vec<SymMatchArm<'db>> },
 
+    /// `loop { $body }`: evaluates `$body` repeatedly until a `break` is reached.
+    /// `while` loops are desugared into a `loop` by the type checker.
+    Loop { body: SymExpr<'db> },
+
+    /// Exit the innermost enclosing loop
+    Break,
+
+    /// Start the next iteration of the innermost enclosing loop
+    Continue,
+
     /// Error occurred somewhere.
     Error(Reported),
 }
@@ -208,11 +238,18 @@ pub enum SymBinaryOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     GreaterThan,
     LessThan,
     GreaterEqual,
     LessEqual,
     EqualEqual,
+    NotEqual,
 }
 
 impl TryFrom<AstBinaryOp> for SymBinaryOp {
@@ -224,11 +261,18 @@ impl TryFrom<AstBinaryOp> for SymBinaryOp {
             AstBinaryOp::Sub => Ok(SymBinaryOp::Sub),
             AstBinaryOp::Mul => Ok(SymBinaryOp::Mul),
             AstBinaryOp::Div => Ok(SymBinaryOp::Div),
+            AstBinaryOp::Rem => Ok(SymBinaryOp::Rem),
+            AstBinaryOp::BitAnd => Ok(SymBinaryOp::BitAnd),
+            AstBinaryOp::BitOr => Ok(SymBinaryOp::BitOr),
+            AstBinaryOp::BitXor => Ok(SymBinaryOp::BitXor),
+            AstBinaryOp::Shl => Ok(SymBinaryOp::Shl),
+            AstBinaryOp::Shr => Ok(SymBinaryOp::Shr),
             AstBinaryOp::GreaterThan => Ok(SymBinaryOp::GreaterThan),
             AstBinaryOp::LessThan => Ok(SymBinaryOp::LessThan),
             AstBinaryOp::GreaterEqual => Ok(SymBinaryOp::GreaterEqual),
             AstBinaryOp::LessEqual => Ok(SymBinaryOp::LessEqual),
             AstBinaryOp::EqualEqual => Ok(SymBinaryOp::EqualEqual),
+            AstBinaryOp::NotEqual => Ok(SymBinaryOp::NotEqual),
             AstBinaryOp::AndAnd | AstBinaryOp::OrOr | AstBinaryOp::Assign => {
                 dada_util::bail!("no equivalent object binary op")
             }
@@ -239,7 +283,8 @@ impl TryFrom<AstBinaryOp> for SymBinaryOp {
 /// A match arm is one part of a match statement.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Update, Serialize)]
 pub struct SymMatchArm<'db> {
-    // FIXME: patterns
+    // Patterns from `match` are desugared into a condition (see [`SymExprKind::IsVariant`])
+    // and `let` bindings within the body.
     /// Condition to evaluate; if `None` then it always applies
     pub condition: Option<SymExpr<'db>>,
 
@@ -282,6 +327,7 @@ impl<'db> SymPlaceExpr<'db> {
         match *self.kind(db) {
             SymPlaceExprKind::Var(lv) => SymPlace::var(db, lv),
             SymPlaceExprKind::Field(place, field) => place.into_sym_place(db).field(db, field),
+            SymPlaceExprKind::Index(place, _) => place.into_sym_place(db).index(db),
             SymPlaceExprKind::Error(r) => SymPlace::err(db, r),
         }
     }
@@ -291,5 +337,9 @@ impl<'db> SymPlaceExpr<'db> {
 pub enum SymPlaceExprKind<'db> {
     Var(SymVariable<'db>),
     Field(SymPlaceExpr<'db>, SymField<'db>),
+
+    /// `owner[index]`, where the index has been stored in the given temporary.
+    Index(SymPlaceExpr<'db>, SymVariable<'db>),
+
     Error(Reported),
 }
@@ -18,8 +18,9 @@ use crate::{
     },
     ir::{
         binder::{Binder, LeafBoundTerm},
-        classes::SymAggregate,
+        classes::{SymAggregate, SymVariant},
         populate::{PopulateDefaultSymbols, PopulateSignatureSymbols},
+        traits::{SymImpl, SymTrait},
         types::SymTy,
         variables::SymVariable,
     },
@@ -47,6 +48,7 @@ impl<'db> SymFunction<'db> {
         let source = self.source(db).effects(db);
         SymFunctionEffects {
             async_effect: source.async_effect.is_some(),
+            unsafe_effect: source.unsafe_effect.is_some(),
         }
     }
 }
@@ -72,6 +74,9 @@ impl<'db> ScopeTreeNode<'db> for SymFunction<'db> {
         let wc = match self.source(db) {
             SymFunctionSource::Function(ast) => ast.where_clauses(db),
             SymFunctionSource::Constructor(_, ast) => ast.where_clauses(db),
+            SymFunctionSource::VariantConstructor(variant) => {
+                variant.aggregate(db).source(db).where_clauses(db)
+            }
             SymFunctionSource::MainFunction(_) => &None,
         };
 
@@ -140,6 +145,14 @@ impl<'db> SymFunction<'db> {
         let symbols = self.symbols(db);
         self.scope_from_symbols(db, symbols)
     }
+
+    /// If this function is a method declared in a trait, returns the trait.
+    pub fn owning_trait(self, db: &'db dyn crate::Db) -> Option<SymTrait<'db>> {
+        match self.super_scope_item(db) {
+            ScopeItem::Trait(sym_trait) => Some(sym_trait),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Update, FromImpls, Serialize)]
@@ -152,13 +165,19 @@ pub enum SymFunctionSource<'db> {
     /// Generated constructor from an aggregate like `struct Foo(x: u32)`
     #[no_from_impl] // I'd prefer to be explicit
     Constructor(SymAggregate<'db>, AstAggregate<'db>),
+
+    /// Generated constructor for an enum variant like `Circle(radius: u32)`
+    #[no_from_impl]
+    VariantConstructor(SymVariant<'db>),
 }
 
 impl<'db> SymFunctionSource<'db> {
     fn effects(self, db: &'db dyn crate::Db) -> AstFunctionEffects<'db> {
         match self {
             Self::Function(ast_function) => ast_function.effects(db),
-            Self::MainFunction(_) | Self::Constructor(..) => AstFunctionEffects::default(),
+            Self::MainFunction(_) | Self::Constructor(..) | Self::VariantConstructor(_) => {
+                AstFunctionEffects::default()
+            }
         }
     }
 
@@ -169,6 +188,7 @@ impl<'db> SymFunctionSource<'db> {
                 span: class.name_span(db),
                 id: Identifier::new_ident(db),
             },
+            Self::VariantConstructor(variant) => variant.source(db).name(db),
             Self::MainFunction(mfunc) => SpannedIdentifier {
                 span: mfunc.statements(db).span,
                 id: Identifier::main(db),
@@ -188,6 +208,15 @@ impl<'db> SymFunctionSource<'db> {
                     .map(|i| i.variable(db).into())
                     .collect::<Vec<_>>(),
             ),
+            Self::VariantConstructor(variant) => Cow::Owned(
+                variant
+                    .source(db)
+                    .fields(db)
+                    .iter()
+                    .flatten()
+                    .map(|i| i.variable(db).into())
+                    .collect::<Vec<_>>(),
+            ),
             Self::MainFunction(_) => Cow::Borrowed(&[]),
         }
     }
@@ -198,6 +227,7 @@ impl<'db> SourceSpanned<'db> for SymFunctionSource<'db> {
         match self {
             SymFunctionSource::Function(ast_function) => ast_function.span(db),
             SymFunctionSource::Constructor(_, ast_aggregate) => ast_aggregate.span(db),
+            SymFunctionSource::VariantConstructor(variant) => variant.source(db).span(db),
             SymFunctionSource::MainFunction(mfunc) => mfunc.span(db),
         }
     }
@@ -207,6 +237,7 @@ impl<'db> SourceSpanned<'db> for SymFunctionSource<'db> {
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct SymFunctionEffects {
     pub async_effect: bool,
+    pub unsafe_effect: bool,
 }
 
 #[derive(SalsaSerialize)]
@@ -272,6 +303,8 @@ impl<'db> SignatureSymbols<'db> {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Update, FromImpls, Serialize)]
 pub enum SignatureSource<'db> {
     Class(SymAggregate<'db>),
+    Trait(SymTrait<'db>),
+    Impl(SymImpl<'db>),
     Function(SymFunction<'db>),
 }
 
@@ -279,7 +312,9 @@ impl<'db> SignatureSource<'db> {
     pub fn aggr_style(self, db: &'db dyn crate::Db) -> Option<SymAggregateStyle> {
         match self {
             SignatureSource::Class(aggr) => Some(aggr.style(db)),
-            SignatureSource::Function(_) => None,
+            SignatureSource::Trait(_) | SignatureSource::Impl(_) | SignatureSource::Function(_) => {
+                None
+            }
         }
     }
 }
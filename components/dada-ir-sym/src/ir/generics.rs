@@ -2,19 +2,41 @@ use dada_util::SalsaSerialize;
 use salsa::Update;
 use serde::Serialize;
 
-use super::types::SymGenericTerm;
+use super::{traits::SymTraitRef, types::SymGenericTerm};
 
 #[derive(SalsaSerialize)]
 #[salsa::interned(debug)]
 pub struct SymWhereClause<'db> {
     pub subject: SymGenericTerm<'db>,
-    pub kind: SymWhereClauseKind,
+    pub kind: SymWhereClauseKind<'db>,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
-pub enum SymWhereClauseKind {
+pub enum SymWhereClauseKind<'db> {
     Unique,
     Shared,
     Owned,
     Lent,
+
+    /// The subject implements the given trait
+    Trait(SymTraitRef<'db>),
+}
+
+impl std::fmt::Display for SymWhereClause<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| write!(f, "{} is {}", self.subject(db), self.kind(db)))
+            .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
+impl std::fmt::Display for SymWhereClauseKind<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymWhereClauseKind::Unique => write!(f, "unique"),
+            SymWhereClauseKind::Shared => write!(f, "shared"),
+            SymWhereClauseKind::Owned => write!(f, "owned"),
+            SymWhereClauseKind::Lent => write!(f, "lent"),
+            SymWhereClauseKind::Trait(trait_ref) => write!(f, "{trait_ref}"),
+        }
+    }
 }
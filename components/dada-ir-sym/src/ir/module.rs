@@ -1,6 +1,6 @@
 use dada_ir_ast::{
     ast::{AstItem, AstModule, AstUse, Identifier},
-    diagnostic::{Diagnostic, Level},
+    diagnostic::{Diagnostic, DiagnosticCode, Level},
     inputs::SourceFile,
     span::{SourceSpanned, Span, Spanned},
 };
@@ -13,7 +13,10 @@ use crate::{
         scope_tree::{ScopeItem, ScopeTreeNode},
     },
     ir::{
-        classes::SymAggregate, functions::SymFunction, primitive::SymPrimitive,
+        classes::SymAggregate,
+        functions::SymFunction,
+        primitive::SymPrimitive,
+        traits::{SymImpl, SymTrait},
         variables::SymVariable,
     },
     prelude::Symbol,
@@ -31,10 +34,18 @@ pub struct SymModule<'db> {
     pub(crate) class_map: Map<Identifier<'db>, SymAggregate<'db>>,
     #[tracked]
     #[return_ref]
+    pub(crate) trait_map: Map<Identifier<'db>, SymTrait<'db>>,
+    #[tracked]
+    #[return_ref]
     pub(crate) function_map: Map<Identifier<'db>, SymFunction<'db>>,
     #[tracked]
     #[return_ref]
     pub(crate) ast_use_map: Map<Identifier<'db>, AstUse<'db>>,
+
+    /// Impls declared in this module; these have no name.
+    #[tracked]
+    #[return_ref]
+    pub impls: Vec<SymImpl<'db>>,
 }
 
 impl<'db> Spanned<'db> for SymModule<'db> {
@@ -66,9 +77,22 @@ impl<'db> SymModule<'db> {
             .values()
             .copied()
             .map(SymItem::from)
+            .chain(self.trait_map(db).values().copied().map(SymItem::from))
             .chain(self.function_map(db).values().copied().map(SymItem::from))
     }
 
+    /// Returns the impls in this module whose self type is `aggr`.
+    pub fn impls_for(
+        self,
+        db: &'db dyn crate::Db,
+        aggr: SymAggregate<'db>,
+    ) -> impl Iterator<Item = SymImpl<'db>> {
+        self.impls(db)
+            .iter()
+            .copied()
+            .filter(move |i| i.self_aggregate(db) == Some(aggr))
+    }
+
     /// Returns the function named `name` in this module, if any.
     pub fn function_named(
         self,
@@ -118,8 +142,10 @@ impl<'db> Symbol<'db> for AstModule<'db> {
     #[salsa::tracked]
     fn symbol(self, db: &'db dyn crate::Db) -> SymModule<'db> {
         let mut class_map = Map::default();
+        let mut trait_map = Map::default();
         let mut function_map = Map::default();
         let mut ast_use_map = Map::default();
+        let mut impls = vec![];
         for item in self.items(db) {
             match *item {
                 AstItem::SourceFile(_) => {}
@@ -139,6 +165,17 @@ impl<'db> Symbol<'db> for AstModule<'db> {
                         SymAggregate::new(db, self.into(), ast_class_item),
                     );
                 }
+                AstItem::Trait(ast_trait) => {
+                    insert(
+                        db,
+                        &mut trait_map,
+                        ast_trait.name(db),
+                        SymTrait::new(db, self.into(), ast_trait),
+                    );
+                }
+                AstItem::Impl(ast_impl) => {
+                    impls.push(SymImpl::new(db, self.into(), ast_impl));
+                }
                 AstItem::Function(func) => {
                     insert(
                         db,
@@ -162,10 +199,19 @@ impl<'db> Symbol<'db> for AstModule<'db> {
         // when resolving names, we prefer the maps that come earlier in this list.
         let canonical_map = &mut Map::default();
         insert_into_canonical_map(db, canonical_map, &class_map);
+        insert_into_canonical_map(db, canonical_map, &trait_map);
         insert_into_canonical_map(db, canonical_map, &function_map);
         insert_into_canonical_map(db, canonical_map, &ast_use_map);
 
-        SymModule::new(db, self, class_map, function_map, ast_use_map)
+        SymModule::new(
+            db,
+            self,
+            class_map,
+            trait_map,
+            function_map,
+            ast_use_map,
+            impls,
+        )
     }
 }
 
@@ -231,6 +277,7 @@ fn report_duplicate<'db>(
         value_span,
         "this definition is a duplicate and will be ignored",
     )
+    .code(DiagnosticCode::DuplicateDefinition)
     .label(db, Level::Error, value_span, "duplicate definition")
     .label(
         db,
@@ -244,6 +291,7 @@ fn report_duplicate<'db>(
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, FromImpls)]
 pub enum SymItem<'db> {
     SymClass(SymAggregate<'db>),
+    SymTrait(SymTrait<'db>),
     SymFunction(SymFunction<'db>),
     SymPrimitive(SymPrimitive<'db>),
 }
@@ -252,6 +300,7 @@ impl<'db> SymItem<'db> {
     pub fn name(self, db: &'db dyn crate::Db) -> Identifier<'db> {
         match self {
             SymItem::SymClass(sym_class) => sym_class.name(db),
+            SymItem::SymTrait(sym_trait) => sym_trait.name(db),
             SymItem::SymFunction(sym_function) => sym_function.name(db),
             SymItem::SymPrimitive(sym_primitive) => sym_primitive.name(db),
         }
@@ -262,6 +311,7 @@ impl<'db> Spanned<'db> for SymItem<'db> {
     fn span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
         match self {
             SymItem::SymClass(sym_class) => sym_class.span(db),
+            SymItem::SymTrait(sym_trait) => sym_trait.span(db),
             SymItem::SymFunction(sym_function) => sym_function.span(db),
             SymItem::SymPrimitive(_) => well_known::prelude_span(db),
         }
@@ -272,6 +322,7 @@ impl<'db> SourceSpanned<'db> for SymItem<'db> {
     fn source_span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
         match self {
             SymItem::SymClass(a) => a.source_span(db),
+            SymItem::SymTrait(t) => t.source_span(db),
             SymItem::SymFunction(f) => f.source_span(db),
             SymItem::SymPrimitive(_) => well_known::prelude_span(db),
         }
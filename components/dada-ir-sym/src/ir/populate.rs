@@ -1,6 +1,6 @@
 use dada_ir_ast::ast::{
-    AstAggregate, AstFunction, AstFunctionInput, AstGenericDecl, AstGenericTerm, AstPerm,
-    AstPermKind, AstSelfArg, AstTy, AstTyKind, VariableDecl,
+    AstAggregate, AstFunction, AstFunctionInput, AstGenericDecl, AstGenericTerm, AstImpl, AstPath,
+    AstPathKind, AstPerm, AstPermKind, AstSelfArg, AstTy, AstTyKind, VariableDecl,
 };
 
 use crate::{
@@ -185,6 +185,39 @@ impl<'db> PopulateSignatureSymbols<'db> for AstAggregate<'db> {
     }
 }
 
+impl<'db> PopulateSignatureSymbols<'db> for AstImpl<'db> {
+    fn populate_signature_symbols(
+        &self,
+        db: &'db dyn crate::Db,
+        symbols: &mut SignatureSymbols<'db>,
+    ) {
+        self.generics(db)
+            .iter()
+            .flatten()
+            .for_each(|g| g.populate_signature_symbols(db, symbols));
+        self.trait_path(db).populate_signature_symbols(db, symbols);
+        self.self_ty(db).populate_signature_symbols(db, symbols);
+    }
+}
+
+impl<'db> PopulateSignatureSymbols<'db> for AstPath<'db> {
+    fn populate_signature_symbols(
+        &self,
+        db: &'db dyn crate::Db,
+        symbols: &mut SignatureSymbols<'db>,
+    ) {
+        match self.kind(db) {
+            AstPathKind::Identifier(_) => {}
+            AstPathKind::GenericArgs { path, args } => {
+                path.populate_signature_symbols(db, symbols);
+                args.iter()
+                    .for_each(|a| a.populate_signature_symbols(db, symbols));
+            }
+            AstPathKind::Member { path, id: _ } => path.populate_signature_symbols(db, symbols),
+        }
+    }
+}
+
 impl<'db> PopulateSignatureSymbols<'db> for SymFunctionSource<'db> {
     fn populate_signature_symbols(
         &self,
@@ -193,7 +226,7 @@ impl<'db> PopulateSignatureSymbols<'db> for SymFunctionSource<'db> {
     ) {
         match self {
             Self::Function(ast_function) => ast_function.populate_signature_symbols(db, symbols),
-            Self::Constructor(..) => {
+            Self::Constructor(..) | Self::VariantConstructor(_) => {
                 self.inputs(db)
                     .iter()
                     .for_each(|i| i.populate_signature_symbols(db, symbols));
@@ -337,7 +370,7 @@ impl<'db> PopulateDefaultSymbols<'db> for SymFunctionSource<'db> {
             Self::Function(ast_function) => {
                 ast_function.populate_default_symbols(db, scope, symbols)
             }
-            Self::Constructor(..) => {
+            Self::Constructor(..) | Self::VariantConstructor(_) => {
                 self.inputs(db)
                     .iter()
                     .for_each(|i| i.populate_default_symbols(db, scope, symbols));
@@ -14,10 +14,11 @@ use crate::{
 };
 
 use super::{
-    classes::SymField,
+    classes::{SymField, SymVariant},
     functions::SymFunction,
     generics::{SymWhereClause, SymWhereClauseKind},
     indices::InferVarIndex,
+    traits::{SymImplHeader, SymTrait, SymTraitRef},
 };
 
 pub struct SubstitutionFns<'s, 'db, Term> {
@@ -392,6 +393,62 @@ impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymWhereClause<'db> {
     }
 }
 
+impl<'db> Subst<'db> for SymWhereClauseKind<'db> {
+    type GenericTerm = SymGenericTerm<'db>;
+}
+
+impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymWhereClauseKind<'db> {
+    type Output = SymWhereClauseKind<'db>;
+
+    fn identity(&self) -> Self::Output {
+        *self
+    }
+
+    fn subst_with<'subst>(
+        &'subst self,
+        db: &'db dyn crate::Db,
+        bound_vars: &mut Vec<SymVariable<'db>>,
+        subst_fns: &mut SubstitutionFns<'_, 'db, SymGenericTerm<'db>>,
+    ) -> Self::Output {
+        match self {
+            SymWhereClauseKind::Unique
+            | SymWhereClauseKind::Shared
+            | SymWhereClauseKind::Owned
+            | SymWhereClauseKind::Lent => *self,
+            SymWhereClauseKind::Trait(trait_ref) => {
+                SymWhereClauseKind::Trait(trait_ref.subst_with(db, bound_vars, subst_fns))
+            }
+        }
+    }
+}
+
+impl<'db> Subst<'db> for SymTraitRef<'db> {
+    type GenericTerm = SymGenericTerm<'db>;
+}
+
+impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymTraitRef<'db> {
+    type Output = SymTraitRef<'db>;
+
+    fn identity(&self) -> Self::Output {
+        *self
+    }
+
+    fn subst_with<'subst>(
+        &'subst self,
+        db: &'db dyn crate::Db,
+        bound_vars: &mut Vec<SymVariable<'db>>,
+        subst_fns: &mut SubstitutionFns<'_, 'db, SymGenericTerm<'db>>,
+    ) -> Self::Output {
+        SymTraitRef::new(
+            db,
+            self.trait_(db),
+            self.args(db).subst_with(db, bound_vars, subst_fns),
+            self.associated_tys(db)
+                .subst_with(db, bound_vars, subst_fns),
+        )
+    }
+}
+
 impl<'db, T: BoundTerm<'db>> Subst<'db> for Binder<'db, T>
 where
     T::Output: BoundTerm<'db>,
@@ -476,6 +533,31 @@ impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymInputOutput<'db> {
     }
 }
 
+impl<'db> Subst<'db> for SymImplHeader<'db> {
+    type GenericTerm = SymGenericTerm<'db>;
+}
+
+impl<'db> SubstWith<'db, SymGenericTerm<'db>> for SymImplHeader<'db> {
+    type Output = Self;
+
+    fn identity(&self) -> Self::Output {
+        self.clone()
+    }
+
+    fn subst_with<'subst>(
+        &'subst self,
+        db: &'db dyn crate::Db,
+        bound_vars: &mut Vec<SymVariable<'db>>,
+        subst_fns: &mut SubstitutionFns<'_, 'db, SymGenericTerm<'db>>,
+    ) -> Self::Output {
+        SymImplHeader {
+            trait_ref: self.trait_ref.subst_with(db, bound_vars, subst_fns),
+            self_ty: self.self_ty.subst_with(db, bound_vars, subst_fns),
+            associated_tys: self.associated_tys.subst_with(db, bound_vars, subst_fns),
+        }
+    }
+}
+
 impl<'db, T> Subst<'db> for Vec<T>
 where
     T: Subst<'db>,
@@ -563,7 +645,8 @@ identity_subst! {
         Span<'db>,
         SymFunction<'db>,
         SymField<'db>,
-        SymWhereClauseKind,
+        SymVariant<'db>,
+        SymTrait<'db>,
     }
 }
 
@@ -0,0 +1,586 @@
+use std::borrow::Cow;
+
+use dada_ir_ast::{
+    ast::{
+        AstAssociatedTy, AstImpl, AstTrait, AstTraitMember, AstTyKind, Identifier,
+        SpannedIdentifier,
+    },
+    diagnostic::{Diagnostic, Errors, Level},
+    span::{SourceSpanned, Span, Spanned},
+};
+use dada_parser::prelude::*;
+use dada_util::{Map, SalsaSerialize};
+use salsa::Update;
+use serde::Serialize;
+
+use crate::{
+    check::{
+        scope::{NameResolutionSym, ResolveToSym, Scope},
+        scope_tree::{ScopeItem, ScopeTreeNode},
+    },
+    ir::{
+        binder::LeafBoundTerm,
+        classes::SymAggregate,
+        functions::{SignatureSymbols, SymFunction},
+        populate::PopulateSignatureSymbols,
+        types::{
+            SymGenericKind, SymGenericTerm, SymPerm, SymPermKind, SymPlace, SymTy, SymTyKind,
+            SymTyName,
+        },
+        variables::{FromVar, SymVariable},
+    },
+    prelude::{CheckedImplHeader, Symbol},
+};
+
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct SymTrait<'db> {
+    /// The scope in which this trait is declared.
+    super_scope: ScopeItem<'db>,
+
+    /// The AST for this trait.
+    pub source: AstTrait<'db>,
+}
+
+#[salsa::tracked]
+impl<'db> SymTrait<'db> {
+    /// Name of the trait.
+    pub fn name(&self, db: &'db dyn salsa::Database) -> Identifier<'db> {
+        self.source(db).name(db)
+    }
+
+    /// Span of the trait name, typically used in diagnostics.
+    /// Also returned by the [`Spanned`][] impl.
+    pub fn name_span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).name_span(db)
+    }
+
+    /// Number of generic parameters declared by the user.
+    /// This does not include `Self` nor the associated types.
+    pub fn len_generics(&self, db: &'db dyn crate::Db) -> usize {
+        if let Some(generics) = self.source(db).generics(db) {
+            generics.len()
+        } else {
+            0
+        }
+    }
+
+    /// Kinds of the generic parameters declared by the user.
+    pub fn generic_kinds(
+        &self,
+        db: &'db dyn crate::Db,
+    ) -> impl Iterator<Item = SymGenericKind> + 'db {
+        self.source(db)
+            .generics(db)
+            .iter()
+            .flatten()
+            .map(move |decl| decl.kind(db).symbol(db))
+    }
+
+    /// Span where generics are declared (possibly the name span, if there are no generics)
+    pub fn generics_span(&self, db: &'db dyn crate::Db) -> Span<'db> {
+        if let Some(generics) = self.source(db).generics(db) {
+            generics.span
+        } else {
+            self.name_span(db)
+        }
+    }
+
+    /// The variable representing the `Self` type within the trait.
+    #[salsa::tracked]
+    pub fn self_var(self, db: &'db dyn crate::Db) -> SymVariable<'db> {
+        SymVariable::new(
+            db,
+            SymGenericKind::Type,
+            Some(Identifier::self_ty_ident(db)),
+            self.name_span(db),
+        )
+    }
+
+    /// Returns the symbols for this trait header. The generic variables are,
+    /// in order, `Self`, the generics declared by the user, and the associated types.
+    #[salsa::tracked(return_ref)]
+    pub(crate) fn symbols(self, db: &'db dyn crate::Db) -> SignatureSymbols<'db> {
+        let mut signature_symbols = SignatureSymbols::new(self);
+        signature_symbols.generic_variables.push(self.self_var(db));
+        self.source(db)
+            .generics(db)
+            .iter()
+            .flatten()
+            .for_each(|g| g.populate_signature_symbols(db, &mut signature_symbols));
+        signature_symbols
+            .generic_variables
+            .extend(self.associated_tys(db).map(|a| a.symbol(db)));
+        signature_symbols
+    }
+
+    /// Variables for the generic parameters declared by the user.
+    pub fn declared_generic_vars(self, db: &'db dyn crate::Db) -> &'db [SymVariable<'db>] {
+        &self.symbols(db).generic_variables[1..1 + self.len_generics(db)]
+    }
+
+    /// Variables for the associated types, in the order they are declared.
+    pub fn associated_ty_vars(self, db: &'db dyn crate::Db) -> &'db [SymVariable<'db>] {
+        &self.symbols(db).generic_variables[1 + self.len_generics(db)..]
+    }
+
+    /// Associated types declared in this trait.
+    pub fn associated_tys(
+        self,
+        db: &'db dyn crate::Db,
+    ) -> impl Iterator<Item = AstAssociatedTy<'db>> + 'db {
+        self.source(db).members(db).iter().filter_map(|m| match *m {
+            AstTraitMember::AssociatedTy(a) => Some(a),
+            AstTraitMember::Function(_) => None,
+        })
+    }
+
+    /// Returns the variable for the associated type named `id`, if any.
+    pub fn associated_ty_named(
+        self,
+        db: &'db dyn crate::Db,
+        id: Identifier<'db>,
+    ) -> Option<SymVariable<'db>> {
+        self.associated_ty_vars(db)
+            .iter()
+            .copied()
+            .find(|v| v.name(db) == Some(id))
+    }
+
+    /// Returns the base scope used to resolve the trait members.
+    pub(crate) fn trait_scope(self, db: &'db dyn crate::Db) -> Scope<'db, 'db> {
+        let symbols = self.symbols(db);
+        assert!(symbols.input_variables.is_empty());
+        self.super_scope(db)
+            .into_scope(db)
+            .with_link(self)
+            .with_link(Cow::Borrowed(&symbols.generic_variables[..]))
+    }
+
+    /// Tracked list of trait methods.
+    #[salsa::tracked(return_ref)]
+    pub fn methods(self, db: &'db dyn crate::Db) -> Vec<SymFunction<'db>> {
+        self.source(db)
+            .members(db)
+            .iter()
+            .filter_map(|m| match *m {
+                AstTraitMember::Function(f) => Some(SymFunction::new(db, self.into(), f.into())),
+                AstTraitMember::AssociatedTy(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the method with the given name, if it exists.
+    pub fn method_named(
+        self,
+        db: &'db dyn crate::Db,
+        id: Identifier<'db>,
+    ) -> Option<SymFunction<'db>> {
+        self.methods(db).iter().copied().find(|m| m.name(db) == id)
+    }
+
+    /// A reference to this trait whose arguments (and associated types)
+    /// are the trait's own generic parameters.
+    pub fn identity_ref(self, db: &'db dyn crate::Db) -> SymTraitRef<'db> {
+        SymTraitRef::new(
+            db,
+            self,
+            self.declared_generic_vars(db)
+                .iter()
+                .map(|&v| SymGenericTerm::var(db, v))
+                .collect(),
+            self.associated_ty_vars(db)
+                .iter()
+                .map(|&v| SymTy::var(db, v))
+                .collect(),
+        )
+    }
+
+    /// Finds the impl of this trait for `aggr`, if any.
+    /// Impls must appear either in the module that declares the trait
+    /// or in the module that declares the aggregate.
+    #[salsa::tracked]
+    pub fn impl_for(self, db: &'db dyn crate::Db, aggr: SymAggregate<'db>) -> Option<SymImpl<'db>> {
+        let trait_module = self.module(db);
+        let aggr_module = aggr.module(db);
+        std::iter::once(trait_module)
+            .chain(Some(aggr_module).filter(|&m| m != trait_module))
+            .flat_map(|m| m.impls(db).iter().copied())
+            .find(|i| i.trait_(db).ok() == Some(self) && i.self_aggregate(db) == Some(aggr))
+    }
+
+    /// Given a call to `method`, a method declared in this trait, with the generic arguments
+    /// `generics` (which begin with the arguments for `Self` and the rest of the trait's generics),
+    /// finds the impl that applies to the `Self` type. Returns the corresponding impl method
+    /// along with its generic arguments.
+    ///
+    /// Used during codegen, when all generic arguments are known.
+    pub fn resolve_method(
+        self,
+        db: &'db dyn crate::Db,
+        method: SymFunction<'db>,
+        generics: &[SymGenericTerm<'db>],
+    ) -> Option<(SymFunction<'db>, Vec<SymGenericTerm<'db>>)> {
+        let self_ty = strip_perms(db, generics[0].assert_type(db));
+        let &SymTyKind::Named(SymTyName::Aggregate(aggr), _) = self_ty.kind(db) else {
+            return None;
+        };
+        let sym_impl = self.impl_for(db, aggr)?;
+        let impl_method = sym_impl.method_named(db, method.name(db))?;
+        let header = sym_impl.checked_header(db).ok()?;
+
+        // Match the impl header against the `Self` type and trait arguments
+        // to find the values for the impl's generic parameters.
+        let impl_vars = &header.variables;
+        let mut bindings = Map::default();
+        match_term(
+            db,
+            impl_vars,
+            header.bound_value.self_ty.into(),
+            self_ty.into(),
+            &mut bindings,
+        );
+        for (&pattern, &value) in header
+            .bound_value
+            .trait_ref
+            .args(db)
+            .iter()
+            .zip(&generics[1..])
+        {
+            match_term(db, impl_vars, pattern, value, &mut bindings);
+        }
+
+        let mut impl_generics: Vec<SymGenericTerm<'db>> = impl_vars
+            .iter()
+            .map(|&v| match bindings.get(&v) {
+                Some(&term) => term,
+                None => unconstrained_term(db, v),
+            })
+            .collect();
+
+        // The method's own generic arguments come last.
+        let method_generics = method.symbols(db).generic_variables.len();
+        impl_generics.extend_from_slice(&generics[generics.len() - method_generics..]);
+
+        Some((impl_method, impl_generics))
+    }
+}
+
+impl std::fmt::Display for SymTrait<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| write!(f, "{}", self.name(db)))
+            .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
+impl<'db> ScopeTreeNode<'db> for SymTrait<'db> {
+    fn direct_super_scope(self, db: &'db dyn crate::Db) -> Option<ScopeItem<'db>> {
+        Some(self.super_scope(db))
+    }
+
+    fn direct_generic_parameters(self, db: &'db dyn crate::Db) -> &'db Vec<SymVariable<'db>> {
+        &self.symbols(db).generic_variables
+    }
+
+    fn into_scope(self, db: &'db dyn crate::Db) -> Scope<'db, 'db> {
+        self.trait_scope(db)
+    }
+
+    fn push_direct_ast_where_clauses(
+        self,
+        db: &'db dyn crate::Db,
+        out: &mut Vec<dada_ir_ast::ast::AstWhereClause<'db>>,
+    ) {
+        if let Some(wc) = self.source(db).where_clauses(db) {
+            out.extend(wc.clauses(db));
+        }
+    }
+}
+
+impl<'db> Spanned<'db> for SymTrait<'db> {
+    fn span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.name_span(db)
+    }
+}
+
+impl<'db> SourceSpanned<'db> for SymTrait<'db> {
+    fn source_span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).span(db)
+    }
+}
+
+/// Create the symbol for an associated type declared in a trait.
+/// This is tracked so that we do it at most once.
+#[salsa::tracked]
+impl<'db> Symbol<'db> for AstAssociatedTy<'db> {
+    type Output = SymVariable<'db>;
+
+    #[salsa::tracked]
+    fn symbol(self, db: &'db dyn crate::Db) -> SymVariable<'db> {
+        let SpannedIdentifier { span, id } = self.name(db);
+        SymVariable::new(db, SymGenericKind::Type, Some(id), span)
+    }
+}
+
+#[derive(SalsaSerialize)]
+#[salsa::tracked(debug)]
+pub struct SymImpl<'db> {
+    /// The scope in which this impl is declared.
+    super_scope: ScopeItem<'db>,
+
+    /// The AST for this impl.
+    pub source: AstImpl<'db>,
+}
+
+#[salsa::tracked]
+impl<'db> SymImpl<'db> {
+    /// Span of the `impl` keyword, typically used in diagnostics.
+    pub fn impl_span(self, db: &'db dyn crate::Db) -> Span<'db> {
+        self.source(db).impl_span(db)
+    }
+
+    /// Returns the symbols for this impl header (generic arguments),
+    /// including those declared inline in the trait or the self type.
+    #[salsa::tracked(return_ref)]
+    pub(crate) fn symbols(self, db: &'db dyn crate::Db) -> SignatureSymbols<'db> {
+        let mut signature_symbols = SignatureSymbols::new(self);
+        self.source(db)
+            .populate_signature_symbols(db, &mut signature_symbols);
+        signature_symbols
+    }
+
+    /// Returns the base scope used to resolve the impl members.
+    pub(crate) fn impl_scope(self, db: &'db dyn crate::Db) -> Scope<'db, 'db> {
+        let symbols = self.symbols(db);
+        assert!(symbols.input_variables.is_empty());
+        self.super_scope(db)
+            .into_scope(db)
+            .with_link(self)
+            .with_link(Cow::Borrowed(&symbols.generic_variables[..]))
+    }
+
+    /// The trait being implemented.
+    /// Reports an error if the path does not name a trait.
+    #[salsa::tracked]
+    pub fn trait_(self, db: &'db dyn crate::Db) -> Errors<SymTrait<'db>> {
+        let trait_path = self.source(db).trait_path(db);
+        match trait_path.resolve_to_sym(db, &self.impl_scope(db))? {
+            NameResolutionSym::SymTrait(sym_trait) => Ok(sym_trait),
+            sym => Err(Diagnostic::error(
+                db,
+                trait_path.span(db),
+                format!("expected a trait, found {}", sym.categorize(db)),
+            )
+            .label(
+                db,
+                Level::Error,
+                trait_path.span(db),
+                format!(
+                    "I expected the name of a trait here, but I found {}",
+                    sym.describe(db)
+                ),
+            )
+            .report(db)),
+        }
+    }
+
+    /// The aggregate that this impl is for, if the self type names one.
+    /// Errors are reported when checking the impl header.
+    #[salsa::tracked]
+    pub fn self_aggregate(self, db: &'db dyn crate::Db) -> Option<SymAggregate<'db>> {
+        let AstTyKind::Named(path, _) = self.source(db).self_ty(db).kind(db) else {
+            return None;
+        };
+        match path.resolve_to_sym(db, &self.impl_scope(db)) {
+            Ok(NameResolutionSym::SymAggregate(aggr)) => Some(aggr),
+            _ => None,
+        }
+    }
+
+    /// Associated types defined in this impl.
+    pub fn associated_tys(
+        self,
+        db: &'db dyn crate::Db,
+    ) -> impl Iterator<Item = AstAssociatedTy<'db>> + 'db {
+        self.source(db).members(db).iter().filter_map(|m| match *m {
+            AstTraitMember::AssociatedTy(a) => Some(a),
+            AstTraitMember::Function(_) => None,
+        })
+    }
+
+    /// Tracked list of impl methods.
+    #[salsa::tracked(return_ref)]
+    pub fn methods(self, db: &'db dyn crate::Db) -> Vec<SymFunction<'db>> {
+        self.source(db)
+            .members(db)
+            .iter()
+            .filter_map(|m| match *m {
+                AstTraitMember::Function(f) => Some(SymFunction::new(db, self.into(), f.into())),
+                AstTraitMember::AssociatedTy(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the method with the given name, if it exists.
+    pub fn method_named(
+        self,
+        db: &'db dyn crate::Db,
+        id: Identifier<'db>,
+    ) -> Option<SymFunction<'db>> {
+        self.methods(db).iter().copied().find(|m| m.name(db) == id)
+    }
+}
+
+impl<'db> ScopeTreeNode<'db> for SymImpl<'db> {
+    fn direct_super_scope(self, db: &'db dyn crate::Db) -> Option<ScopeItem<'db>> {
+        Some(self.super_scope(db))
+    }
+
+    fn direct_generic_parameters(self, db: &'db dyn crate::Db) -> &'db Vec<SymVariable<'db>> {
+        &self.symbols(db).generic_variables
+    }
+
+    fn into_scope(self, db: &'db dyn crate::Db) -> Scope<'db, 'db> {
+        self.impl_scope(db)
+    }
+
+    fn push_direct_ast_where_clauses(
+        self,
+        db: &'db dyn crate::Db,
+        out: &mut Vec<dada_ir_ast::ast::AstWhereClause<'db>>,
+    ) {
+        if let Some(wc) = self.source(db).where_clauses(db) {
+            out.extend(wc.clauses(db));
+        }
+    }
+}
+
+impl<'db> Spanned<'db> for SymImpl<'db> {
+    fn span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).impl_span(db)
+    }
+}
+
+impl<'db> SourceSpanned<'db> for SymImpl<'db> {
+    fn source_span(&self, db: &'db dyn dada_ir_ast::Db) -> Span<'db> {
+        self.source(db).span(db)
+    }
+}
+
+/// A reference to a trait with generic arguments, e.g., `Place[my]`.
+/// The arguments do not include `Self` nor the associated types.
+#[derive(SalsaSerialize)]
+#[salsa::interned(debug)]
+pub struct SymTraitRef<'db> {
+    pub trait_: SymTrait<'db>,
+
+    #[return_ref]
+    pub args: Vec<SymGenericTerm<'db>>,
+
+    /// Values for the associated types, in the order the trait declares them.
+    /// Empty if they are not known (e.g., for a where-clause written by the user).
+    #[return_ref]
+    pub associated_tys: Vec<SymTy<'db>>,
+}
+
+impl std::fmt::Display for SymTraitRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        salsa::with_attached_database(|db| {
+            let args = self.args(db);
+            if args.is_empty() {
+                write!(f, "{}", self.trait_(db))
+            } else {
+                write!(
+                    f,
+                    "{}[{}]",
+                    self.trait_(db),
+                    args.iter()
+                        .map(|g| g.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+        })
+        .unwrap_or_else(|| std::fmt::Debug::fmt(self, f))
+    }
+}
+
+/// The checked header of an impl like `impl[perm P] Place[P] for Foo { type Output = String }`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Update, Debug, Serialize)]
+pub struct SymImplHeader<'db> {
+    /// The trait being implemented, e.g., `Place[P]`
+    pub trait_ref: SymTraitRef<'db>,
+
+    /// The type the trait is implemented for, e.g., `Foo`
+    pub self_ty: SymTy<'db>,
+
+    /// Values of the associated types, in the order the trait declares them
+    pub associated_tys: Vec<SymTy<'db>>,
+}
+
+impl<'db> LeafBoundTerm<'db> for SymImplHeader<'db> {}
+
+/// Strips any permissions from the outside of `ty`.
+fn strip_perms<'db>(db: &'db dyn crate::Db, ty: SymTy<'db>) -> SymTy<'db> {
+    match ty.kind(db) {
+        SymTyKind::Perm(_, ty) => strip_perms(db, *ty),
+        _ => ty,
+    }
+}
+
+/// Structurally matches `pattern` against `value`, recording in `bindings`
+/// the values found for any of `vars` that appear in `pattern`.
+fn match_term<'db>(
+    db: &'db dyn crate::Db,
+    vars: &[SymVariable<'db>],
+    pattern: SymGenericTerm<'db>,
+    value: SymGenericTerm<'db>,
+    bindings: &mut Map<SymVariable<'db>, SymGenericTerm<'db>>,
+) {
+    match (pattern, value) {
+        (SymGenericTerm::Type(pattern_ty), SymGenericTerm::Type(value_ty)) => {
+            match (pattern_ty.kind(db), value_ty.kind(db)) {
+                (SymTyKind::Var(var), _) if vars.contains(var) => {
+                    bindings.insert(*var, value);
+                }
+                (SymTyKind::Named(_, pattern_args), SymTyKind::Named(_, value_args)) => {
+                    for (&p, &v) in pattern_args.iter().zip(value_args) {
+                        match_term(db, vars, p, v, bindings);
+                    }
+                }
+                (
+                    SymTyKind::Perm(pattern_perm, pattern_ty),
+                    SymTyKind::Perm(value_perm, value_ty),
+                ) => {
+                    match_term(
+                        db,
+                        vars,
+                        (*pattern_perm).into(),
+                        (*value_perm).into(),
+                        bindings,
+                    );
+                    match_term(db, vars, (*pattern_ty).into(), (*value_ty).into(), bindings);
+                }
+                _ => {}
+            }
+        }
+        (SymGenericTerm::Perm(pattern_perm), SymGenericTerm::Perm(_)) => {
+            if let SymPermKind::Var(var) = pattern_perm.kind(db)
+                && vars.contains(var)
+            {
+                bindings.insert(*var, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The value used for an impl generic parameter that is not determined by
+/// the `Self` type nor the trait arguments.
+fn unconstrained_term<'db>(db: &'db dyn crate::Db, var: SymVariable<'db>) -> SymGenericTerm<'db> {
+    match var.kind(db) {
+        SymGenericKind::Type => SymTy::fallback(db).into(),
+        SymGenericKind::Perm => SymPerm::my(db).into(),
+        SymGenericKind::Place => SymPlace::erased(db).into(),
+    }
+}
@@ -387,23 +387,9 @@ impl<'db> FromInfer<'db> for SymTy<'db> {
 impl std::fmt::Display for SymTy<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         salsa::with_attached_database(|db| match self.kind(db) {
-            SymTyKind::Named(name, generics) => {
-                if generics.is_empty() {
-                    write!(f, "{name}")
-                } else {
-                    write!(
-                        f,
-                        "{name}[{}]",
-                        generics
-                            .iter()
-                            .map(|g| g.to_string())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )
-                }
-            }
+            SymTyKind::Named(name, generics) => write_named_ty(f, name, generics),
             SymTyKind::Perm(sym_perm, sym_ty) => write!(f, "{sym_perm} {sym_ty}"),
-            SymTyKind::Infer(infer_var_index) => write!(f, "?{}", infer_var_index.as_usize()),
+            SymTyKind::Infer(infer_var_index) => write!(f, "?T{}", infer_var_index.as_usize()),
             SymTyKind::Var(sym_variable) => write!(f, "{sym_variable}"),
             SymTyKind::Never => write!(f, "!"),
             SymTyKind::Error(_) => write!(f, "<error>"),
@@ -412,6 +398,41 @@ impl std::fmt::Display for SymTy<'_> {
     }
 }
 
+/// Write a named type in surface syntax, e.g. `Vec[String]` or `(u32, String)`.
+pub(crate) fn write_named_ty(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &SymTyName<'_>,
+    generics: &[SymGenericTerm<'_>],
+) -> std::fmt::Result {
+    match name {
+        SymTyName::Tuple { arity: 1 } => write!(f, "({},)", generics[0]),
+        SymTyName::Tuple { arity: _ } => {
+            write!(f, "(")?;
+            write_comma_separated(f, generics)?;
+            write!(f, ")")
+        }
+        _ if generics.is_empty() => write!(f, "{name}"),
+        _ => {
+            write!(f, "{name}[")?;
+            write_comma_separated(f, generics)?;
+            write!(f, "]")
+        }
+    }
+}
+
+fn write_comma_separated(
+    f: &mut std::fmt::Formatter<'_>,
+    items: &[impl std::fmt::Display],
+) -> std::fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
 impl<'db> LeafBoundTerm<'db> for SymTy<'db> {}
 
 impl<'db> Err<'db> for SymTy<'db> {
@@ -493,6 +514,7 @@ impl std::fmt::Display for SymTyName<'_> {
             match self {
                 SymTyName::Primitive(primitive) => write!(f, "{primitive}"),
                 SymTyName::Aggregate(class) => write!(f, "{}", class.name(db)),
+                SymTyName::Tuple { arity: 0 } => write!(f, "()"),
                 SymTyName::Tuple { arity } => write!(f, "{arity}-tuple"),
                 SymTyName::Future => write!(f, "Future"),
             }
@@ -607,26 +629,18 @@ impl std::fmt::Display for SymPerm<'_> {
                 SymPermKind::Our => write!(f, "our"),
                 SymPermKind::Referenced(places) => {
                     write!(f, "ref[")?;
-                    for (i, place) in places.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{place}")?;
-                    }
+                    write_comma_separated(f, places)?;
                     write!(f, "]")
                 }
                 SymPermKind::Mutable(places) => {
-                    write!(f, "mutable[")?;
-                    for (i, place) in places.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{place}")?;
-                    }
+                    write!(f, "mut[")?;
+                    write_comma_separated(f, places)?;
                     write!(f, "]")
                 }
                 SymPermKind::Apply(perm1, perm2) => write!(f, "{perm1} {perm2}"),
-                SymPermKind::Infer(infer_var_index) => write!(f, "?{}", infer_var_index.as_usize()),
+                SymPermKind::Infer(infer_var_index) => {
+                    write!(f, "?P{}", infer_var_index.as_usize())
+                }
                 SymPermKind::Var(sym_variable) => write!(f, "{sym_variable}"),
                 SymPermKind::Or(l, r) => write!(f, "({l} | {r})"),
                 SymPermKind::Error(_) => write!(f, "<error>"),
@@ -699,6 +713,10 @@ impl<'db> SymPlace<'db> {
         SymPlace::new(db, SymPlaceKind::Field(self, field))
     }
 
+    pub fn index(self, db: &'db dyn crate::Db) -> Self {
+        SymPlace::new(db, SymPlaceKind::Index(self))
+    }
+
     pub fn erased(db: &'db dyn crate::Db) -> Self {
         SymPlace::new(db, SymPlaceKind::Erased)
     }
@@ -718,13 +736,15 @@ impl<'db> SymPlace<'db> {
     ///
     /// # Definition
     ///
-    /// A place P *covers* another place Q if P includes all of Q. E.g., `a` covers `a.b`.
+    /// A place P *covers* another place Q if P includes all of Q. E.g., `a` covers `a.b` and `a[_]`.
     pub fn is_prefix_of(self, db: &'db dyn crate::Db, other: SymPlace<'db>) -> bool {
         assert!(self.no_inference_vars(db));
         assert!(other.no_inference_vars(db));
         self == other
             || match (self.kind(db), other.kind(db)) {
-                (_, SymPlaceKind::Field(p2, _)) => self.is_prefix_of(db, *p2),
+                (_, SymPlaceKind::Field(p2, _)) | (_, SymPlaceKind::Index(p2)) => {
+                    self.is_prefix_of(db, *p2)
+                }
                 _ => false,
             }
     }
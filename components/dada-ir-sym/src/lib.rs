@@ -12,6 +12,7 @@ pub mod prelude {
     use crate::ir::classes::SymField;
     use crate::ir::exprs::SymExpr;
     use crate::ir::functions::{SymFunction, SymFunctionSignature};
+    use crate::ir::traits::{SymImpl, SymImplHeader};
     use crate::ir::types::SymTy;
     use dada_ir_ast::diagnostic::Errors;
 
@@ -27,6 +28,10 @@ pub mod prelude {
         fn check_use_items(self, db: &'db dyn crate::Db);
     }
 
+    pub trait CheckMembers<'db> {
+        fn check_members(self, db: &'db dyn crate::Db);
+    }
+
     pub trait CheckedBody<'db> {
         fn checked_body(self, db: &'db dyn crate::Db) -> Option<SymExpr<'db>>;
     }
@@ -68,4 +73,17 @@ pub mod prelude {
             }
         }
     }
+
+    pub trait CheckedImplHeader<'db> {
+        /// See [`crate::check::traits::check_impl_header`][]
+        fn checked_header(self, db: &'db dyn crate::Db) -> Errors<Binder<'db, SymImplHeader<'db>>>;
+    }
+
+    #[salsa::tracked]
+    impl<'db> CheckedImplHeader<'db> for SymImpl<'db> {
+        #[salsa::tracked]
+        fn checked_header(self, db: &'db dyn crate::Db) -> Errors<Binder<'db, SymImplHeader<'db>>> {
+            crate::check::traits::check_impl_header(db, self)
+        }
+    }
 }
@@ -60,25 +60,88 @@ pub fn string_class<'db>(db: &'db dyn crate::Db) -> Errors<SymAggregate<'db>> {
 /// Returns the `literal` function of the `String` class from the `libdada` prelude.
 #[salsa::tracked]
 pub fn string_literal_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
-    let string_class = string_class(db)?;
-    let literal_fn = string_class
-        .inherent_member_str(db, "literal")
-        .ok_or_else(|| {
-            report_unexpected(
-                db,
-                string_class,
-                "String",
-                "does not have a `literal` member",
-            )
-        })?;
-    match literal_fn {
+    string_member_fn(db, "literal")
+}
+
+/// Returns the `concat` method of the `String` class from the `libdada` prelude.
+/// Used to lower string interpolation.
+#[salsa::tracked]
+pub fn string_concat_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    string_member_fn(db, "concat")
+}
+
+/// Returns the function named `name` declared in the `String` class.
+fn string_member_fn<'db>(db: &'db dyn crate::Db, name: &str) -> Errors<SymFunction<'db>> {
+    class_member_fn(db, string_class(db)?, "String", name)
+}
+
+/// Returns the `Vec` class from the `libdada` prelude.
+#[salsa::tracked]
+pub fn vec_class<'db>(db: &'db dyn crate::Db) -> Errors<SymAggregate<'db>> {
+    match prelude_member(db, "Vec")? {
+        SymItem::SymClass(class) if class.is_class(db) => {
+            if !class
+                .symbols(db)
+                .has_generics_of_kind(db, &[SymGenericKind::Type])
+            {
+                return Err(report_unexpected(
+                    db,
+                    class,
+                    "Vec",
+                    "it should have 1 generic parameter",
+                ));
+            }
+            Ok(class)
+        }
+        m => Err(report_unexpected(db, m, "Vec", "it is not a class")),
+    }
+}
+
+/// Returns the `new` function of the `Vec` class from the `libdada` prelude.
+/// It has no body; its implementation is generated by the compiler.
+#[salsa::tracked]
+pub fn vec_new_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    class_member_fn(db, vec_class(db)?, "Vec", "new")
+}
+
+/// Returns the `push` method of the `Vec` class from the `libdada` prelude.
+/// It has no body; its implementation is generated by the compiler.
+#[salsa::tracked]
+pub fn vec_push_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    class_member_fn(db, vec_class(db)?, "Vec", "push")
+}
+
+/// Returns the `pop` method of the `Vec` class from the `libdada` prelude.
+/// It has no body; its implementation is generated by the compiler.
+#[salsa::tracked]
+pub fn vec_pop_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    class_member_fn(db, vec_class(db)?, "Vec", "pop")
+}
+
+/// Returns the function named `name` declared in `class` (named `class_name`),
+/// which should not have generic parameters of its own other than permissions.
+fn class_member_fn<'db>(
+    db: &'db dyn crate::Db,
+    class: SymAggregate<'db>,
+    class_name: &str,
+    name: &str,
+) -> Errors<SymFunction<'db>> {
+    let member = class.inherent_member_str(db, name).ok_or_else(|| {
+        report_unexpected(
+            db,
+            class,
+            class_name,
+            &format!("does not have a `{name}` member"),
+        )
+    })?;
+    match member {
         SymClassMember::SymFunction(function) => {
-            if !function.symbols(db).has_generics_of_kind(db, &[]) {
+            if !has_non_perm_generics_of_kind(db, function, &[]) {
                 return Err(report_unexpected(
                     db,
                     function,
-                    "String",
-                    "`literal` should not have generic parameters",
+                    class_name,
+                    &format!("`{name}` should not have generic parameters"),
                 ));
             }
             Ok(function)
@@ -86,8 +149,8 @@ pub fn string_literal_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>
         m => Err(report_unexpected(
             db,
             m,
-            "String",
-            "`literal` is not a function",
+            class_name,
+            &format!("`{name}` is not a function"),
         )),
     }
 }
@@ -114,6 +177,72 @@ pub fn pointer_struct<'db>(db: &'db dyn crate::Db) -> Errors<SymAggregate<'db>>
     }
 }
 
+/// Returns the `print` function from the `libdada` prelude.
+/// It has no body; its implementation is provided by the host.
+#[salsa::tracked]
+pub fn print_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    match prelude_member(db, "print")? {
+        SymItem::SymFunction(function) => {
+            if !has_non_perm_generics_of_kind(db, function, &[]) {
+                return Err(report_unexpected(
+                    db,
+                    function,
+                    "print",
+                    "it should not have generic parameters",
+                ));
+            }
+            Ok(function)
+        }
+        m => Err(report_unexpected(db, m, "print", "it is not a function")),
+    }
+}
+
+/// Returns the `primitive_to_string` function from the `libdada` prelude.
+/// It has no body; its implementation is provided by the host.
+/// Used to lower string interpolation of primitive values.
+#[salsa::tracked]
+pub fn primitive_to_string_fn<'db>(db: &'db dyn crate::Db) -> Errors<SymFunction<'db>> {
+    match prelude_member(db, "primitive_to_string")? {
+        SymItem::SymFunction(function) => {
+            if !function
+                .symbols(db)
+                .has_generics_of_kind(db, &[SymGenericKind::Type])
+            {
+                return Err(report_unexpected(
+                    db,
+                    function,
+                    "primitive_to_string",
+                    "it should have 1 generic parameter",
+                ));
+            }
+            Ok(function)
+        }
+        m => Err(report_unexpected(
+            db,
+            m,
+            "primitive_to_string",
+            "it is not a function",
+        )),
+    }
+}
+
+/// True if the generic parameters of `function`, other than permissions, have the given `kinds`.
+/// Permission parameters are ignored because functions get one implicitly
+/// for `self` and for each argument whose type is a class (e.g., `s: String`).
+fn has_non_perm_generics_of_kind<'db>(
+    db: &'db dyn crate::Db,
+    function: SymFunction<'db>,
+    kinds: &[SymGenericKind],
+) -> bool {
+    let generics: Vec<_> = function
+        .symbols(db)
+        .generic_variables
+        .iter()
+        .filter(|v| v.kind(db) != SymGenericKind::Perm)
+        .collect();
+    generics.len() == kinds.len() && generics.iter().zip(kinds).all(|(v, &k)| v.kind(db) == k)
+}
+
 fn report_not_found<'db>(db: &'db dyn crate::Db, module: SymModule<'db>, name: &str) -> Reported {
     let module_span = module.span(db);
     Diagnostic::error(db, module_span, format!("could not find {name}")).report(db)
@@ -22,11 +22,38 @@ pub struct Options {
 pub struct GlobalOptions {
     #[structopt(long)]
     no_color: bool,
+
+    /// How to print diagnostics: `human` or `json` (one JSON object per line on stdout)
+    #[structopt(long, default_value = "human")]
+    message_format: MessageFormat,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageFormat {
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!(
+                "unknown message format `{s}`; expected `human` or `json`"
+            )),
+        }
+    }
 }
 
 impl GlobalOptions {
     pub(crate) fn test_options() -> Self {
-        Self { no_color: false }
+        Self {
+            no_color: false,
+            message_format: MessageFormat::Human,
+        }
     }
 
     pub(crate) fn render_opts(&self) -> RenderOptions {
@@ -60,11 +87,23 @@ pub enum Command {
         #[structopt(flatten)]
         compile_options: CompileOptions,
     },
+
+    Explain {
+        #[structopt(flatten)]
+        explain_options: ExplainOptions,
+    },
+
+    Fix {
+        #[structopt(flatten)]
+        compile_options: CompileOptions,
+    },
 }
 
 #[derive(Debug, StructOpt)]
 pub struct CompileOptions {
-    /// Main source file to compile.
+    /// Main source file to compile. For `dada compile`, this may also be
+    /// a crate root like `foo.dada` or a directory like `foo/`, in which case
+    /// every module beneath it is checked.
     input: String,
 }
 
@@ -74,6 +113,12 @@ pub struct RunOptions {
     compile_options: CompileOptions,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct ExplainOptions {
+    /// Diagnostic code to explain, like `D0001`.
+    code: String,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct TestOptions {
     /// Print each test as we run it
@@ -3,11 +3,13 @@ use dada_util::Fallible;
 use crate::{Command, GlobalOptions};
 
 mod compile;
+mod diagnostics;
+mod explain;
+mod fix;
 mod run;
 mod test;
 
 pub struct Main {
-    #[allow(dead_code)]
     global_options: GlobalOptions,
 }
 
@@ -21,6 +23,8 @@ impl Main {
             Command::Compile { compile_options } => self.compile(&compile_options, None)?,
             Command::Test { test_options } => self.test(test_options)?,
             Command::Run { run_options } => self.run_command(&run_options)?,
+            Command::Explain { explain_options } => self.explain(&explain_options)?,
+            Command::Fix { compile_options } => self.fix(&compile_options)?,
             Command::Debug {
                 debug_options,
                 compile_options,
@@ -1,8 +1,12 @@
 use std::{path::Path, sync::mpsc::Sender};
 
 use dada_compiler::{Compiler, RealFs};
-use dada_ir_ast::{DebugEvent, diagnostic::Level};
-use dada_util::{Fallible, bail};
+use dada_ir_ast::{
+    DebugEvent,
+    diagnostic::{Diagnostic, Level},
+};
+use dada_util::{Fallible, Set, bail};
+use rayon::prelude::*;
 
 use crate::CompileOptions;
 
@@ -17,18 +21,44 @@ impl Main {
         let debug_mode = debug_tx.is_some();
         let mut compiler = Compiler::new(RealFs::default(), debug_tx);
         let source_url = Path::new(&compile_options.input);
-        let source_file = compiler.load_source_file(source_url)?;
-        let diagnostics = compiler.check_all(source_file);
-
-        for diagnostic in &diagnostics {
-            eprintln!(
-                "{}",
-                diagnostic.render(&compiler, &self.global_options.render_opts())
-            );
-        }
+        let source_files = compiler.load_crate_source_files(source_url)?;
+
+        // Check each file on its own fork so that independent modules are checked in parallel.
+        // The results are memoized, so collecting the diagnostics below is cheap.
+        source_files
+            .iter()
+            .map(|&source_file| (compiler.fork(), source_file))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .for_each(|(fork, source_file)| {
+                fork.check_all(source_file);
+            });
+
+        // A module used by several files reports its diagnostics for each of them.
+        let mut seen = Set::default();
+        let diagnostics: Vec<&Diagnostic> = source_files
+            .iter()
+            .flat_map(|&source_file| compiler.check_all(source_file))
+            .filter(|&d| seen.insert(d))
+            .collect();
+
+        self.report_diagnostics(&compiler, &diagnostics)?;
+
+        let errors = diagnostics
+            .iter()
+            .filter(|d| d.level >= Level::Error)
+            .count();
+        eprintln!(
+            "checked {files} file(s): {errors} error(s), {warnings} warning(s)",
+            files = source_files.len(),
+            warnings = diagnostics
+                .iter()
+                .filter(|d| d.level == Level::Warning)
+                .count(),
+        );
 
         // In debug mode, diagnostics get reported to the `debug_tx` and aren't considered errors.
-        if !debug_mode && diagnostics.iter().any(|d| d.level >= Level::Error) {
+        if !debug_mode && errors > 0 {
             bail!("compilation failed due to errors");
         }
 
@@ -0,0 +1,156 @@
+use dada_compiler::Compiler;
+use dada_ir_ast::{
+    diagnostic::{Diagnostic, DiagnosticLabel, DiagnosticSuggestion, Level},
+    span::{AbsoluteOffset, AbsoluteSpan},
+};
+use dada_util::Fallible;
+use serde::Serialize;
+
+use crate::MessageFormat;
+
+use super::Main;
+
+impl Main {
+    /// Print `diagnostics` in the format selected by `--message-format`.
+    /// Human-readable diagnostics go to stderr; JSON diagnostics go to stdout,
+    /// one object per line, so that tools can read them without scraping.
+    pub(super) fn report_diagnostics(
+        &self,
+        compiler: &Compiler,
+        diagnostics: &[&Diagnostic],
+    ) -> Fallible<()> {
+        for diagnostic in diagnostics {
+            match self.global_options.message_format {
+                MessageFormat::Human => eprintln!(
+                    "{}",
+                    diagnostic.render(compiler, &self.global_options.render_opts())
+                ),
+                MessageFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string(&JsonDiagnostic::new(compiler, diagnostic))?
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// JSON output structures for `--message-format=json`
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    level: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    message: String,
+    span: JsonSpan,
+    labels: Vec<JsonLabel>,
+    suggestions: Vec<JsonSuggestion>,
+    children: Vec<JsonDiagnostic>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    level: &'static str,
+    message: String,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion {
+    message: String,
+    replacement: String,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    url: String,
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+/// A position in a source file. `line` and `column` are one-based;
+/// `column` and `offset` count bytes.
+#[derive(Serialize)]
+struct JsonPosition {
+    offset: usize,
+    line: u32,
+    column: u32,
+}
+
+impl JsonDiagnostic {
+    fn new(db: &Compiler, diagnostic: &Diagnostic) -> Self {
+        Self {
+            level: level_str(diagnostic.level),
+            code: diagnostic.code.map(|code| code.to_string()),
+            message: diagnostic.message.clone(),
+            span: JsonSpan::new(db, diagnostic.span),
+            labels: diagnostic
+                .labels
+                .iter()
+                .map(|label| JsonLabel::new(db, label))
+                .collect(),
+            suggestions: diagnostic
+                .suggestions
+                .iter()
+                .map(|suggestion| JsonSuggestion::new(db, suggestion))
+                .collect(),
+            children: diagnostic
+                .children
+                .iter()
+                .map(|child| JsonDiagnostic::new(db, child))
+                .collect(),
+        }
+    }
+}
+
+impl JsonLabel {
+    fn new(db: &Compiler, label: &DiagnosticLabel) -> Self {
+        Self {
+            level: level_str(label.level),
+            message: label.message.clone(),
+            span: JsonSpan::new(db, label.span),
+        }
+    }
+}
+
+impl JsonSuggestion {
+    fn new(db: &Compiler, suggestion: &DiagnosticSuggestion) -> Self {
+        Self {
+            message: suggestion.message.clone(),
+            replacement: suggestion.replacement.clone(),
+            span: JsonSpan::new(db, suggestion.span),
+        }
+    }
+}
+
+impl JsonSpan {
+    fn new(db: &Compiler, span: AbsoluteSpan) -> Self {
+        Self {
+            url: span.source_file.url(db).to_string(),
+            start: JsonPosition::new(db, span, span.start),
+            end: JsonPosition::new(db, span, span.end),
+        }
+    }
+}
+
+impl JsonPosition {
+    fn new(db: &Compiler, span: AbsoluteSpan, offset: AbsoluteOffset) -> Self {
+        let (line, column) = span.source_file.line_col(db, offset);
+        Self {
+            offset: offset.as_usize(),
+            line: line.as_u32() + 1,
+            column: column.as_u32() + 1,
+        }
+    }
+}
+
+fn level_str(level: Level) -> &'static str {
+    match level {
+        Level::Note => "note",
+        Level::Help => "help",
+        Level::Info => "info",
+        Level::Warning => "warning",
+        Level::Error => "error",
+    }
+}
@@ -0,0 +1,20 @@
+use dada_ir_ast::diagnostic::DiagnosticCode;
+use dada_util::{Fallible, bail};
+
+use crate::ExplainOptions;
+
+use super::Main;
+
+impl Main {
+    pub(super) fn explain(&mut self, explain_options: &ExplainOptions) -> Fallible<()> {
+        let Ok(code) = explain_options.code.parse::<DiagnosticCode>() else {
+            bail!(
+                "`{}` is not a valid diagnostic code; codes look like `D0001`",
+                explain_options.code
+            );
+        };
+
+        print!("{}", code.explanation());
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use dada_compiler::{Compiler, RealFs};
+use dada_ir_ast::{
+    diagnostic::{Diagnostic, DiagnosticSuggestion},
+    inputs::SourceFile,
+};
+use dada_util::Fallible;
+
+use crate::CompileOptions;
+
+use super::Main;
+
+impl Main {
+    pub(super) fn fix(&mut self, compile_options: &CompileOptions) -> Fallible<()> {
+        let mut compiler = Compiler::new(RealFs::default(), None);
+        let source_path = Path::new(&compile_options.input);
+        let source_file = compiler.load_source_file(source_path)?;
+        let diagnostics = compiler.check_all(source_file);
+
+        let (contents, applied) = apply_suggestions(&compiler, source_file, &diagnostics);
+
+        if !applied.is_empty() {
+            std::fs::write(source_path, contents)?;
+        }
+
+        for suggestion in &applied {
+            eprintln!("fixed: {}", suggestion.message);
+        }
+        eprintln!(
+            "applied {} fix(es) to `{}`",
+            applied.len(),
+            source_path.display()
+        );
+
+        Ok(())
+    }
+}
+
+/// Applies the suggestions from `diagnostics` to the contents of `source_file`,
+/// returning the fixed contents and the suggestions that were applied.
+/// Used by `dada fix` and by `#:fix` tests.
+pub(super) fn apply_suggestions<'d>(
+    compiler: &Compiler,
+    source_file: SourceFile,
+    diagnostics: &'d [&Diagnostic],
+) -> (String, Vec<&'d DiagnosticSuggestion>) {
+    let mut suggestions: Vec<&DiagnosticSuggestion> = diagnostics
+        .iter()
+        .flat_map(|&d| d.all_suggestions())
+        .filter(|s| s.span.source_file == source_file)
+        .collect();
+    suggestions.sort_by_key(|s| (s.span.start, s.span.end));
+    suggestions.dedup_by_key(|s| (s.span, s.replacement.clone()));
+
+    // Keep only suggestions that do not overlap an earlier one;
+    // the rest may no longer make sense once the earlier fix is applied.
+    let mut applied: Vec<&DiagnosticSuggestion> = vec![];
+    for suggestion in suggestions {
+        if let Some(previous) = applied.last()
+            && suggestion.span.start < previous.span.end
+        {
+            continue;
+        }
+        applied.push(suggestion);
+    }
+
+    // Apply back to front so that earlier offsets remain valid.
+    let mut contents = source_file.contents_if_ok(compiler).to_string();
+    for suggestion in applied.iter().rev() {
+        contents.replace_range(
+            suggestion.span.start.as_usize()..suggestion.span.end.as_usize(),
+            &suggestion.replacement,
+        );
+    }
+
+    (contents, applied)
+}
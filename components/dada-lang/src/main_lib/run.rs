@@ -1,7 +1,9 @@
 use std::path::Path;
 
 use dada_compiler::{Compiler, RealFs};
-use dada_util::Fallible;
+use dada_ir_ast::diagnostic::Level;
+use dada_runtime::ExitStatus;
+use dada_util::{Fallible, bail};
 
 use crate::RunOptions;
 
@@ -12,18 +14,25 @@ impl Main {
         let mut compiler = Compiler::new(RealFs::default(), None);
         let source_url = Path::new(&run_options.compile_options.input);
         let source_file = compiler.load_source_file(source_url)?;
-        let bytes = compiler.codegen_main_fn(source_file);
         let diagnostics = compiler.check_all(source_file);
 
-        for diagnostic in &diagnostics {
-            eprintln!(
-                "{}",
-                diagnostic.render(&compiler, &self.global_options.render_opts())
-            );
+        self.report_diagnostics(&compiler, &diagnostics)?;
+
+        // Code generation assumes the program is well-typed, so don't run it otherwise.
+        if diagnostics.iter().any(|d| d.level >= Level::Error) {
+            bail!("compilation failed due to errors");
         }
 
-        let _ = bytes;
+        let Some(bytes) = compiler.codegen_main_fn(source_file) else {
+            bail!("no `main` function found");
+        };
+
+        let output = dada_runtime::run(bytes, None)?;
+        print!("{}", output.stdout);
 
-        Ok(())
+        match output.status {
+            ExitStatus::Success => Ok(()),
+            status => bail!("program failed ({status})"),
+        }
     }
 }
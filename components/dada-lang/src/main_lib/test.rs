@@ -431,6 +431,15 @@ fn extract_annotations(expectations: &expected::TestExpectations) -> Vec<String>
     if !expectations.codegen() {
         annotations.push("#:skip_codegen".to_string());
     }
+    if expectations.run() {
+        annotations.push("#:run".to_string());
+    }
+    if expectations.fix() {
+        annotations.push("#:fix".to_string());
+    }
+    if expectations.crate_diagnostics() {
+        annotations.push("#:crate".to_string());
+    }
     if expectations.fixme() {
         annotations.push("#:FIXME".to_string());
     }
@@ -2,19 +2,25 @@ use std::path::{Path, PathBuf};
 
 use dada_compiler::Compiler;
 use dada_ir_ast::{
-    diagnostic::Diagnostic,
+    diagnostic::{Diagnostic, Level},
     inputs::SourceFile,
     span::{AbsoluteOffset, AbsoluteSpan},
 };
-use dada_util::{Context, Fallible, bail};
+use dada_runtime::ExitStatus;
+use dada_util::{Context, Fallible, Set, bail};
 use prettydiff::text::ContextConfig;
 use regex::Regex;
 
 use crate::GlobalOptions;
+use crate::main_lib::fix::apply_suggestions;
 
 use super::spec_validation::SpecValidator;
 use super::{FailedTest, Failure};
 
+/// Fuel given to each `#:run` test, so that a test that does not terminate
+/// fails with an "out of fuel" status instead of hanging the test suite.
+const RUN_TEST_FUEL: u64 = 100_000_000;
+
 #[derive(Clone, Debug)]
 pub struct ExpectedDiagnostic {
     /// The span where this diagnostic is expected to start.
@@ -40,6 +46,9 @@ pub struct TestExpectations {
     expected_diagnostics: Vec<ExpectedDiagnostic>,
     fn_asts: bool,
     codegen: bool,
+    run: bool,
+    fix: bool,
+    crate_diagnostics: bool,
     fixme: bool,
     fixme_ice: bool,
     probes: Vec<Probe>,
@@ -86,6 +95,9 @@ pub enum ProbeKind {
 
     /// Dumps the compact AST representation of the smallest containing expression
     Ast,
+
+    /// Tests the location (`line:column`) of the declaration referenced here
+    Definition,
 }
 
 enum Bless {
@@ -129,6 +141,9 @@ impl TestExpectations {
             expected_diagnostics: vec![],
             fn_asts: false,
             codegen: true,
+            run: false,
+            fix: false,
+            crate_diagnostics: false,
             fixme: false,
             fixme_ice: false,
             probes: vec![],
@@ -265,6 +280,7 @@ impl TestExpectations {
                     ("VariableType", ProbeKind::VariableType),
                     ("ExprType", ProbeKind::ExprType),
                     ("Ast", ProbeKind::Ast),
+                    ("Definition", ProbeKind::Definition),
                 ];
                 let user_probe_kind = c.name("kind").unwrap().as_str();
                 let Some(&(_, kind)) = valid_probe_kinds
@@ -308,6 +324,13 @@ impl TestExpectations {
 
         self.expected_diagnostics.sort_by_key(|e| *e.span());
 
+        if self.run && !self.codegen {
+            bail!(
+                "{}: `#:run` cannot be combined with `#:skip_codegen`",
+                self.source_file.url_display(db),
+            );
+        }
+
         Ok(())
     }
 
@@ -332,6 +355,21 @@ impl TestExpectations {
             return Ok(());
         }
 
+        if line == "run" {
+            self.run = true;
+            return Ok(());
+        }
+
+        if line == "fix" {
+            self.fix = true;
+            return Ok(());
+        }
+
+        if line == "crate" {
+            self.crate_diagnostics = true;
+            return Ok(());
+        }
+
         if line == "FIXME" {
             self.fixme = true;
             return Ok(());
@@ -362,6 +400,18 @@ impl TestExpectations {
         self.codegen
     }
 
+    pub fn run(&self) -> bool {
+        self.run
+    }
+
+    pub fn fix(&self) -> bool {
+        self.fix
+    }
+
+    pub fn crate_diagnostics(&self) -> bool {
+        self.crate_diagnostics
+    }
+
     pub fn fixme(&self) -> bool {
         self.fixme
     }
@@ -391,9 +441,30 @@ impl TestExpectations {
             Self::generate_fn_asts,
         )?);
 
+        test.failures.extend(self.compare_auxiliary(
+            compiler,
+            "stdout",
+            self.run,
+            Self::generate_stdout,
+        )?);
+
+        test.failures.extend(self.compare_auxiliary(
+            compiler,
+            "fixed",
+            self.fix,
+            Self::generate_fixed,
+        )?);
+
+        test.failures.extend(self.compare_auxiliary(
+            compiler,
+            "crate",
+            self.crate_diagnostics,
+            Self::generate_crate_diagnostics,
+        )?);
+
         let actual_diagnostics = compiler.check_all(self.source_file);
 
-        if self.codegen {
+        if self.codegen && !self.run {
             let _wasm_bytes = compiler.codegen_main_fn(self.source_file);
         }
 
@@ -432,6 +503,9 @@ impl TestExpectations {
                     ProbeKind::Ast => compiler
                         .probe_ast(probe.span)
                         .unwrap_or_else(|| "<no expression found>".to_string()),
+                    ProbeKind::Definition => compiler
+                        .probe_definition(probe.span)
+                        .unwrap_or_else(|| "<no definition found>".to_string()),
                 };
 
                 if probe.message.is_match(&actual) {
@@ -450,6 +524,81 @@ impl TestExpectations {
         compiler.fn_asts(self.source_file)
     }
 
+    /// Executes the generated wasm and returns what it printed.
+    /// If the program does not terminate normally, a final line records how it ended,
+    /// so that traps show up in the diff against the `.stdout.ref` file.
+    ///
+    /// Code generation assumes the program is well-typed,
+    /// so a program with errors is not run at all.
+    fn generate_stdout(&self, compiler: &mut Compiler) -> String {
+        let has_errors = compiler
+            .check_all(self.source_file)
+            .iter()
+            .any(|d| d.level >= Level::Error);
+        if has_errors {
+            return "<not run due to compilation errors>\n".to_string();
+        }
+
+        let Some(wasm_bytes) = compiler.codegen_main_fn(self.source_file) else {
+            return "<no main function>\n".to_string();
+        };
+
+        match dada_runtime::run(wasm_bytes, Some(RUN_TEST_FUEL)) {
+            Ok(output) => match output.status {
+                ExitStatus::Success => output.stdout,
+                status => format!("{}<{status}>\n", output.stdout),
+            },
+            Err(error) => format!("<failed to run: {error}>\n"),
+        }
+    }
+
+    /// Applies the suggested fixes as `dada fix` would and returns the resulting source,
+    /// so that the `.fixed.ref` file records what each suggestion replaces and with what.
+    fn generate_fixed(&self, compiler: &mut Compiler) -> String {
+        let diagnostics = compiler.check_all(self.source_file);
+        let (contents, _) = apply_suggestions(compiler, self.source_file, &diagnostics);
+        contents
+    }
+
+    /// Loads the crate rooted at this file as `dada compile` does and lists the diagnostics
+    /// reported for each of its files (as `path:line: message`, with paths relative to this file),
+    /// so that the `.crate.ref` file shows which modules were checked.
+    fn generate_crate_diagnostics(&self, compiler: &mut Compiler) -> String {
+        use std::fmt::Write;
+
+        let source_path = self.source_path(compiler);
+        let crate_directory = source_path.parent().unwrap_or(Path::new(""));
+        let source_files = match compiler.load_crate_source_files(source_path.as_path()) {
+            Ok(source_files) => source_files,
+            Err(error) => return format!("<failed to load crate: {error}>\n"),
+        };
+
+        // A module used by several files reports its diagnostics for each of them.
+        let mut seen = Set::default();
+        let mut output = String::new();
+        for &source_file in &source_files {
+            for diagnostic in compiler.check_all(source_file) {
+                if !seen.insert(diagnostic) {
+                    continue;
+                }
+                let file = diagnostic.span.source_file;
+                let path = file.url(compiler).to_file_path().unwrap();
+                let (line, _) = file.line_col(compiler, diagnostic.span.start);
+                writeln!(
+                    output,
+                    "{}:{}: {}",
+                    path.strip_prefix(crate_directory)
+                        .unwrap_or(&path)
+                        .display(),
+                    line.as_u32() + 1,
+                    diagnostic.message,
+                )
+                .unwrap();
+            }
+        }
+        output
+    }
+
     fn compare_auxiliary(
         &self,
         compiler: &mut Compiler,
@@ -474,7 +623,21 @@ impl TestExpectations {
             return Ok(vec![]);
         }
 
-        let expected = std::fs::read_to_string(&ref_path).unwrap_or_default();
+        let expected = match std::fs::read_to_string(&ref_path) {
+            Ok(expected) => expected,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(vec![Failure::Auxiliary {
+                    kind: format!(":{ext}"),
+                    ref_path,
+                    txt_path,
+                    diff: "reference file does not exist (set `UPDATE_EXPECT=1` to create it)"
+                        .to_string(),
+                }]);
+            }
+            Err(error) => {
+                return Err(error).with_context(|| format!("reading `{}`", ref_path.display()));
+            }
+        };
         if actual == expected {
             return Ok(vec![]);
         }
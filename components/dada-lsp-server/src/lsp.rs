@@ -51,6 +51,20 @@ pub trait Lsp: Sized {
         editor: &mut dyn Editor<Self>,
         params: lsp_types::HoverParams,
     ) -> Fallible<Option<lsp_types::Hover>>;
+
+    /// Handle go-to-definition requests.
+    fn goto_definition(
+        &mut self,
+        editor: &mut dyn Editor<Self>,
+        params: lsp_types::GotoDefinitionParams,
+    ) -> Fallible<Option<lsp_types::GotoDefinitionResponse>>;
+
+    /// Handle code action requests (e.g., quick fixes for diagnostics).
+    fn code_action(
+        &mut self,
+        editor: &mut dyn Editor<Self>,
+        params: lsp_types::CodeActionParams,
+    ) -> Fallible<Option<lsp_types::CodeActionResponse>>;
 }
 
 pub trait LspFork: Sized + Send {
@@ -89,6 +103,8 @@ pub fn run_server<L: Lsp>() -> Fallible<()> {
         .on_notification::<notification::DidOpenTextDocument>(Lsp::did_open)
         .on_notification::<notification::DidChangeTextDocument>(Lsp::did_change)
         .on_request::<request::HoverRequest>(Lsp::hover)
+        .on_request::<request::GotoDefinition>(Lsp::goto_definition)
+        .on_request::<request::CodeActionRequest>(Lsp::code_action)
         .execute()?;
 
     io_threads.join()?;
@@ -8,10 +8,10 @@ use dada_ir_ast::span::{AbsoluteOffset, AbsoluteSpan};
 use dada_util::{Fallible, Map, Set, bail};
 use lsp::{Editor, Lsp, LspFork};
 use lsp_types::{
-    DidChangeTextDocumentParams, DidOpenTextDocumentParams, HoverProviderCapability, MessageType,
-    OneOf, PublishDiagnosticsParams, TextDocumentContentChangeEvent, TextDocumentItem,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions, Uri,
-    VersionedTextDocumentIdentifier,
+    CodeActionProviderCapability, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
+    HoverProviderCapability, MessageType, OneOf, PublishDiagnosticsParams,
+    TextDocumentContentChangeEvent, TextDocumentItem, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, Uri, VersionedTextDocumentIdentifier,
 };
 use lsp_types::{InitializeParams, ServerCapabilities};
 
@@ -52,13 +52,14 @@ impl lsp::Lsp for Server {
             text_document_sync: Some(TextDocumentSyncCapability::Options(
                 TextDocumentSyncOptions {
                     open_close: Some(true),
-                    change: Some(TextDocumentSyncKind::FULL),
+                    change: Some(TextDocumentSyncKind::INCREMENTAL),
                     will_save: None,
                     will_save_wait_until: None,
                     save: None,
                 },
             )),
             definition_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             ..ServerCapabilities::default()
         })
     }
@@ -116,10 +117,15 @@ impl lsp::Lsp for Server {
         } in content_changes
         {
             match range {
-                Some(_) => {
-                    // FIXME: We should implement incremental change events; but LSP sends line/column
-                    // positions and that's just *annoying*.
-                    bail!("we requested full content change events");
+                Some(range) => {
+                    let start = Self::absolute_offset(&self.db, source_file, range.start);
+                    let end = Self::absolute_offset(&self.db, source_file, range.end);
+                    let mut contents = match source_file.contents(&self.db) {
+                        Ok(contents) => contents.clone(),
+                        Err(message) => bail!("cannot apply edit to `{uri_str}`: {message}"),
+                    };
+                    contents.replace_range(start.as_usize()..end.as_usize(), &text);
+                    let _old_contents = source_file.set_contents(&mut self.db).to(Ok(contents));
                 }
                 None => {
                     let _old_contents = source_file.set_contents(&mut self.db).to(Ok(text));
@@ -148,32 +154,8 @@ impl lsp::Lsp for Server {
             work_done_progress_params: _,
         } = params;
 
-        // Get the source file
-        let source_file = self.db.get_previously_opened_source_file(uri.as_str())?;
-
-        // Convert LSP position to absolute offset
-        let line = position.line as usize;
-        let character = position.character as usize;
-
-        // Get line starts
-        let line_starts = source_file.line_starts(&self.db);
-
-        // Make sure the line is valid
-        if line >= line_starts.len() - 1 {
+        let Some(span) = self.span_at_position(uri.as_str(), position)? else {
             return Ok(None);
-        }
-
-        // Get the start offset of the line
-        let line_start = line_starts[line];
-
-        // Calculate the absolute offset
-        let offset = AbsoluteOffset::from(line_start.as_usize() + character);
-
-        // Create a span at the position
-        let span = AbsoluteSpan {
-            source_file,
-            start: offset,
-            end: offset,
         };
 
         // Use probe_expression_type to get the type
@@ -192,6 +174,176 @@ impl lsp::Lsp for Server {
             Ok(None)
         })
     }
+
+    fn goto_definition(
+        &mut self,
+        _editor: &mut dyn Editor<Self>,
+        params: lsp_types::GotoDefinitionParams,
+    ) -> Fallible<Option<lsp_types::GotoDefinitionResponse>> {
+        let lsp_types::GotoDefinitionParams {
+            text_document_position_params:
+                lsp_types::TextDocumentPositionParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri },
+                    position,
+                },
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        let Some(span) = self.span_at_position(uri.as_str(), position)? else {
+            return Ok(None);
+        };
+
+        let Some(definition) = self.db.attach(|db| dada_probe::probe_definition(db, span)) else {
+            return Ok(None);
+        };
+
+        let location = self.definition_location(definition)?;
+        Ok(Some(lsp_types::GotoDefinitionResponse::Scalar(location)))
+    }
+
+    fn code_action(
+        &mut self,
+        _editor: &mut dyn Editor<Self>,
+        params: lsp_types::CodeActionParams,
+    ) -> Fallible<Option<lsp_types::CodeActionResponse>> {
+        let lsp_types::CodeActionParams {
+            text_document: lsp_types::TextDocumentIdentifier { uri },
+            range,
+            context: _,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        let source_file = self.db.get_previously_opened_source_file(uri.as_str())?;
+        let start = Self::absolute_offset(&self.db, source_file, range.start);
+        let end = Self::absolute_offset(&self.db, source_file, range.end);
+
+        // Offer one quick fix per suggestion on each diagnostic that overlaps the range.
+        let mut actions = vec![];
+        for diagnostic in self.db.check_all(source_file) {
+            let span = diagnostic.span;
+            if span.source_file != source_file || span.end < start || end < span.start {
+                continue;
+            }
+
+            for suggestion in diagnostic.all_suggestions() {
+                let edit = lsp_types::TextEdit {
+                    range: EditorDiagnostics::lsp_range(&self.db, suggestion.span),
+                    new_text: suggestion.replacement.clone(),
+                };
+                let changes = std::iter::once((uri.clone(), vec![edit])).collect();
+                actions.push(lsp_types::CodeActionOrCommand::CodeAction(
+                    lsp_types::CodeAction {
+                        title: suggestion.message.clone(),
+                        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![EditorDiagnostics::lsp_diagnostic(
+                            &self.db, diagnostic,
+                        )]),
+                        edit: Some(lsp_types::WorkspaceEdit {
+                            changes: Some(changes),
+                            document_changes: None,
+                            change_annotations: None,
+                        }),
+                        command: None,
+                        is_preferred: None,
+                        disabled: None,
+                        data: None,
+                    },
+                ));
+            }
+        }
+
+        Ok(Some(actions))
+    }
+}
+
+impl Server {
+    /// Convert an LSP position in the (previously opened) document `uri` into an empty span.
+    /// Returns `None` if the position is outside of the document.
+    fn span_at_position(
+        &mut self,
+        uri: &str,
+        position: lsp_types::Position,
+    ) -> Fallible<Option<AbsoluteSpan>> {
+        // Get the source file
+        let source_file = self.db.get_previously_opened_source_file(uri)?;
+
+        // Make sure the line is valid
+        let line_starts = source_file.line_starts(&self.db);
+        if position.line as usize >= line_starts.len() - 1 {
+            return Ok(None);
+        }
+
+        // Convert LSP position to absolute offset
+        let offset = Self::absolute_offset(&self.db, source_file, position);
+
+        // Create a span at the position
+        Ok(Some(AbsoluteSpan {
+            source_file,
+            start: offset,
+            end: offset,
+        }))
+    }
+
+    /// Convert an LSP position into an offset in `source_file`.
+    ///
+    /// LSP columns count UTF-16 code units, so we have to walk the characters of the line.
+    /// Positions past the end of a line (or of the document) are clamped to its end,
+    /// as the LSP specification requires.
+    fn absolute_offset(
+        db: &Compiler,
+        source_file: SourceFile,
+        position: lsp_types::Position,
+    ) -> AbsoluteOffset {
+        let line_starts = source_file.line_starts(db);
+        let line = position.line as usize;
+        if line >= line_starts.len() - 1 {
+            return *line_starts.last().unwrap();
+        }
+
+        let line_start = line_starts[line].as_usize();
+        let line_text =
+            &source_file.contents_if_ok(db)[line_start..line_starts[line + 1].as_usize()];
+        let line_text = line_text.strip_suffix('\n').unwrap_or(line_text);
+        let line_text = line_text.strip_suffix('\r').unwrap_or(line_text);
+
+        let mut utf16_column = 0;
+        for (index, ch) in line_text.char_indices() {
+            if utf16_column >= position.character as usize {
+                return AbsoluteOffset::from(line_start + index);
+            }
+            utf16_column += ch.len_utf16();
+        }
+        AbsoluteOffset::from(line_start + line_text.len())
+    }
+
+    /// Location to report to the editor for a definition found at `span`.
+    ///
+    /// Files from `libdada` are embedded in the compiler and have `libdada:` URLs
+    /// that the editor cannot open, so we write their contents out to a
+    /// temporary directory and point the editor there instead.
+    fn definition_location(&self, span: AbsoluteSpan) -> Fallible<lsp_types::Location> {
+        let mut location = EditorDiagnostics::lsp_location(&self.db, span);
+
+        let url = span.source_file.url(&self.db);
+        if url.scheme() == "libdada" {
+            let path = std::env::temp_dir()
+                .join("dada-libdada")
+                .join(url.path().trim_start_matches('/'));
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, span.source_file.contents_if_ok(&self.db))?;
+
+            let Ok(file_url) = Url::from_file_path(&path) else {
+                bail!("cannot convert `{}` to a URL", path.display());
+            };
+            location.uri = EditorDiagnostics::lsp_uri(&file_url);
+        }
+
+        Ok(location)
+    }
 }
 
 struct ServerFork {
@@ -287,7 +439,9 @@ impl EditorDiagnostics {
         lsp_types::Diagnostic {
             range: Self::lsp_range(db, diagnostic.span),
             severity: Some(Self::lsp_severity(db, diagnostic.level)),
-            code: None,
+            code: diagnostic
+                .code
+                .map(|code| lsp_types::NumberOrString::String(code.to_string())),
             code_description: None,
             source: Some("Dada compiler".to_string()),
             message: diagnostic.message.clone(),
@@ -333,10 +487,13 @@ impl EditorDiagnostics {
         source_file: SourceFile,
         offset: AbsoluteOffset,
     ) -> lsp_types::Position {
-        let (line, column) = source_file.line_col(db, offset);
+        // LSP columns count UTF-16 code units, not bytes.
+        let (line, _) = source_file.line_col(db, offset);
+        let line_start = source_file.line_starts(db)[line.as_usize()];
+        let line_prefix = &source_file.contents_if_ok(db)[line_start.as_usize()..offset.as_usize()];
         lsp_types::Position {
             line: line.as_u32(),
-            character: column.as_u32(),
+            character: line_prefix.encode_utf16().count() as u32,
         }
     }
 
@@ -1,7 +1,8 @@
 use dada_ir_ast::{
     ast::{
         AstAggregate, AstAggregateKind, AstFieldDecl, AstFunction, AstGenericDecl, AstMember,
-        AstTy, AstTyKind, AstVisibility, AstWhereClauses, SpanVec, VariableDecl, VisibilityKind,
+        AstTy, AstTyKind, AstVariant, AstVisibility, AstWhereClauses, SpanVec, VariableDecl,
+        VisibilityKind,
     },
     span::{Span, Spanned},
 };
@@ -44,12 +45,17 @@ impl<'db> Parse<'db> for AstAggregate<'db> {
             AstGenericDecl::eat_comma,
         )?;
 
-        let inputs = AstFieldDecl::opt_parse_delimited(
-            db,
-            parser,
-            Delimiter::Parentheses,
-            AstFieldDecl::eat_comma,
-        )?;
+        // Enums store their data in their variants, so they have no `()` section.
+        let inputs = if aggregate_kind == AstAggregateKind::Enum {
+            None
+        } else {
+            AstFieldDecl::opt_parse_delimited(
+                db,
+                parser,
+                Delimiter::Parentheses,
+                AstFieldDecl::eat_comma,
+            )?
+        };
 
         let where_clauses = AstWhereClauses::opt_parse(db, parser)?;
 
@@ -108,6 +114,12 @@ impl<'db> Parse<'db> for AstAggregatePrefix<'db> {
                 aggregate_kind: AstAggregateKind::Struct,
                 aggregate_keyword: span,
             }))
+        } else if let Ok(span) = parser.eat_keyword(Keyword::Enum) {
+            Ok(Some(AstAggregatePrefix {
+                visibility,
+                aggregate_kind: AstAggregateKind::Enum,
+                aggregate_keyword: span,
+            }))
         } else {
             Ok(None)
         }
@@ -123,8 +135,13 @@ impl<'db> crate::prelude::ClassItemMembers<'db> for AstAggregate<'db> {
     #[salsa::tracked(return_ref)]
     fn members(self, db: &'db dyn crate::Db) -> SpanVec<'db, AstMember<'db>> {
         if let Some(contents) = self.contents(db) {
-            Parser::deferred(db, self, contents, |parser| {
-                parser.parse_many_and_report_diagnostics::<AstMember<'db>>(db)
+            Parser::deferred(db, self, contents, |parser| match self.kind(db) {
+                AstAggregateKind::Class | AstAggregateKind::Struct => {
+                    parser.parse_many_and_report_diagnostics::<AstMember<'db>>(db)
+                }
+                AstAggregateKind::Enum => {
+                    parser.parse_many_and_report_diagnostics::<EnumMember>(db)
+                }
             })
         } else {
             SpanVec {
@@ -156,6 +173,59 @@ impl<'db> Parse<'db> for AstMember<'db> {
     }
 }
 
+/// Members of an enum: like class members, except that
+/// variants take the place of fields.
+struct EnumMember;
+
+impl<'db> Parse<'db> for EnumMember {
+    type Output = AstMember<'db>;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<AstMember<'db>>, super::ParseFail<'db>> {
+        // As with `AstMember`, try `fn` first, since it has a distinctive keyword.
+        AstFunction::opt_parse(db, parser)
+            .or_opt_parse::<AstMember<'db>, AstVariant<'db>>(db, parser)
+    }
+
+    fn expected() -> Expected {
+        Expected::Nonterminal("enum variant")
+    }
+}
+
+/// `Name` or `Name(field: Ty, ...)`
+impl<'db> Parse<'db> for AstVariant<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, super::ParseFail<'db>> {
+        let Ok(name) = parser.eat_id() else {
+            return Ok(None);
+        };
+
+        let fields = AstFieldDecl::opt_parse_delimited(
+            db,
+            parser,
+            Delimiter::Parentheses,
+            AstFieldDecl::eat_comma,
+        )?;
+
+        Ok(Some(AstVariant::new(
+            db,
+            name.span.to(db, parser.last_span()),
+            name,
+            fields,
+        )))
+    }
+
+    fn expected() -> Expected {
+        Expected::Nonterminal("enum variant")
+    }
+}
+
 impl<'db> Parse<'db> for AstFieldDecl<'db> {
     type Output = Self;
 
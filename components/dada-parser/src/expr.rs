@@ -1,14 +1,16 @@
 use dada_ir_ast::ast::{
-    AstBinaryOp, AstBlock, AstConstructorField, AstExpr, AstExprKind, AstPath, AstPathKind,
-    DeferredParse, Identifier, IfArm, Literal, LiteralKind, PermissionOp, SpannedBinaryOp,
-    SpannedIdentifier, SpannedUnaryOp, SquareBracketArgs, UnaryOp,
+    AstBinaryOp, AstBlock, AstConstructorField, AstExpr, AstExprKind, AstMatchArm, AstPath,
+    AstPathKind, AstPattern, AstPatternKind, AstStringPart, DeferredParse, Identifier, IfArm,
+    Literal, LiteralKind, PermissionOp, SpannedBinaryOp, SpannedIdentifier, SpannedUnaryOp,
+    SquareBracketArgs, UnaryOp,
 };
 
 use crate::{
     Parse, Parser,
     tokenizer::{
-        Keyword, Token, TokenKind,
+        Keyword, StringPart, Token, TokenKind,
         operator::{self, Op},
+        tokenize,
     },
 };
 
@@ -63,6 +65,13 @@ fn opt_parse_expr_with_precedence<'db>(
 }
 
 const BINARY_OP_PRECEDENCE: &[&[(Op, AstBinaryOp)]] = &[
+    &[(operator::PIPE, AstBinaryOp::BitOr)],
+    &[(operator::CARET, AstBinaryOp::BitXor)],
+    &[(operator::AND, AstBinaryOp::BitAnd)],
+    &[
+        (operator::LESSLESS, AstBinaryOp::Shl),
+        (operator::GREATERGREATER, AstBinaryOp::Shr),
+    ],
     &[
         (operator::PLUS, AstBinaryOp::Add),
         (operator::MINUS, AstBinaryOp::Sub),
@@ -70,6 +79,7 @@ const BINARY_OP_PRECEDENCE: &[&[(Op, AstBinaryOp)]] = &[
     &[
         (operator::STAR, AstBinaryOp::Mul),
         (operator::SLASH, AstBinaryOp::Div),
+        (operator::PERCENT, AstBinaryOp::Rem),
     ],
     &[
         (operator::GREATERTHANEQ, AstBinaryOp::GreaterEqual),
@@ -77,6 +87,7 @@ const BINARY_OP_PRECEDENCE: &[&[(Op, AstBinaryOp)]] = &[
         (operator::GREATERTHAN, AstBinaryOp::GreaterThan),
         (operator::LESSTHAN, AstBinaryOp::LessThan),
         (operator::EQEQ, AstBinaryOp::EqualEqual),
+        (operator::BANGEQ, AstBinaryOp::NotEqual),
     ],
     &[(operator::ANDAND, AstBinaryOp::AndAnd)],
     &[(operator::PIPEPIPE, AstBinaryOp::OrOr)],
@@ -233,10 +244,66 @@ fn base_expr_precedence<'db, const SELECT: u32>(
         return Ok(Some(AstExprKind::Literal(literal)));
     }
 
+    if let Some(parts) = opt_parse_interpolated_string(db, parser)? {
+        return Ok(Some(AstExprKind::InterpolatedString(parts)));
+    }
+
     if let Ok(if_span) = parser.eat_keyword(Keyword::If) {
         return Ok(Some(if_chain(db, parser, if_span)?));
     }
 
+    if parser.eat_keyword(Keyword::Match).is_ok() {
+        // As with `if`, the scrutinee cannot be a struct expression,
+        // since `match x { ... }` would be ambiguous.
+        let scrutinee = eat_expr_with_precedence(
+            db,
+            parser,
+            binary_expr_precedence::<{ SELECT_ALL - SELECT_STRUCT }>,
+        )?;
+        let Some(arms) = AstMatchArm::opt_parse_delimited(
+            db,
+            parser,
+            crate::tokenizer::Delimiter::CurlyBraces,
+            AstMatchArm::eat_many,
+        )?
+        else {
+            return Err(parser.illformed(crate::Expected::Delimited(
+                crate::tokenizer::Delimiter::CurlyBraces,
+            )));
+        };
+        return Ok(Some(AstExprKind::Match { scrutinee, arms }));
+    }
+
+    if parser.eat_keyword(Keyword::While).is_ok() {
+        // As with `if`, the condition cannot be a struct expression,
+        // since `while x { ... }` would be ambiguous.
+        let condition = eat_expr_with_precedence(
+            db,
+            parser,
+            binary_expr_precedence::<{ SELECT_ALL - SELECT_STRUCT }>,
+        )?;
+        let body = AstBlock::eat(db, parser)?;
+        return Ok(Some(AstExprKind::While { condition, body }));
+    }
+
+    if parser.eat_keyword(Keyword::Loop).is_ok() {
+        let body = AstBlock::eat(db, parser)?;
+        return Ok(Some(AstExprKind::Loop(body)));
+    }
+
+    if parser.eat_keyword(Keyword::Unsafe).is_ok() {
+        let body = AstBlock::eat(db, parser)?;
+        return Ok(Some(AstExprKind::Unsafe(body)));
+    }
+
+    if parser.eat_keyword(Keyword::Break).is_ok() {
+        return Ok(Some(AstExprKind::Break));
+    }
+
+    if parser.eat_keyword(Keyword::Continue).is_ok() {
+        return Ok(Some(AstExprKind::Continue));
+    }
+
     if let Ok(id) = parser.eat_id() {
         // Could be `X { field1: value1, .. }`
         if (SELECT & SELECT_STRUCT != 0)
@@ -273,7 +340,7 @@ fn base_expr_precedence<'db, const SELECT: u32>(
         return Ok(Some(AstExprKind::Return(None)));
     }
 
-    if let Ok(span) = parser.eat_op(operator::BANG) {
+    if let Ok(span) = parser.eat_prefix_op(operator::BANG) {
         let expr = eat_expr_with_precedence(db, parser, postfix_expr_precedence::<SELECT>)?;
         return Ok(Some(AstExprKind::UnaryOp(
             SpannedUnaryOp {
@@ -284,7 +351,7 @@ fn base_expr_precedence<'db, const SELECT: u32>(
         )));
     }
 
-    if let Ok(span) = parser.eat_op(operator::MINUS) {
+    if let Ok(span) = parser.eat_prefix_op(operator::MINUS) {
         let expr = eat_expr_with_precedence(db, parser, postfix_expr_precedence::<SELECT>)?;
         return Ok(Some(AstExprKind::UnaryOp(
             SpannedUnaryOp {
@@ -298,6 +365,46 @@ fn base_expr_precedence<'db, const SELECT: u32>(
     Ok(None)
 }
 
+/// Parses a string literal with `{...}` interpolations.
+/// The tokenizer has already split it into parts; here we parse the text of each
+/// interpolated expression.
+fn opt_parse_interpolated_string<'db>(
+    db: &'db dyn crate::Db,
+    parser: &mut Parser<'_, 'db>,
+) -> Result<Option<Vec<AstStringPart<'db>>>, crate::ParseFail<'db>> {
+    let Some(Token {
+        kind: TokenKind::InterpolatedString(token_parts),
+        ..
+    }) = parser.peek()
+    else {
+        return Ok(None);
+    };
+    let token_parts = token_parts.clone();
+    parser.eat_next_token().unwrap();
+
+    let mut parts = vec![];
+    for token_part in token_parts {
+        match token_part {
+            StringPart::Text(token_text) => {
+                parts.push(AstStringPart::Text(token_text.text(db).clone()));
+            }
+            StringPart::Expr { span, text } => {
+                if text.trim().is_empty() {
+                    return Err(crate::ParseFail::Expected(span, AstExpr::expected()));
+                }
+
+                let tokens = tokenize(db, span.anchor, span.start, text);
+                let mut parser1 = Parser::new(db, span.anchor, &tokens);
+                let expr = AstExpr::eat(db, &mut parser1);
+                parser.take_diagnostics(parser1);
+                parts.push(AstStringPart::Expr(expr?));
+            }
+        }
+    }
+
+    Ok(Some(parts))
+}
+
 fn if_chain<'db>(
     db: &'db dyn crate::Db,
     parser: &mut Parser<'_, 'db>,
@@ -341,6 +448,69 @@ fn if_chain<'db>(
     Ok(AstExprKind::If(arms))
 }
 
+/// `P => E`, optionally followed by a comma
+impl<'db> Parse<'db> for AstMatchArm<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self::Output>, crate::ParseFail<'db>> {
+        let Some(pattern) = AstPattern::opt_parse(db, parser)? else {
+            return Ok(None);
+        };
+        parser.eat_op(operator::FAT_ARROW)?;
+        let result = AstExpr::eat(db, parser)?;
+        let _ = parser.eat_op(operator::COMMA);
+        Ok(Some(AstMatchArm { pattern, result }))
+    }
+
+    fn expected() -> crate::Expected {
+        crate::Expected::Nonterminal("match arm")
+    }
+}
+
+/// `_`, `x`, or `Path(P0, ..., Pn)`
+impl<'db> Parse<'db> for AstPattern<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self::Output>, crate::ParseFail<'db>> {
+        let start_span = parser.peek_span();
+        let Some(path) = AstPath::opt_parse(db, parser)? else {
+            return Ok(None);
+        };
+
+        let fields = AstPattern::opt_parse_delimited(
+            db,
+            parser,
+            crate::tokenizer::Delimiter::Parentheses,
+            AstPattern::eat_comma,
+        )?;
+
+        // A lone identifier binds a variable (or, if it is `_`, ignores the value);
+        // anything else names a variant.
+        let kind = match (path.kind(db), fields) {
+            (&AstPathKind::Identifier(id), None) if id.id == Identifier::underscore(db) => {
+                AstPatternKind::Wildcard
+            }
+            (&AstPathKind::Identifier(id), None) => AstPatternKind::Binding(id),
+            (_, fields) => AstPatternKind::Variant(path, fields),
+        };
+
+        Ok(Some(AstPattern {
+            span: start_span.to(db, parser.last_span()),
+            kind,
+        }))
+    }
+
+    fn expected() -> crate::Expected {
+        crate::Expected::Nonterminal("pattern")
+    }
+}
+
 impl<'db> Parse<'db> for PermissionOp {
     type Output = Self;
 
@@ -4,7 +4,7 @@ use dada_ir_ast::{
         AstLetStatement, AstPerm, AstSelfArg, AstStatement, AstTy, AstVisibility, AstWhereClauses,
         SpanVec, VariableDecl,
     },
-    diagnostic::{Diagnostic, Level},
+    diagnostic::{Diagnostic, DiagnosticCode, Level},
     span::Span,
 };
 use salsa::Update;
@@ -160,6 +160,7 @@ fn report_duplicate_keyword<'db>(
     prev_span: Span<'db>,
 ) {
     Diagnostic::error(db, span, format!("duplicate `{kw}` keyword"))
+        .code(DiagnosticCode::DuplicateEffect)
         .label(
             db,
             Level::Error,
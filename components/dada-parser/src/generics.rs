@@ -1,5 +1,5 @@
 use dada_ir_ast::ast::{
-    AstGenericDecl, AstGenericKind, AstGenericTerm, AstWhereClause, AstWhereClauseKind,
+    AstGenericDecl, AstGenericKind, AstGenericTerm, AstPath, AstWhereClause, AstWhereClauseKind,
     AstWhereClauses, SpanVec,
 };
 
@@ -106,7 +106,7 @@ impl<'db> Parse<'db> for AstWhereClauseKind<'db> {
     type Output = AstWhereClauseKind<'db>;
 
     fn opt_parse(
-        _db: &'db dyn crate::Db,
+        db: &'db dyn crate::Db,
         parser: &mut Parser<'_, 'db>,
     ) -> Result<Option<Self::Output>, ParseFail<'db>> {
         if let Ok(span) = parser.eat_keyword(Keyword::Ref) {
@@ -121,6 +121,8 @@ impl<'db> Parse<'db> for AstWhereClauseKind<'db> {
             Ok(Some(AstWhereClauseKind::Unique(span)))
         } else if let Ok(span) = parser.eat_keyword(Keyword::Lent) {
             Ok(Some(AstWhereClauseKind::Lent(span)))
+        } else if let Some(path) = AstPath::opt_parse(db, parser)? {
+            Ok(Some(AstWhereClauseKind::Trait(path)))
         } else {
             Ok(None)
         }
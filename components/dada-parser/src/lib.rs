@@ -9,7 +9,7 @@ use tokenizer::{
 
 use dada_ir_ast::{
     ast::{AstModule, DeferredParse, SpanVec, SpannedIdentifier},
-    diagnostic::{Diagnostic, Level, Reported},
+    diagnostic::{Diagnostic, DiagnosticCode, Level, Reported},
     inputs::SourceFile,
     span::{Anchor, Offset, Span, Spanned},
 };
@@ -25,6 +25,7 @@ mod module_body;
 pub mod prelude;
 mod square_bracket_args;
 mod tokenizer;
+mod traits;
 mod types;
 
 #[salsa::tracked]
@@ -33,7 +34,9 @@ impl prelude::SourceFileParse for SourceFile {
     fn parse(self, db: &dyn crate::Db) -> AstModule<'_> {
         let anchor = Anchor::SourceFile(self);
         if let Err(message) = self.contents(db) {
-            Diagnostic::new(db, Level::Error, self.span(db), message).report(db);
+            Diagnostic::new(db, Level::Error, self.span(db), message)
+                .code(DiagnosticCode::UnreadableSourceFile)
+                .report(db);
         }
         let text = self.contents_if_ok(db);
         let tokens = tokenizer::tokenize(db, anchor, Offset::ZERO, text);
@@ -404,6 +407,25 @@ impl<'token, 'db> Parser<'token, 'db> {
         Ok(start_span.to(self.db, self.last_span()))
     }
 
+    /// Eats the single-character operator `op` used as a prefix operator.
+    /// Unlike [`Self::eat_op`], the operator need not stand alone,
+    /// so that prefix operators can be stacked, as in `--x` or `!-x`.
+    pub fn eat_prefix_op(&mut self, op: Op) -> Result<Span<'db>, ParseFail<'db>> {
+        assert_eq!(op.len(), 1, "prefix operators are a single character");
+
+        if let Some(&Token {
+            kind: TokenKind::OpChar(ch),
+            span,
+            skipped: _,
+        }) = self.peek()
+            && ch == op[0]
+        {
+            self.eat_next_token().unwrap();
+            return Ok(span);
+        }
+        Err(self.illformed(Expected::Operator(op)))
+    }
+
     /// Returns a deferred parse of the next delimited token.
     /// If this returns `Err`, then nothing has been consumed.
     pub fn defer_delimited(
@@ -692,17 +714,20 @@ impl ParseFail<'_> {
     pub fn into_diagnostic(self, db: &dyn crate::Db) -> Diagnostic {
         return match self {
             ParseFail::Expected(span, Expected::EOF) => {
-                Diagnostic::error(db, span, "extra input".to_string()).label(
-                    db,
-                    Level::Error,
-                    span,
-                    "I don't know what to do with this, it appears to be extra".to_string(),
-                )
+                Diagnostic::error(db, span, "extra input".to_string())
+                    .code(DiagnosticCode::ExtraInput)
+                    .label(
+                        db,
+                        Level::Error,
+                        span,
+                        "I don't know what to do with this, it appears to be extra".to_string(),
+                    )
             }
 
             ParseFail::ExpectedTokenToBeFollowedBy(span, next_span, expected) => {
                 let message = expected_to_string(db, expected);
                 Diagnostic::error(db, span, format!("expected {message} to come next"))
+                    .code(DiagnosticCode::ExpectedSyntax)
                     .label(
                         db,
                         Level::Error,
@@ -719,12 +744,14 @@ impl ParseFail<'_> {
 
             ParseFail::Expected(span, expected) => {
                 let message = expected_to_string(db, expected);
-                Diagnostic::error(db, span, format!("expected {message}")).label(
-                    db,
-                    Level::Error,
-                    span,
-                    format!("I expected to see {message}, not this"),
-                )
+                Diagnostic::error(db, span, format!("expected {message}"))
+                    .code(DiagnosticCode::ExpectedSyntax)
+                    .label(
+                        db,
+                        Level::Error,
+                        span,
+                        format!("I expected to see {message}, not this"),
+                    )
             }
         };
 
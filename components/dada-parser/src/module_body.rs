@@ -1,9 +1,9 @@
 use dada_ir_ast::{
     ast::{
-        AstAggregate, AstFunction, AstItem, AstMainFunction, AstModule, AstPath, AstStatement,
-        AstUse, SpanVec,
+        AstAggregate, AstFunction, AstImpl, AstItem, AstMainFunction, AstModule, AstPath,
+        AstStatement, AstTrait, AstUse, SpanVec,
     },
-    diagnostic::Diagnostic,
+    diagnostic::{Diagnostic, DiagnosticCode},
     span::Spanned,
 };
 
@@ -57,11 +57,14 @@ impl<'db> Parse<'db> for AstModule<'db> {
             }
 
             parser.eat_next_token().unwrap();
-            parser.push_diagnostic(Diagnostic::error(
-                db,
-                parser.last_span(),
-                "expected a statement or a module-level item",
-            ));
+            parser.push_diagnostic(
+                Diagnostic::error(
+                    db,
+                    parser.last_span(),
+                    "expected a statement or a module-level item",
+                )
+                .code(DiagnosticCode::ExpectedModuleItem),
+            );
         }
 
         // If we have statements on their own, wrap them in a `main` function
@@ -102,12 +105,14 @@ impl<'db> Parse<'db> for AstItem<'db> {
         parser: &mut Parser<'_, 'db>,
     ) -> Result<Option<Self>, ParseFail<'db>> {
         AstAggregate::opt_parse(db, parser)
+            .or_opt_parse::<Self, AstTrait<'db>>(db, parser)
+            .or_opt_parse::<Self, AstImpl<'db>>(db, parser)
             .or_opt_parse::<Self, AstUse<'db>>(db, parser)
             .or_opt_parse::<Self, AstFunction<'db>>(db, parser)
     }
 
     fn expected() -> Expected {
-        panic!("module-level item (class, function, use)")
+        panic!("module-level item (class, trait, impl, function, use)")
     }
 }
 
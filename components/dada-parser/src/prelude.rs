@@ -1,4 +1,7 @@
-use dada_ir_ast::ast::{AstBlock, AstGenericTerm, AstMember};
+use dada_ir_ast::{
+    ast::{AstBlock, AstExpr, AstGenericTerm, AstMember, AstTraitMember},
+    diagnostic::Errors,
+};
 
 use super::*;
 
@@ -12,6 +15,11 @@ pub trait ClassItemMembers<'db> {
     fn members(self, db: &'db dyn crate::Db) -> &'db SpanVec<'db, AstMember<'db>>;
 }
 
+/// Given a [`dada_ir_ast::ast::AstTrait`] or [`dada_ir_ast::ast::AstImpl`], parse its members
+pub trait TraitItemMembers<'db> {
+    fn members(self, db: &'db dyn crate::Db) -> &'db SpanVec<'db, AstTraitMember<'db>>;
+}
+
 /// Given a [`dada_ir_ast::ast::AstFunction`], parse its associated body into a block
 pub trait FunctionBlock<'db> {
     fn body_block(self, db: &'db dyn crate::Db) -> Option<AstBlock<'db>>;
@@ -20,4 +28,7 @@ pub trait FunctionBlock<'db> {
 /// Given a [`SquareBracketArgs`], parse its associated body into a block
 pub trait SquareBracketArgs<'db> {
     fn parse_as_generics(self, db: &'db dyn crate::Db) -> SpanVec<'db, AstGenericTerm<'db>>;
+
+    /// Parse the contents as the index expression of an indexing expression like `v[i]`.
+    fn parse_as_index(self, db: &'db dyn crate::Db) -> Errors<AstExpr<'db>>;
 }
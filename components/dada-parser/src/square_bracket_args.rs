@@ -1,6 +1,9 @@
-use dada_ir_ast::ast::{AstGenericTerm, SpanVec, SquareBracketArgs};
+use dada_ir_ast::{
+    ast::{AstExpr, AstGenericTerm, SpanVec, SquareBracketArgs},
+    diagnostic::Errors,
+};
 
-use crate::Parser;
+use crate::{Parse, Parser};
 
 #[salsa::tracked]
 impl<'db> crate::prelude::SquareBracketArgs<'db> for SquareBracketArgs<'db> {
@@ -15,4 +18,18 @@ impl<'db> crate::prelude::SquareBracketArgs<'db> for SquareBracketArgs<'db> {
             parser.parse_many_and_report_diagnostics::<AstGenericTerm<'db>>(db)
         })
     }
+
+    #[salsa::tracked]
+    fn parse_as_index(self, db: &'db dyn crate::Db) -> Errors<AstExpr<'db>> {
+        let deferred = self.deferred(db);
+        let anchor = deferred.span.anchor;
+        Parser::deferred(db, anchor, deferred, |mut parser| {
+            let result =
+                AstExpr::eat(db, &mut parser).map_err(|err| err.into_diagnostic(db).report(db));
+            for diagnostic in parser.into_diagnostics() {
+                diagnostic.report(db);
+            }
+            result
+        })
+    }
 }
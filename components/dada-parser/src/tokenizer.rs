@@ -2,7 +2,7 @@ use dada_util::Map;
 
 use dada_ir_ast::{
     ast::{Identifier, LiteralKind},
-    diagnostic::{Diagnostic, Level},
+    diagnostic::{Diagnostic, DiagnosticCode, Level},
     span::{Anchor, Offset, Span},
 };
 
@@ -61,13 +61,30 @@ pub enum TokenKind<'input, 'db> {
     /// An op-char like `+`, `-`, etc.
     OpChar(char),
 
-    /// An integer like `22`
+    /// A literal like `22`, `1.5`, `'c'`, or `"foo"`
     Literal(LiteralKind, TokenText<'db>),
 
+    /// A string literal containing at least one `{...}` interpolation, like `"x = {x}"`.
+    InterpolatedString(Vec<StringPart<'input, 'db>>),
+
     /// Invalid characters
     Error(Diagnostic),
 }
 
+/// One piece of an interpolated string literal.
+#[derive(Clone, Debug)]
+pub enum StringPart<'input, 'db> {
+    /// Literal text, with escape sequences (and dedenting) already applied.
+    Text(TokenText<'db>),
+
+    /// The source text of an interpolated expression, excluding the `{}`.
+    Expr { span: Span<'db>, text: &'input str },
+}
+
+/// Stands in for the interpolated expressions of a string literal while
+/// we dedent and process escapes in the surrounding text.
+const INTERPOLATION_PLACEHOLDER: char = '\u{FFFC}';
+
 macro_rules! keywords {
     (pub enum $Keyword:ident {
         $($kw:ident = $kwstr:expr,)*
@@ -101,7 +118,9 @@ keywords! {
         Await = "await",
         Box = "box",
         Boxed = "boxed",
+        Break = "break",
         Class = "class",
+        Continue = "continue",
         Crate = "crate",
         Dyn = "dyn",
         Else = "else",
@@ -109,10 +128,13 @@ keywords! {
         Export = "export",
         False = "false",
         Fn = "fn",
+        For = "for",
         If = "if",
+        Impl = "impl",
         Is = "is",
         Lent = "lent",
         Let = "let",
+        Loop = "loop",
         Give = "give",
         Given = "given",
         Match = "match",
@@ -132,12 +154,14 @@ keywords! {
         Shared = "shared",
         Struct = "struct",
         Tracked = "tracked",
+        Trait = "trait",
         True = "true",
         Type = "type",
         Unique = "unique",
         Unsafe = "unsafe",
         Use = "use",
         Where = "where",
+        While = "while",
     }
 }
 
@@ -180,19 +204,23 @@ pub mod operator {
     pub const MINUS: Op = Op(&['-']);
     pub const STAR: Op = Op(&['*']);
     pub const SLASH: Op = Op(&['/']);
-    #[expect(dead_code)]
+    pub const PERCENT: Op = Op(&['%']);
     pub const AND: Op = Op(&['&']);
     pub const ANDAND: Op = Op(&['&', '&']);
-    #[expect(dead_code)]
     pub const PIPE: Op = Op(&['|']);
     pub const PIPEPIPE: Op = Op(&['|', '|']);
+    pub const CARET: Op = Op(&['^']);
     pub const LESSTHAN: Op = Op(&['<']);
     pub const LESSTHANEQ: Op = Op(&['<', '=']);
+    pub const LESSLESS: Op = Op(&['<', '<']);
     pub const GREATERTHAN: Op = Op(&['>']);
     pub const GREATERTHANEQ: Op = Op(&['>', '=']);
+    pub const GREATERGREATER: Op = Op(&['>', '>']);
     pub const EQ: Op = Op(&['=']);
     pub const EQEQ: Op = Op(&['=', '=']);
+    pub const BANGEQ: Op = Op(&['!', '=']);
     pub const ARROW: Op = Op(&['-', '>']);
+    pub const FAT_ARROW: Op = Op(&['=', '>']);
     pub const DOT: Op = Op(&['.']);
     pub const COLON: Op = Op(&[':']);
     pub const BANG: Op = Op(&['!']);
@@ -279,11 +307,12 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                 '[' => self.delimited(index, Delimiter::SquareBrackets, ']'),
                 '(' => self.delimited(index, Delimiter::Parentheses, ')'),
 
-                // Integers
-                _ if ch.is_ascii_digit() => self.integer(index, ch),
+                // Numbers
+                _ if ch.is_ascii_digit() => self.number(index, ch),
 
-                // Strings
+                // Strings and characters
                 '"' => self.string_literal(index),
+                '\'' => self.char_literal(index),
 
                 // Newline
                 '\n' => {
@@ -330,12 +359,14 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                 span,
                 skipped: None,
                 kind: TokenKind::Error(
-                    Diagnostic::error(self.db, span, "unrecognized characters(s)").label(
-                        self.db,
-                        Level::Error,
-                        span,
-                        "I don't know how to interpret these characters",
-                    ),
+                    Diagnostic::error(self.db, span, "unrecognized characters(s)")
+                        .code(DiagnosticCode::UnrecognizedCharacters)
+                        .label(
+                            self.db,
+                            Level::Error,
+                            span,
+                            "I don't know how to interpret these characters",
+                        ),
                 ),
             });
         }
@@ -396,29 +427,144 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         }
     }
 
-    fn integer(&mut self, start: usize, ch: char) {
+    /// Tokenize a number like `22`, `0xFF`, `1.5e-3`, or `22_u8`.
+    /// The token text is the source text; digits and suffixes are validated
+    /// by the type checker, which knows which suffixes name types.
+    fn number(&mut self, start: usize, ch: char) {
         let skipped = self.clear_accumulated(start);
 
-        let mut end = start + ch.len_utf8();
+        let rest = &self.input[start..];
+        let bytes = rest.as_bytes();
+        let mut kind = LiteralKind::Integer;
 
-        while let Some(&(index, ch)) = self.chars.peek() {
-            if ch.is_ascii_digit() || ch == '_' {
-                end = index + ch.len_utf8();
-                self.chars.next();
+        // Radix prefix, like `0x`.
+        let radix_prefix = ch == '0' && matches!(bytes.get(1), Some(b'x' | b'o' | b'b'));
+        let mut len = if radix_prefix { 2 } else { 1 };
+
+        // Digits. For radix prefixes we accept any alphanumeric characters
+        // so that a typo like `0b102` is reported as one bad literal.
+        let is_digit = |b: u8| {
+            if radix_prefix {
+                b.is_ascii_hexdigit() || b == b'_'
             } else {
-                break;
+                b.is_ascii_digit() || b == b'_'
+            }
+        };
+        while bytes.get(len).is_some_and(|&b| is_digit(b)) {
+            len += 1;
+        }
+
+        if !radix_prefix {
+            // Fraction, like `.5`. We require a digit after the `.`
+            // so that `22.foo()` is still a method call.
+            if bytes.get(len) == Some(&b'.') && bytes.get(len + 1).is_some_and(u8::is_ascii_digit) {
+                kind = LiteralKind::Float;
+                len += 1;
+                while bytes.get(len).is_some_and(|&b| is_digit(b)) {
+                    len += 1;
+                }
             }
+
+            // Exponent, like `e10` or `E-3`.
+            if matches!(bytes.get(len), Some(b'e' | b'E')) {
+                let sign = usize::from(matches!(bytes.get(len + 1), Some(b'+' | b'-')));
+                if bytes.get(len + 1 + sign).is_some_and(u8::is_ascii_digit) {
+                    kind = LiteralKind::Float;
+                    len += 1 + sign;
+                    while bytes.get(len).is_some_and(|&b| is_digit(b)) {
+                        len += 1;
+                    }
+                }
+            }
+        }
+
+        // Type suffix, like `u8` or `f32`.
+        let suffix_start = len;
+        while bytes
+            .get(len)
+            .is_some_and(|&b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            len += 1;
         }
+        if !radix_prefix && bytes.get(suffix_start) == Some(&b'f') {
+            kind = LiteralKind::Float;
+        }
+
+        let end = start + len;
+        while self.chars.next_if(|&(index, _)| index < end).is_some() {}
 
         let span = self.span(start, end);
+        let token_text = TokenText::new(self.db, self.input[start..end].to_string());
+        self.tokens.push(Token {
+            span,
+            skipped,
+            kind: TokenKind::Literal(kind, token_text),
+        });
+    }
 
-        let text = &self.input[start..end];
-        let token_text = TokenText::new(self.db, text.to_string());
+    /// Tokenize a character literal like `'c'` or `'\n'`, after consuming the opening `'`.
+    /// Like an unterminated string, a malformed character literal produces a literal token
+    /// (so that parsing can continue) followed by an error token.
+    fn char_literal(&mut self, start: usize) {
+        let skipped = self.clear_accumulated(start);
+        let tokens_before = self.tokens.len();
+
+        let mut content = String::new();
+        let mut end = start + 1;
+        let mut terminated = false;
+        while let Some(&(index, ch)) = self.chars.peek() {
+            if ch == '\n' {
+                break;
+            }
+
+            self.chars.next();
+            end = index + ch.len_utf8();
+            match ch {
+                '\'' => {
+                    terminated = true;
+                    break;
+                }
+                '\\' => self.escape_sequence(index, &mut content),
+                _ => content.push(ch),
+            }
+        }
+
+        // An invalid escape has already been reported and leaves extra characters in `content`.
+        let escape_reported = self.tokens.len() > tokens_before;
+
+        let message = if !terminated {
+            Some("missing `'` to end character literal")
+        } else if content.chars().count() != 1 && !escape_reported {
+            Some("character literals must contain exactly one character")
+        } else {
+            None
+        };
+
+        let span = self.span(start, end);
+        let ch = content.chars().next().unwrap_or('\0');
+        let token_text = TokenText::new(self.db, ch.to_string());
         self.tokens.push(Token {
             span,
             skipped,
-            kind: TokenKind::Literal(LiteralKind::Integer, token_text),
+            kind: TokenKind::Literal(LiteralKind::Char, token_text),
         });
+
+        if let Some(message) = message {
+            self.tokens.push(Token {
+                span,
+                skipped: None,
+                kind: TokenKind::Error(
+                    Diagnostic::error(self.db, span, message)
+                        .code(DiagnosticCode::InvalidLiteral)
+                        .label(
+                            self.db,
+                            Level::Error,
+                            span,
+                            "I expected a single character between `'` quotes, like `'c'`",
+                        ),
+                ),
+            });
+        }
     }
 
     /// Process an escape sequence after consuming `\`.
@@ -427,6 +573,7 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         if let Some((index, escape)) = self.chars.next() {
             match escape {
                 '"' => content.push('"'),
+                '\'' => content.push('\''),
                 '\\' => content.push('\\'),
                 'n' => content.push('\n'),
                 'r' => content.push('\r'),
@@ -441,11 +588,14 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                     self.tokens.push(Token {
                         span,
                         skipped: None,
-                        kind: TokenKind::Error(Diagnostic::error(
-                            self.db,
-                            span,
-                            format!("invalid escape `\\{escape}`"),
-                        )),
+                        kind: TokenKind::Error(
+                            Diagnostic::error(
+                                self.db,
+                                span,
+                                format!("invalid escape `\\{escape}`"),
+                            )
+                            .code(DiagnosticCode::InvalidEscape),
+                        ),
                     });
                 }
             }
@@ -456,11 +606,14 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
             self.tokens.push(Token {
                 span,
                 skipped: None,
-                kind: TokenKind::Error(Diagnostic::error(
-                    self.db,
-                    span,
-                    "`\\` must be followed by an escape character",
-                )),
+                kind: TokenKind::Error(
+                    Diagnostic::error(
+                        self.db,
+                        span,
+                        "`\\` must be followed by an escape character",
+                    )
+                    .code(DiagnosticCode::InvalidEscape),
+                ),
             });
         }
     }
@@ -471,6 +624,10 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
     /// When the raw source content begins with a newline, multiline dedenting is applied:
     /// the raw content is dedented and escape sequences are re-processed on the result,
     /// replacing the `content` that was built during scanning.
+    ///
+    /// `interpolations` are the (input-relative) byte ranges of the `{...}` interpolations,
+    /// excluding the braces themselves. If there are any, an
+    /// [`InterpolatedString`](`TokenKind::InterpolatedString`) token is emitted.
     fn emit_string_literal(
         &mut self,
         span: Span<'db>,
@@ -478,12 +635,24 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         content: String,
         quote_len: usize,
         raw: bool,
+        interpolations: Vec<(usize, usize)>,
     ) {
         // Extract the raw source content between the quote delimiters.
         let raw_start = (span.start - self.input_offset).as_usize() + quote_len;
         let raw_end = (span.end - self.input_offset).as_usize() - quote_len;
         let raw_content = &self.input[raw_start..raw_end];
 
+        if !interpolations.is_empty() {
+            return self.emit_interpolated_string_literal(
+                span,
+                skipped,
+                raw_start,
+                raw_end,
+                raw,
+                interpolations,
+            );
+        }
+
         // 💡 Multiline detection: if the raw content begins with a newline,
         // we apply dedenting on the raw source text (before escape processing)
         // and then re-process escapes. This ensures escape sequences like `\n`
@@ -511,6 +680,62 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         });
     }
 
+    /// Emit a string literal that contains interpolations (see [`Self::emit_string_literal`]).
+    ///
+    /// Each `{...}` in the raw content is replaced with [`INTERPOLATION_PLACEHOLDER`] so that
+    /// dedenting and escape processing apply to the surrounding text exactly as they would
+    /// for an ordinary string literal. We then split the result at the placeholders.
+    fn emit_interpolated_string_literal(
+        &mut self,
+        span: Span<'db>,
+        skipped: Option<Skipped>,
+        raw_start: usize,
+        raw_end: usize,
+        raw: bool,
+        interpolations: Vec<(usize, usize)>,
+    ) {
+        let mut template = String::new();
+        let mut text_start = raw_start;
+        for &(expr_start, expr_end) in &interpolations {
+            // `expr_start` and `expr_end` exclude the `{` and `}`
+            template.push_str(&self.input[text_start..expr_start - 1]);
+            template.push(INTERPOLATION_PLACEHOLDER);
+            text_start = expr_end + 1;
+        }
+        template.push_str(&self.input[text_start..raw_end]);
+
+        let template = if raw {
+            process_escape_sequences(&template[1..]) // skip `\`
+        } else if template.starts_with('\n') {
+            process_escape_sequences(&dedent_multiline(&template))
+        } else {
+            process_escape_sequences(&template)
+        };
+
+        let mut parts = vec![];
+        for (text, interpolation) in template
+            .split(INTERPOLATION_PLACEHOLDER)
+            .zip(interpolations.iter().map(Some).chain(Some(None)))
+        {
+            if !text.is_empty() {
+                parts.push(StringPart::Text(TokenText::new(self.db, text.to_string())));
+            }
+
+            if let Some(&(expr_start, expr_end)) = interpolation {
+                parts.push(StringPart::Expr {
+                    span: self.span(expr_start, expr_end),
+                    text: &self.input[expr_start..expr_end],
+                });
+            }
+        }
+
+        self.tokens.push(Token {
+            span,
+            skipped,
+            kind: TokenKind::InterpolatedString(parts),
+        });
+    }
+
     /// Emit tokens for an unterminated string literal: a literal token
     /// with whatever content was accumulated, plus an error token.
     /// No multiline dedenting is applied since the string is malformed.
@@ -531,7 +756,9 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         self.tokens.push(Token {
             span,
             skipped: None,
-            kind: TokenKind::Error(Diagnostic::error(self.db, span, message)),
+            kind: TokenKind::Error(
+                Diagnostic::error(self.db, span, message).code(DiagnosticCode::UnterminatedString),
+            ),
         });
     }
 
@@ -557,6 +784,7 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                 String::new(),
                 1,
                 false,
+                vec![],
             );
             return;
         }
@@ -578,6 +806,7 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         }
 
         let mut processed_content = String::new();
+        let mut interpolations = vec![];
 
         while let Some((end, ch)) = self.chars.next() {
             if ch == '"' {
@@ -587,12 +816,18 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                     processed_content,
                     1,
                     raw,
+                    interpolations,
                 );
                 return;
             }
 
             if ch == '\\' {
                 self.escape_sequence(end, &mut processed_content);
+            } else if ch == '{' {
+                let Some(close) = self.skip_interpolation() else {
+                    break;
+                };
+                interpolations.push((end + 1, close));
             } else {
                 processed_content.push(ch);
             }
@@ -610,6 +845,7 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
     /// has been consumed. Scans until the closing `"""` is found.
     fn triple_quoted_string_literal(&mut self, start: usize, skipped: Option<Skipped>) {
         let mut processed_content = String::new();
+        let mut interpolations = vec![];
 
         while let Some((end, ch)) = self.chars.next() {
             if ch == '"' {
@@ -632,12 +868,18 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
                             processed_content,
                             3,
                             false,
+                            interpolations,
                         );
                         return;
                     }
                 }
             } else if ch == '\\' {
                 self.escape_sequence(end, &mut processed_content);
+            } else if ch == '{' {
+                let Some(close) = self.skip_interpolation() else {
+                    break;
+                };
+                interpolations.push((end + 1, close));
             } else {
                 processed_content.push(ch);
             }
@@ -651,12 +893,97 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         );
     }
 
+    /// Skip past an interpolation `{...}` in a string literal, after the `{` has been consumed.
+    /// Tracks nested delimiters, string literals and character literals so that (e.g.) a `}` appearing
+    /// in a nested block, string or character literal does not end the interpolation.
+    /// Returns the index of the closing `}` or `None` if the input ended first.
+    fn skip_interpolation(&mut self) -> Option<usize> {
+        let mut depth = 0_usize;
+        while let Some((index, ch)) = self.chars.next() {
+            match ch {
+                '{' | '[' | '(' => depth += 1,
+                '}' if depth == 0 => return Some(index),
+                '}' | ']' | ')' => depth = depth.saturating_sub(1),
+                '"' => self.skip_string_literal(),
+                '\'' => self.skip_char_literal(),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Skip past a string literal nested inside of some other token, after the opening `"`
+    /// has been consumed. The nested literal will be tokenized properly when its
+    /// enclosing text is parsed.
+    fn skip_string_literal(&mut self) {
+        let mut triple = false;
+        if let Some(&(_, '"')) = self.chars.peek() {
+            self.chars.next();
+            if let Some(&(_, '"')) = self.chars.peek() {
+                self.chars.next();
+                triple = true;
+            } else {
+                return; // empty string `""`
+            }
+        }
+
+        while let Some((_, ch)) = self.chars.next() {
+            match ch {
+                '\\' => {
+                    self.chars.next();
+                }
+                '{' => {
+                    self.skip_interpolation();
+                }
+                '"' if !triple => return,
+                '"' => {
+                    let mut lookahead = self.chars.clone();
+                    if let (Some((_, '"')), Some((_, '"'))) = (lookahead.next(), lookahead.next()) {
+                        self.chars = lookahead;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip past a character literal nested inside of some other token, after the opening `'`
+    /// has been consumed. Like [`Self::char_literal`], the literal ends at the closing `'`
+    /// or (if it is unterminated) at the end of the line.
+    fn skip_char_literal(&mut self) {
+        while let Some(&(_, ch)) = self.chars.peek() {
+            if ch == '\n' {
+                return;
+            }
+
+            self.chars.next();
+            match ch {
+                '\'' => return,
+                '\\' => {
+                    self.chars.next_if(|&(_, ch)| ch != '\n');
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn delimited(&mut self, start: usize, delim: Delimiter, close: char) {
         let skipped = self.clear_accumulated(start);
         let mut close_stack = vec![close];
 
         while let Some((end, ch)) = self.chars.next() {
             match ch {
+                // Braces within strings, character literals and comments do not count.
+                '"' => self.skip_string_literal(),
+                '\'' => self.skip_char_literal(),
+                '#' => {
+                    for (_, ch) in &mut self.chars {
+                        if ch == '\n' {
+                            break;
+                        }
+                    }
+                }
                 '{' => close_stack.push('}'),
                 '[' => close_stack.push(']'),
                 '(' => close_stack.push(')'),
@@ -693,11 +1020,10 @@ impl<'input, 'db> Tokenizer<'input, 'db> {
         self.tokens.push(Token {
             span,
             skipped: None,
-            kind: TokenKind::Error(Diagnostic::error(
-                self.db,
-                span,
-                format!("missing `{close}`"),
-            )),
+            kind: TokenKind::Error(
+                Diagnostic::error(self.db, span, format!("missing `{close}`"))
+                    .code(DiagnosticCode::MissingClosingDelimiter),
+            ),
         });
     }
 
@@ -724,6 +1050,7 @@ pub fn is_op_char(ch: char) -> bool {
             | '>'
             | '&'
             | '|'
+            | '^'
             | ':'
             | ','
             | '.'
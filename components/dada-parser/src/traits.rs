@@ -0,0 +1,220 @@
+use dada_ir_ast::ast::{
+    AstAssociatedTy, AstFunction, AstGenericDecl, AstImpl, AstPath, AstTrait, AstTraitMember,
+    AstTy, AstVisibility, AstWhereClauses, SpanVec,
+};
+use dada_ir_ast::span::Span;
+use salsa::Update;
+
+use crate::{ParseFail, tokenizer::operator};
+
+use super::{
+    Expected, Parse, Parser,
+    miscellaneous::OrOptParse,
+    tokenizer::{Delimiter, Keyword},
+};
+
+/// trait Name[...] { ... }
+impl<'db> Parse<'db> for AstTrait<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, ParseFail<'db>> {
+        if !AstTraitPrefix::can_eat(db, parser) {
+            return Ok(None);
+        }
+
+        let start = parser.peek_span();
+
+        let AstTraitPrefix {
+            visibility,
+            trait_keyword: _,
+        } = AstTraitPrefix::eat(db, parser)?;
+
+        let id = parser.eat_id()?;
+
+        let generics = AstGenericDecl::opt_parse_delimited(
+            db,
+            parser,
+            Delimiter::SquareBrackets,
+            AstGenericDecl::eat_comma,
+        )?;
+
+        let where_clauses = AstWhereClauses::opt_parse(db, parser)?;
+
+        let body = parser.defer_delimited(Delimiter::CurlyBraces).ok();
+
+        Ok(Some(AstTrait::new(
+            db,
+            start.to(db, parser.last_span()),
+            visibility,
+            id.id,
+            id.span,
+            generics,
+            where_clauses,
+            body,
+        )))
+    }
+
+    fn expected() -> Expected {
+        Expected::Keyword(Keyword::Trait)
+    }
+}
+
+/// The *prefix* parses a trait declaration up until
+/// the `trait` keyword, like `AstAggregatePrefix` does for classes.
+#[derive(Update)]
+struct AstTraitPrefix<'db> {
+    /// Visibility of the trait
+    visibility: Option<AstVisibility<'db>>,
+    trait_keyword: Span<'db>,
+}
+
+impl<'db> Parse<'db> for AstTraitPrefix<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, ParseFail<'db>> {
+        let visibility = AstVisibility::opt_parse(db, parser)?;
+
+        if let Ok(span) = parser.eat_keyword(Keyword::Trait) {
+            Ok(Some(AstTraitPrefix {
+                visibility,
+                trait_keyword: span,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn expected() -> Expected {
+        Expected::Nonterminal("trait")
+    }
+}
+
+/// impl[...] Trait[...] for Ty { ... }
+impl<'db> Parse<'db> for AstImpl<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, ParseFail<'db>> {
+        let Ok(impl_span) = parser.eat_keyword(Keyword::Impl) else {
+            return Ok(None);
+        };
+
+        let generics = AstGenericDecl::opt_parse_delimited(
+            db,
+            parser,
+            Delimiter::SquareBrackets,
+            AstGenericDecl::eat_comma,
+        )?;
+
+        let trait_path = AstPath::eat(db, parser)?;
+
+        parser.eat_keyword(Keyword::For)?;
+
+        let self_ty = AstTy::eat(db, parser)?;
+
+        let where_clauses = AstWhereClauses::opt_parse(db, parser)?;
+
+        let body = parser.defer_delimited(Delimiter::CurlyBraces).ok();
+
+        Ok(Some(AstImpl::new(
+            db,
+            impl_span.to(db, parser.last_span()),
+            impl_span,
+            generics,
+            trait_path,
+            self_ty,
+            where_clauses,
+            body,
+        )))
+    }
+
+    fn expected() -> Expected {
+        Expected::Keyword(Keyword::Impl)
+    }
+}
+
+#[salsa::tracked]
+impl<'db> crate::prelude::TraitItemMembers<'db> for AstTrait<'db> {
+    #[salsa::tracked(return_ref)]
+    fn members(self, db: &'db dyn crate::Db) -> SpanVec<'db, AstTraitMember<'db>> {
+        if let Some(contents) = self.contents(db) {
+            Parser::deferred(db, self, contents, |parser| {
+                parser.parse_many_and_report_diagnostics::<AstTraitMember<'db>>(db)
+            })
+        } else {
+            SpanVec {
+                span: self.span(db).at_end(),
+                values: vec![],
+            }
+        }
+    }
+}
+
+#[salsa::tracked]
+impl<'db> crate::prelude::TraitItemMembers<'db> for AstImpl<'db> {
+    #[salsa::tracked(return_ref)]
+    fn members(self, db: &'db dyn crate::Db) -> SpanVec<'db, AstTraitMember<'db>> {
+        if let Some(contents) = self.contents(db) {
+            Parser::deferred(db, self, contents, |parser| {
+                parser.parse_many_and_report_diagnostics::<AstTraitMember<'db>>(db)
+            })
+        } else {
+            SpanVec {
+                span: self.span(db).at_end(),
+                values: vec![],
+            }
+        }
+    }
+}
+
+impl<'db> Parse<'db> for AstTraitMember<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, super::ParseFail<'db>> {
+        AstFunction::opt_parse(db, parser).or_opt_parse::<Self, AstAssociatedTy<'db>>(db, parser)
+    }
+
+    fn expected() -> Expected {
+        Expected::Nonterminal("trait member")
+    }
+}
+
+/// `type Name` or `type Name = Ty`
+impl<'db> Parse<'db> for AstAssociatedTy<'db> {
+    type Output = Self;
+
+    fn opt_parse(
+        db: &'db dyn crate::Db,
+        parser: &mut Parser<'_, 'db>,
+    ) -> Result<Option<Self>, super::ParseFail<'db>> {
+        let Ok(type_span) = parser.eat_keyword(Keyword::Type) else {
+            return Ok(None);
+        };
+
+        let name = parser.eat_id()?;
+
+        let value = AstTy::opt_parse_guarded(operator::EQ, db, parser)?;
+
+        Ok(Some(AstAssociatedTy::new(
+            db,
+            type_span.to(db, parser.last_span()),
+            name,
+            value,
+        )))
+    }
+
+    fn expected() -> Expected {
+        Expected::Nonterminal("associated type")
+    }
+}
@@ -2,21 +2,24 @@ use std::ops::ControlFlow;
 
 use dada_ir_ast::{
     ast::{
-        AstExpr, AstExprKind, AstItem, AstMember, AstPathKind, AstStatement, LiteralKind,
-        PermissionOp, SpannedIdentifier, UnaryOp,
+        AstBlock, AstExpr, AstExprKind, AstFunctionInput, AstGenericTerm, AstItem, AstLetStatement,
+        AstMember, AstPath, AstPathKind, AstPattern, AstPatternKind, AstStatement, AstStringPart,
+        AstTraitMember, AstTy, AstTyKind, Identifier, LiteralKind, PermissionOp, SpannedIdentifier,
+        UnaryOp,
     },
-    span::{AbsoluteSpan, SourceSpanned, Spanned},
+    span::{AbsoluteSpan, SourceSpanned, Span, Spanned},
 };
 pub use dada_ir_sym::Db;
 use dada_ir_sym::{
     ir::{
-        exprs::{SymExpr, SymExprKind},
-        functions::SymFunction,
+        exprs::{SymExpr, SymExprKind, SymPlaceExpr, SymPlaceExprKind},
+        functions::{SymFunction, SymFunctionSource},
         module::SymItem,
+        types::{SymTyKind, SymTyName},
     },
     prelude::{CheckedBody, Symbol},
 };
-use dada_parser::prelude::{ClassItemMembers, FunctionBlock, SourceFileParse};
+use dada_parser::prelude::{ClassItemMembers, FunctionBlock, SourceFileParse, TraitItemMembers};
 
 /// Probe for the type of an expression found in a given file at a given span.
 /// Returns the type of the smallest expression that contains the given span.
@@ -70,6 +73,221 @@ pub fn probe_variable_type<'db>(db: &'db dyn crate::Db, span: AbsoluteSpan) -> O
     })
 }
 
+/// Probe for the declaration referenced at a given span (e.g., for "go to definition").
+///
+/// Finds the smallest reference containing `span` -- a use of a local variable,
+/// a field access, the callee of a call, the class of a struct/class construction,
+/// a name in a type annotation, or the name imported by a `use` item -- and returns
+/// the span of the name in the corresponding declaration. The declaration may be in
+/// another file, including `libdada`.
+pub fn probe_definition<'db>(db: &'db dyn crate::Db, span: AbsoluteSpan) -> Option<AbsoluteSpan> {
+    let mut result = None;
+    let mut smallest_size = usize::MAX;
+
+    let mut consider = |use_span: AbsoluteSpan, definition: Span<'db>| {
+        if use_span.contains(span) {
+            let size = use_span.end.as_usize() - use_span.start.as_usize();
+            if size < smallest_size {
+                result = Some(definition.absolute_span(db));
+                smallest_size = size;
+            }
+        }
+    };
+
+    visit_exprs(db, span, &mut |expr| {
+        match expr.kind(db) {
+            SymExprKind::PermissionOp(_, place) | SymExprKind::Assign { place, value: _ } => {
+                visit_place_references(db, *place, &mut consider);
+            }
+            SymExprKind::Call {
+                function,
+                substitution: _,
+                arg_temps: _,
+            } => {
+                // Calls synthesized by the compiler (e.g., for string interpolation)
+                // have no callee in the source, so they are skipped.
+                if let Some(callee) = find_callee(db, expr, function.name(db)) {
+                    consider(callee.span.absolute_span(db), function.name_span(db));
+                }
+            }
+            SymExprKind::Aggregate { ty, fields: _ } => {
+                if let SymTyKind::Named(SymTyName::Aggregate(aggr), _) = ty.kind(db)
+                    && let Some(callee) = find_callee(db, expr, aggr.name(db))
+                {
+                    consider(callee.span.absolute_span(db), aggr.name_span(db));
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::<()>::Continue(())
+    });
+
+    result.or_else(|| probe_name_definition(db, span))
+}
+
+/// Finds the name of the callee of the call expression `expr` as written by the user,
+/// e.g., `foo` in `foo(x)` or `bar` in `x.bar()`. Returns `None` if there is no call in the source
+/// at the span of `expr` whose callee is named `name`, as is the case for calls synthesized
+/// by the compiler.
+fn find_callee<'db>(
+    db: &'db dyn crate::Db,
+    expr: SymExpr<'db>,
+    name: Identifier<'db>,
+) -> Option<SpannedIdentifier<'db>> {
+    let expr_span = expr.source_span(db).absolute_span(db);
+    let mut callee = None;
+    walk_ast_items(db, expr_span, &mut |node| {
+        if let AstNode::Expr(ast_expr) = node
+            && ast_expr.span.absolute_span(db) == expr_span
+        {
+            let id = match &*ast_expr.kind {
+                AstExprKind::ParenthesisOp(callee_expr, _) => callee_name(callee_expr),
+                AstExprKind::Constructor(path, _) => Some(path.last_id(db)),
+                _ => None,
+            };
+            if let Some(id) = id
+                && id.id == name
+            {
+                callee = Some(id);
+            }
+        }
+    });
+    callee
+}
+
+/// The name of the function or class that `callee_expr` refers to, e.g., `bar` in `x.bar`.
+fn callee_name<'db>(callee_expr: &AstExpr<'db>) -> Option<SpannedIdentifier<'db>> {
+    match &*callee_expr.kind {
+        AstExprKind::Id(id) | AstExprKind::DotId(_, id) => Some(*id),
+        AstExprKind::SquareBracketOp(callee_expr, _) => callee_name(callee_expr),
+        _ => None,
+    }
+}
+
+/// Probe for the declaration of a name found outside of expressions:
+/// in a type annotation (e.g., `Point` in `let p: Point` or in a parameter type)
+/// or imported by a `use` item.
+fn probe_name_definition<'db>(db: &'db dyn crate::Db, span: AbsoluteSpan) -> Option<AbsoluteSpan> {
+    let contains = |id: &SpannedIdentifier<'db>| id.span.absolute_span(db).contains(span);
+
+    // `use` items bind their last identifier (or the `as` name) in the module.
+    let module = span.source_file.symbol(db);
+    for item in &span.source_file.parse(db).items(db).values {
+        if let AstItem::Use(ast_use) = item
+            && ast_use.span(db).absolute_span(db).contains(span)
+        {
+            let last_id = ast_use.path(db).last_id(db);
+            let bound_id = ast_use.as_id(db).unwrap_or(last_id);
+            if contains(&last_id) || contains(&bound_id) {
+                let definition = module.mod_scope(db).definition_span(db, bound_id.id)?;
+                return Some(definition.absolute_span(db));
+            }
+            return None;
+        }
+    }
+
+    // Otherwise, look for type annotations in the signature and body of the enclosing function.
+    let func = find_func(db, span)?;
+    let mut tys = vec![];
+    match func.source(db) {
+        SymFunctionSource::Function(ast_function) => {
+            for input in &ast_function.inputs(db).values {
+                if let AstFunctionInput::Variable(decl) = input {
+                    tys.push(decl.base_ty(db));
+                }
+            }
+            tys.extend(ast_function.output_ty(db));
+        }
+        SymFunctionSource::MainFunction(_)
+        | SymFunctionSource::Constructor(..)
+        | SymFunctionSource::VariantConstructor(_) => {}
+    }
+    walk_ast_items(db, span, &mut |node| {
+        if let AstNode::Let(let_stmt) = node {
+            tys.extend(let_stmt.ty(db));
+        }
+    });
+
+    let id = tys.into_iter().find_map(|ty| ty_name_at(db, ty, span))?;
+    let definition = func.scope(db).definition_span(db, id.id)?;
+    Some(definition.absolute_span(db))
+}
+
+/// The identifier at `span` in the type `ty`, if it names a type (e.g., `Point` in `my Point`
+/// or in `Vec[Point]`). Only paths consisting of a single identifier are considered.
+fn ty_name_at<'db>(
+    db: &'db dyn crate::Db,
+    ty: AstTy<'db>,
+    span: AbsoluteSpan,
+) -> Option<SpannedIdentifier<'db>> {
+    if !ty.span(db).absolute_span(db).contains(span) {
+        return None;
+    }
+
+    match ty.kind(db) {
+        AstTyKind::Perm(_, ty) => ty_name_at(db, ty, span),
+        AstTyKind::Named(path, args) => path_name_at(db, path, span).or_else(|| {
+            args.iter()
+                .flat_map(|args| args.values.iter())
+                .find_map(|arg| generic_term_name_at(db, arg, span))
+        }),
+        AstTyKind::GenericDecl(_) => None,
+    }
+}
+
+/// Like [`ty_name_at`] but for a path.
+fn path_name_at<'db>(
+    db: &'db dyn crate::Db,
+    path: AstPath<'db>,
+    span: AbsoluteSpan,
+) -> Option<SpannedIdentifier<'db>> {
+    match path.kind(db) {
+        AstPathKind::Identifier(id) if id.span.absolute_span(db).contains(span) => Some(*id),
+        AstPathKind::Identifier(_) | AstPathKind::Member { .. } => None,
+        AstPathKind::GenericArgs { path, args } => path_name_at(db, *path, span).or_else(|| {
+            args.values
+                .iter()
+                .find_map(|arg| generic_term_name_at(db, arg, span))
+        }),
+    }
+}
+
+/// Like [`ty_name_at`] but for a generic argument.
+fn generic_term_name_at<'db>(
+    db: &'db dyn crate::Db,
+    term: &AstGenericTerm<'db>,
+    span: AbsoluteSpan,
+) -> Option<SpannedIdentifier<'db>> {
+    match term {
+        AstGenericTerm::Ty(ty) => ty_name_at(db, *ty, span),
+        AstGenericTerm::Id(id) if id.span.absolute_span(db).contains(span) => Some(*id),
+        AstGenericTerm::Id(_) | AstGenericTerm::Perm(_) => None,
+    }
+}
+
+/// Invoke `op` with the span of each reference in `place` and the span of what it refers to.
+fn visit_place_references<'db>(
+    db: &'db dyn crate::Db,
+    place: SymPlaceExpr<'db>,
+    op: &mut dyn FnMut(AbsoluteSpan, Span<'db>),
+) {
+    let place_span = place.span(db).absolute_span(db);
+    match place.kind(db) {
+        SymPlaceExprKind::Var(lv) => {
+            // Unnamed variables are temporaries introduced by the type checker.
+            if lv.name(db).is_some() {
+                op(place_span, lv.span(db));
+            }
+        }
+        SymPlaceExprKind::Field(owner, field) => {
+            visit_place_references(db, *owner, op);
+            op(place_span, field.name_span(db));
+        }
+        SymPlaceExprKind::Index(owner, _) => visit_place_references(db, *owner, op),
+        SymPlaceExprKind::Error(_) => {}
+    }
+}
+
 /// Probe for the compact AST representation of the expression at a given span.
 ///
 /// # Example
@@ -98,10 +316,21 @@ fn find_item<'db>(db: &'db dyn crate::Db, span: AbsoluteSpan) -> Option<SymItem<
 
 /// Find the fn or method containing `span`
 fn find_func<'db>(db: &'db dyn crate::Db, span: AbsoluteSpan) -> Option<SymFunction<'db>> {
+    let contains = |m: &SymFunction<'db>| m.source_span(db).absolute_span(db).contains(span);
+
+    // Impls are not items, so check them first.
+    let module = span.source_file.symbol(db);
+    if let Some(sym_impl) = module
+        .impls(db)
+        .iter()
+        .find(|i| i.source_span(db).absolute_span(db).contains(span))
+    {
+        return sym_impl.methods(db).iter().copied().find(contains);
+    }
+
     match find_item(db, span)? {
-        SymItem::SymClass(aggr) => aggr
-            .methods(db)
-            .find(|m| m.source_span(db).absolute_span(db).contains(span)),
+        SymItem::SymClass(aggr) => aggr.methods(db).find(contains),
+        SymItem::SymTrait(sym_trait) => sym_trait.methods(db).iter().copied().find(contains),
         SymItem::SymFunction(func) => Some(func),
         SymItem::SymPrimitive(_) => None,
     }
@@ -170,10 +399,19 @@ fn walk_expr_and_visit<'db, B>(
         SymExprKind::Not {
             operand,
             op_span: _,
+        }
+        | SymExprKind::Negate {
+            operand,
+            op_span: _,
         } => walk_expr_and_visit(db, *operand, span, op),
         SymExprKind::BinaryOp(_, lhs, rhs) => walk_expr_and_visit(db, *lhs, span, op)
             .or_else(|| walk_expr_and_visit(db, *rhs, span, op)),
-        SymExprKind::Aggregate { ty: _, fields } => {
+        SymExprKind::Aggregate { ty: _, fields }
+        | SymExprKind::Variant {
+            ty: _,
+            variant: _,
+            fields,
+        } => {
             for &field in fields {
                 if let Some(b) = walk_expr_and_visit(db, field, span, op) {
                     return Some(b);
@@ -181,6 +419,10 @@ fn walk_expr_and_visit<'db, B>(
             }
             None
         }
+        SymExprKind::IsVariant {
+            place: _,
+            variant: _,
+        } => None,
         SymExprKind::Match { arms } => {
             for arm in arms {
                 if let Some(b) = walk_expr_and_visit(db, arm.body, span, op) {
@@ -189,6 +431,8 @@ fn walk_expr_and_visit<'db, B>(
             }
             None
         }
+        SymExprKind::Loop { body } => walk_expr_and_visit(db, *body, span, op),
+        SymExprKind::Break | SymExprKind::Continue => None,
         SymExprKind::Error(_) => None,
     }
 }
@@ -203,22 +447,49 @@ fn find_smallest_containing_ast_expr<'db>(
     db: &'db dyn crate::Db,
     target: AbsoluteSpan,
 ) -> Option<AstExpr<'db>> {
-    let module = target.source_file.parse(db);
     let mut best: Option<AstExpr<'db>> = None;
     let mut best_size = usize::MAX;
 
+    walk_ast_items(db, target, &mut |node| {
+        if let AstNode::Expr(expr) = node {
+            let expr_abs = expr.span.absolute_span(db);
+            let size = expr_abs.end.as_usize() - expr_abs.start.as_usize();
+            if size < best_size {
+                best = Some(expr.clone());
+                best_size = size;
+            }
+        }
+    });
+
+    best
+}
+
+/// A node of the parser AST visited by [`walk_ast_items`].
+enum AstNode<'a, 'db> {
+    Expr(&'a AstExpr<'db>),
+    Let(AstLetStatement<'db>),
+}
+
+/// Walk the expressions and `let` statements in function bodies that contain the target span,
+/// outermost first, and invoke `op` on each of them.
+fn walk_ast_items<'db>(
+    db: &'db dyn crate::Db,
+    target: AbsoluteSpan,
+    op: &mut dyn FnMut(AstNode<'_, 'db>),
+) {
+    let module = target.source_file.parse(db);
     for item in &module.items(db).values {
         match item {
             AstItem::Function(func) => {
                 if let Some(block) = func.body_block(db) {
                     for stmt in &block.statements(db).values {
-                        walk_ast_statement(db, stmt, target, &mut best, &mut best_size);
+                        walk_ast_statement(db, stmt, target, op);
                     }
                 }
             }
             AstItem::MainFunction(main_fn) => {
                 for stmt in &main_fn.statements(db).values {
-                    walk_ast_statement(db, stmt, target, &mut best, &mut best_size);
+                    walk_ast_statement(db, stmt, target, op);
                 }
             }
             AstItem::Aggregate(aggr) => {
@@ -227,33 +498,45 @@ fn find_smallest_containing_ast_expr<'db>(
                         && let Some(block) = func.body_block(db)
                     {
                         for stmt in &block.statements(db).values {
-                            walk_ast_statement(db, stmt, target, &mut best, &mut best_size);
+                            walk_ast_statement(db, stmt, target, op);
                         }
                     }
                 }
             }
-            AstItem::SourceFile(_) | AstItem::Use(_) => {}
+            AstItem::Impl(ast_impl) => {
+                for member in &ast_impl.members(db).values {
+                    if let AstTraitMember::Function(func) = member
+                        && let Some(block) = func.body_block(db)
+                    {
+                        for stmt in &block.statements(db).values {
+                            walk_ast_statement(db, stmt, target, op);
+                        }
+                    }
+                }
+            }
+            AstItem::SourceFile(_) | AstItem::Use(_) | AstItem::Trait(_) => {}
         }
     }
-
-    best
 }
 
 fn walk_ast_statement<'db>(
     db: &'db dyn crate::Db,
     stmt: &AstStatement<'db>,
     target: AbsoluteSpan,
-    best: &mut Option<AstExpr<'db>>,
-    best_size: &mut usize,
+    op: &mut dyn FnMut(AstNode<'_, 'db>),
 ) {
     match stmt {
         AstStatement::Let(let_stmt) => {
+            if !let_stmt.span(db).absolute_span(db).contains(target) {
+                return;
+            }
+            op(AstNode::Let(*let_stmt));
             if let Some(init) = let_stmt.initializer(db) {
-                walk_ast_expr(db, &init, target, best, best_size);
+                walk_ast_expr(db, &init, target, op);
             }
         }
         AstStatement::Expr(expr) => {
-            walk_ast_expr(db, expr, target, best, best_size);
+            walk_ast_expr(db, expr, target, op);
         }
     }
 }
@@ -262,78 +545,100 @@ fn walk_ast_expr<'db>(
     db: &'db dyn crate::Db,
     expr: &AstExpr<'db>,
     target: AbsoluteSpan,
-    best: &mut Option<AstExpr<'db>>,
-    best_size: &mut usize,
+    op: &mut dyn FnMut(AstNode<'_, 'db>),
 ) {
     let expr_abs = expr.span.absolute_span(db);
     if !expr_abs.contains(target) {
         return;
     }
 
-    let size = expr_abs.end.as_usize() - expr_abs.start.as_usize();
-    if size < *best_size {
-        *best = Some(expr.clone());
-        *best_size = size;
-    }
+    op(AstNode::Expr(expr));
 
     // Recurse into children
     match &*expr.kind {
-        AstExprKind::Literal(_) | AstExprKind::Id(_) => {}
+        AstExprKind::Literal(_)
+        | AstExprKind::Id(_)
+        | AstExprKind::Break
+        | AstExprKind::Continue => {}
+        AstExprKind::InterpolatedString(parts) => {
+            for part in parts {
+                if let AstStringPart::Expr(sub_expr) = part {
+                    walk_ast_expr(db, sub_expr, target, op);
+                }
+            }
+        }
         AstExprKind::Block(block) => {
             for stmt in &block.statements(db).values {
-                walk_ast_statement(db, stmt, target, best, best_size);
+                walk_ast_statement(db, stmt, target, op);
             }
         }
         AstExprKind::DotId(sub_expr, _) => {
-            walk_ast_expr(db, sub_expr, target, best, best_size);
+            walk_ast_expr(db, sub_expr, target, op);
         }
         AstExprKind::SquareBracketOp(sub_expr, _) => {
-            walk_ast_expr(db, sub_expr, target, best, best_size);
+            walk_ast_expr(db, sub_expr, target, op);
         }
         AstExprKind::ParenthesisOp(callee, args) => {
-            walk_ast_expr(db, callee, target, best, best_size);
+            walk_ast_expr(db, callee, target, op);
             for arg in &args.values {
-                walk_ast_expr(db, arg, target, best, best_size);
+                walk_ast_expr(db, arg, target, op);
             }
         }
         AstExprKind::Tuple(elems) => {
             for elem in &elems.values {
-                walk_ast_expr(db, elem, target, best, best_size);
+                walk_ast_expr(db, elem, target, op);
             }
         }
         AstExprKind::Constructor(_, fields) => {
             for field in &fields.values {
-                walk_ast_expr(db, &field.value, target, best, best_size);
+                walk_ast_expr(db, &field.value, target, op);
             }
         }
         AstExprKind::Return(opt_expr) => {
             if let Some(sub_expr) = opt_expr {
-                walk_ast_expr(db, sub_expr, target, best, best_size);
+                walk_ast_expr(db, sub_expr, target, op);
             }
         }
         AstExprKind::Await { future, .. } => {
-            walk_ast_expr(db, future, target, best, best_size);
+            walk_ast_expr(db, future, target, op);
         }
         AstExprKind::PermissionOp { value, .. } => {
-            walk_ast_expr(db, value, target, best, best_size);
+            walk_ast_expr(db, value, target, op);
         }
         AstExprKind::BinaryOp(_, lhs, rhs) => {
-            walk_ast_expr(db, lhs, target, best, best_size);
-            walk_ast_expr(db, rhs, target, best, best_size);
+            walk_ast_expr(db, lhs, target, op);
+            walk_ast_expr(db, rhs, target, op);
         }
         AstExprKind::UnaryOp(_, sub_expr) => {
-            walk_ast_expr(db, sub_expr, target, best, best_size);
+            walk_ast_expr(db, sub_expr, target, op);
         }
         AstExprKind::If(arms) => {
             for arm in arms {
                 if let Some(cond) = &arm.condition {
-                    walk_ast_expr(db, cond, target, best, best_size);
+                    walk_ast_expr(db, cond, target, op);
                 }
                 for stmt in &arm.result.statements(db).values {
-                    walk_ast_statement(db, stmt, target, best, best_size);
+                    walk_ast_statement(db, stmt, target, op);
                 }
             }
         }
+        AstExprKind::Match { scrutinee, arms } => {
+            walk_ast_expr(db, scrutinee, target, op);
+            for arm in arms {
+                walk_ast_expr(db, &arm.result, target, op);
+            }
+        }
+        AstExprKind::Loop(body) | AstExprKind::Unsafe(body) => {
+            for stmt in &body.statements(db).values {
+                walk_ast_statement(db, stmt, target, op);
+            }
+        }
+        AstExprKind::While { condition, body } => {
+            walk_ast_expr(db, condition, target, op);
+            for stmt in &body.statements(db).values {
+                walk_ast_statement(db, stmt, target, op);
+            }
+        }
     }
 }
 
@@ -357,6 +662,8 @@ fn format_ast_expr<'db>(db: &'db dyn crate::Db, expr: &AstExpr<'db>, buf: &mut S
             let kind = match lit.kind(db) {
                 LiteralKind::Boolean => "Boolean",
                 LiteralKind::Integer => "Integer",
+                LiteralKind::Float => "Float",
+                LiteralKind::Char => "Char",
                 LiteralKind::String => "String",
             };
             buf.push_str("Literal(");
@@ -365,6 +672,23 @@ fn format_ast_expr<'db>(db: &'db dyn crate::Db, expr: &AstExpr<'db>, buf: &mut S
             escape_string_into(lit.text(db), buf);
             buf.push_str("\")");
         }
+        AstExprKind::InterpolatedString(parts) => {
+            buf.push_str("InterpolatedString([");
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(", ");
+                }
+                match part {
+                    AstStringPart::Text(text) => {
+                        buf.push('"');
+                        escape_string_into(text, buf);
+                        buf.push('"');
+                    }
+                    AstStringPart::Expr(sub_expr) => format_ast_expr(db, sub_expr, buf),
+                }
+            }
+            buf.push_str("])");
+        }
         AstExprKind::Id(spanned_id) => {
             buf.push_str("Id(");
             format_identifier(db, spanned_id, buf);
@@ -497,7 +821,51 @@ fn format_ast_expr<'db>(db: &'db dyn crate::Db, expr: &AstExpr<'db>, buf: &mut S
             }
             buf.push_str("])");
         }
+        AstExprKind::Match { scrutinee, arms } => {
+            buf.push_str("Match(");
+            format_ast_expr(db, scrutinee, buf);
+            buf.push_str(", [");
+            for (i, arm) in arms.iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(", ");
+                }
+                format_ast_pattern(db, &arm.pattern, buf);
+                buf.push_str(" => ");
+                format_ast_expr(db, &arm.result, buf);
+            }
+            buf.push_str("])");
+        }
+        AstExprKind::Loop(body) => {
+            buf.push_str("Loop(");
+            format_ast_block(db, body, buf);
+            buf.push(')');
+        }
+        AstExprKind::Unsafe(body) => {
+            buf.push_str("Unsafe(");
+            format_ast_block(db, body, buf);
+            buf.push(')');
+        }
+        AstExprKind::While { condition, body } => {
+            buf.push_str("While(");
+            format_ast_expr(db, condition, buf);
+            buf.push_str(", ");
+            format_ast_block(db, body, buf);
+            buf.push(')');
+        }
+        AstExprKind::Break => buf.push_str("Break"),
+        AstExprKind::Continue => buf.push_str("Continue"),
+    }
+}
+
+fn format_ast_block<'db>(db: &'db dyn crate::Db, block: &AstBlock<'db>, buf: &mut String) {
+    buf.push_str("Block([");
+    for (i, stmt) in block.statements(db).values.iter().enumerate() {
+        if i > 0 {
+            buf.push_str(", ");
+        }
+        format_ast_statement(db, stmt, buf);
     }
+    buf.push_str("])");
 }
 
 fn format_ast_statement<'db>(db: &'db dyn crate::Db, stmt: &AstStatement<'db>, buf: &mut String) {
@@ -517,6 +885,26 @@ fn format_ast_statement<'db>(db: &'db dyn crate::Db, stmt: &AstStatement<'db>, b
     }
 }
 
+fn format_ast_pattern(db: &dyn crate::Db, pattern: &AstPattern<'_>, buf: &mut String) {
+    match &pattern.kind {
+        AstPatternKind::Wildcard => buf.push('_'),
+        AstPatternKind::Binding(spanned_id) => format_identifier(db, spanned_id, buf),
+        AstPatternKind::Variant(path, sub_patterns) => {
+            format_ast_path(db, path, buf);
+            if let Some(sub_patterns) = sub_patterns {
+                buf.push('(');
+                for (i, sub_pattern) in sub_patterns.iter().enumerate() {
+                    if i > 0 {
+                        buf.push_str(", ");
+                    }
+                    format_ast_pattern(db, sub_pattern, buf);
+                }
+                buf.push(')');
+            }
+        }
+    }
+}
+
 fn format_identifier(db: &dyn crate::Db, id: &SpannedIdentifier<'_>, buf: &mut String) {
     buf.push_str(id.id.text(db));
 }
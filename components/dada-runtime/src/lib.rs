@@ -0,0 +1,284 @@
+//! Executes the WebAssembly modules produced by `dada-codegen`.
+//!
+//! Modules are run in-process using the [`wasmi`] interpreter.
+//!
+//! # Runtime ABI
+//!
+//! The generated module is expected to export:
+//!
+//! * a function named [`MAIN_EXPORT`] that takes the initial stack pointer
+//!   as its only argument and returns nothing;
+//! * its linear memory, named [`MEMORY_EXPORT`];
+//! * an immutable `i32` global named [`STACK_START_EXPORT`] giving the initial stack pointer;
+//! * a function named [`ALLOC_EXPORT`] that takes a size in bytes, allocates that many bytes
+//!   of linear memory, and returns their address.
+//!
+//! In return, we provide the following functions in the [`HOST_MODULE`] module.
+//! Strings are passed as the address and length of their UTF-8 data in linear memory;
+//! strings that we return are allocated with [`ALLOC_EXPORT`].
+//!
+//! * `print(data: i32, length: i32)`: writes the string to stdout, followed by a newline.
+//! * `concat(data1: i32, length1: i32, data2: i32, length2: i32) -> (i32, i32)`:
+//!   returns the concatenation of two strings.
+//! * `bool_to_string(value: i32) -> (i32, i32)`, `char_to_string(value: i32) -> (i32, i32)`,
+//!   `i32_to_string(value: i32) -> (i32, i32)`, `u32_to_string(value: i32) -> (i32, i32)`,
+//!   `i64_to_string(value: i64) -> (i32, i32)`, `u64_to_string(value: i64) -> (i32, i32)`,
+//!   `f32_to_string(value: f32) -> (i32, i32)`, `f64_to_string(value: f64) -> (i32, i32)`:
+//!   return the string representation of a primitive value. Integers smaller than 32 bits
+//!   are passed as an `i32` or `u32`; booleans as `0` or `1`; characters as their code point.
+//!
+//! The codegen side of this ABI is in `dada-codegen` and must be kept in sync.
+
+use dada_util::{Context, Fallible, bail};
+use wasmi::{Caller, Config, Engine, Extern, Linker, Memory, Module, Store, core::TrapCode};
+
+/// Name of the exported function that we invoke to start the program.
+pub const MAIN_EXPORT: &str = "main";
+
+/// Name of the exported linear memory.
+pub const MEMORY_EXPORT: &str = "memory";
+
+/// Name of the exported global holding the initial stack pointer.
+pub const STACK_START_EXPORT: &str = "stack_start";
+
+/// Name of the exported function that allocates linear memory.
+pub const ALLOC_EXPORT: &str = "alloc";
+
+/// Module name under which host functions are imported.
+pub const HOST_MODULE: &str = "dada";
+
+/// The result of executing a Dada program.
+#[derive(Debug)]
+pub struct RunOutput {
+    /// Everything the program wrote to its standard output.
+    pub stdout: String,
+
+    /// How the program terminated.
+    pub status: ExitStatus,
+}
+
+/// How a Dada program terminated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// `main` returned normally.
+    Success,
+
+    /// Execution trapped, e.g. because it reached code that failed to compile.
+    /// The string is the message reported by the interpreter.
+    Trap(String),
+
+    /// Execution used up the fuel it was given (see [`run`]),
+    /// e.g. because the program does not terminate.
+    OutOfFuel,
+}
+
+impl ExitStatus {
+    /// The process exit code corresponding to this status.
+    pub fn code(&self) -> i32 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Trap(_) | ExitStatus::OutOfFuel => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitStatus::Success => write!(f, "exit status 0"),
+            ExitStatus::Trap(message) => write!(f, "trapped: {message}"),
+            ExitStatus::OutOfFuel => write!(f, "out of fuel"),
+        }
+    }
+}
+
+/// State accessible to the host functions that we provide to the module.
+#[derive(Default)]
+struct HostState {
+    /// Accumulated output of the program.
+    stdout: String,
+}
+
+/// Instantiate `wasm_bytes`, invoke its `main` function, and capture the result.
+///
+/// If `fuel` is `Some`, execution stops with [`ExitStatus::OutOfFuel`] once that much
+/// fuel has been consumed (roughly one unit per executed instruction).
+///
+/// # Returns
+///
+/// * `Err(e)` if the module could not be loaded or instantiated.
+/// * `Ok(output)` otherwise; note that a program that traps still yields `Ok`,
+///   with the trap recorded in [`RunOutput::status`].
+pub fn run(wasm_bytes: &[u8], fuel: Option<u64>) -> Fallible<RunOutput> {
+    let mut config = Config::default();
+    config.consume_fuel(fuel.is_some());
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm_bytes).context("loading generated wasm module")?;
+    let mut store = Store::new(&engine, HostState::default());
+    if let Some(fuel) = fuel {
+        store
+            .set_fuel(fuel)
+            .expect("fuel metering is enabled when `fuel` is given");
+    }
+
+    let mut linker = Linker::<HostState>::new(&engine);
+    linker.func_wrap(HOST_MODULE, "print", host_print)?;
+    linker.func_wrap(HOST_MODULE, "concat", host_concat)?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "bool_to_string",
+        |caller: Caller<'_, HostState>, value: i32| host_to_string(caller, value != 0),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "char_to_string",
+        |caller: Caller<'_, HostState>, value: i32| {
+            let value = char::from_u32(value as u32).ok_or_else(|| {
+                wasmi::Error::new(format!("`char_to_string` given invalid character: {value}"))
+            })?;
+            host_to_string(caller, value)
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "i32_to_string",
+        |caller: Caller<'_, HostState>, value: i32| host_to_string(caller, value),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "u32_to_string",
+        |caller: Caller<'_, HostState>, value: i32| host_to_string(caller, value as u32),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "i64_to_string",
+        |caller: Caller<'_, HostState>, value: i64| host_to_string(caller, value),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "u64_to_string",
+        |caller: Caller<'_, HostState>, value: i64| host_to_string(caller, value as u64),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "f32_to_string",
+        |caller: Caller<'_, HostState>, value: f32| host_to_string(caller, value),
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "f64_to_string",
+        |caller: Caller<'_, HostState>, value: f64| host_to_string(caller, value),
+    )?;
+
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .context("instantiating generated wasm module")?
+        .start(&mut store)
+        .context("starting generated wasm module")?;
+
+    let main = instance
+        .get_typed_func::<i32, ()>(&store, MAIN_EXPORT)
+        .with_context(|| format!("generated module has no `{MAIN_EXPORT}` function"))?;
+
+    let stack_start = match instance.get_global(&store, STACK_START_EXPORT) {
+        Some(global) => match global.get(&store).i32() {
+            Some(v) => v,
+            None => bail!("`{STACK_START_EXPORT}` is not an `i32` global"),
+        },
+        None => bail!("generated module has no `{STACK_START_EXPORT}` global"),
+    };
+
+    let status = match main.call(&mut store, stack_start) {
+        Ok(()) => ExitStatus::Success,
+        Err(error) if error.as_trap_code() == Some(TrapCode::OutOfFuel) => ExitStatus::OutOfFuel,
+        Err(error) => ExitStatus::Trap(error.to_string()),
+    };
+
+    Ok(RunOutput {
+        stdout: std::mem::take(&mut store.data_mut().stdout),
+        status,
+    })
+}
+
+/// Implementation of `print`: see the [runtime ABI](crate#runtime-abi).
+fn host_print(
+    mut caller: Caller<'_, HostState>,
+    data: i32,
+    length: i32,
+) -> Result<(), wasmi::Error> {
+    let string = read_string(&caller, "print", data, length)?;
+
+    let stdout = &mut caller.data_mut().stdout;
+    stdout.push_str(&string);
+    stdout.push('\n');
+    Ok(())
+}
+
+/// Implementation of `concat`: see the [runtime ABI](crate#runtime-abi).
+fn host_concat(
+    mut caller: Caller<'_, HostState>,
+    data1: i32,
+    length1: i32,
+    data2: i32,
+    length2: i32,
+) -> Result<(i32, i32), wasmi::Error> {
+    let mut string = read_string(&caller, "concat", data1, length1)?;
+    string.push_str(&read_string(&caller, "concat", data2, length2)?);
+    write_string(&mut caller, &string)
+}
+
+/// Implementation of the `*_to_string` functions: see the [runtime ABI](crate#runtime-abi).
+fn host_to_string(
+    mut caller: Caller<'_, HostState>,
+    value: impl std::fmt::Display,
+) -> Result<(i32, i32), wasmi::Error> {
+    write_string(&mut caller, &value.to_string())
+}
+
+/// Reads the string with the given `data` and `length` from linear memory.
+/// `host_fn` is the name of the host function that was given the string, used in errors.
+fn read_string(
+    caller: &Caller<'_, HostState>,
+    host_fn: &str,
+    data: i32,
+    length: i32,
+) -> Result<String, wasmi::Error> {
+    let mut bytes = vec![0; length as u32 as usize];
+    memory(caller)?
+        .read(caller, data as u32 as usize, &mut bytes)
+        .map_err(|e| wasmi::Error::new(format!("`{host_fn}` given invalid string: {e}")))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Allocates space for `string` in linear memory (using the function exported as [`ALLOC_EXPORT`]),
+/// copies it there, and returns its data and length.
+fn write_string(
+    caller: &mut Caller<'_, HostState>,
+    string: &str,
+) -> Result<(i32, i32), wasmi::Error> {
+    let Some(Extern::Func(alloc)) = caller.get_export(ALLOC_EXPORT) else {
+        return Err(wasmi::Error::new(format!(
+            "generated module has no `{ALLOC_EXPORT}` export"
+        )));
+    };
+    let length = i32::try_from(string.len())
+        .map_err(|_| wasmi::Error::new("string too large for linear memory"))?;
+    let data = alloc
+        .typed::<i32, i32>(&*caller)?
+        .call(&mut *caller, length)?;
+
+    memory(caller)?
+        .write(&mut *caller, data as u32 as usize, string.as_bytes())
+        .map_err(|e| wasmi::Error::new(format!("`{ALLOC_EXPORT}` returned invalid memory: {e}")))?;
+    Ok((data, length))
+}
+
+/// The linear memory exported by the module.
+fn memory(caller: &Caller<'_, HostState>) -> Result<Memory, wasmi::Error> {
+    match caller.get_export(MEMORY_EXPORT) {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(wasmi::Error::new(format!(
+            "generated module has no `{MEMORY_EXPORT}` export"
+        ))),
+    }
+}
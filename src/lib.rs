@@ -37,6 +37,7 @@
 //! - [`dada_ir_sym`](../dada_ir_sym) - Symbolic IR and type checking
 //! - [`dada_check`](../dada_check) - Type checking orchestration
 //! - [`dada_codegen`](../dada_codegen) - WebAssembly code generation
+//! - [`dada_runtime`](../dada_runtime) - Executes generated WebAssembly (`dada run`)
 //! - [`dada_compiler`](../dada_compiler) - Compilation orchestration
 //!
 //! For the complete documentation, visit [`dada_lang`](../dada_lang).